@@ -0,0 +1,106 @@
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const CHECKS_FILE_RELATIVE_PATH: &str = ".thence/checks.json";
+
+/// Checks approved by an earlier run, reused when neither `--checks` nor `[checks].commands`
+/// is set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChecksFile {
+    pub commands: Vec<String>,
+    /// Where the commands came from (`cli`, `config`, `proposer`, `human`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+pub fn checks_file_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(CHECKS_FILE_RELATIVE_PATH)
+}
+
+pub fn load_checks_file(repo_root: &Path) -> Result<Option<ChecksFile>> {
+    let path = checks_file_path(repo_root);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("read checks file {}", path.display()))?;
+    let file: ChecksFile =
+        serde_json::from_str(&raw).with_context(|| format!("parse {}", path.display()))?;
+    if file.commands.iter().all(|c| c.trim().is_empty()) {
+        bail!("{} has no commands", path.display());
+    }
+    Ok(Some(file))
+}
+
+/// Read a shared check list given with `--checks-from-file`: JSON `{"commands": [...]}`, or
+/// TOML `commands = [...]` when the file ends in `.toml`.
+pub fn load_checks_list(path: &Path) -> Result<Vec<String>> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("read checks file {}", path.display()))?;
+    let file: ChecksFile = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&raw).with_context(|| format!("parse {}", path.display()))?
+    } else {
+        serde_json::from_str(&raw).with_context(|| format!("parse {}", path.display()))?
+    };
+    Ok(file.commands)
+}
+
+pub fn save_checks_file(repo_root: &Path, commands: &[String], source: &str) -> Result<PathBuf> {
+    let path = checks_file_path(repo_root);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create checks file dir {}", parent.display()))?;
+    }
+    let file = ChecksFile {
+        commands: commands.to_vec(),
+        source: Some(source.to_string()),
+    };
+    std::fs::write(&path, serde_json::to_string_pretty(&file)? + "\n")
+        .with_context(|| format!("write checks file {}", path.display()))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn saved_checks_round_trip_and_empty_files_are_rejected() {
+        let tmp = tempdir().unwrap();
+        assert!(load_checks_file(tmp.path()).unwrap().is_none());
+
+        let commands = vec!["cargo test".to_string(), "cargo clippy".to_string()];
+        save_checks_file(tmp.path(), &commands, "proposer").unwrap();
+        let loaded = load_checks_file(tmp.path()).unwrap().unwrap();
+        assert_eq!(loaded.commands, commands);
+        assert_eq!(loaded.source.as_deref(), Some("proposer"));
+
+        std::fs::write(checks_file_path(tmp.path()), r#"{"commands":[]}"#).unwrap();
+        let err = load_checks_file(tmp.path()).unwrap_err();
+        assert!(format!("{err}").contains("has no commands"));
+    }
+
+    #[test]
+    fn checks_lists_parse_as_json_or_toml_by_extension() {
+        let tmp = tempdir().unwrap();
+        let json_path = tmp.path().join("checks.json");
+        std::fs::write(&json_path, r#"{"commands":["cargo test"]}"#).unwrap();
+        assert_eq!(load_checks_list(&json_path).unwrap(), vec!["cargo test"]);
+
+        let toml_path = tmp.path().join("checks.toml");
+        std::fs::write(
+            &toml_path,
+            "commands = [\"cargo fmt --check\", \"cargo test\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            load_checks_list(&toml_path).unwrap(),
+            vec!["cargo fmt --check", "cargo test"]
+        );
+
+        std::fs::write(&toml_path, r#"{"commands":["cargo test"]}"#).unwrap();
+        assert!(load_checks_list(&toml_path).is_err());
+    }
+}
@@ -1,48 +1,385 @@
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::path::Path;
-use std::process::Command;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CheckSpec {
+    pub command: String,
+    /// Overrides the run-wide check timeout for this command.
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Directory relative to the worktree to run the command in; validated at config load.
+    #[serde(default)]
+    pub workdir: Option<PathBuf>,
+    #[serde(default)]
+    pub stage: CheckStage,
+}
+
+/// When a check gates an attempt: before the reviewer is spent on it, or after approval.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStage {
+    PreReview,
+    #[default]
+    PostReview,
+}
+
+impl CheckStage {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CheckStage::PreReview => "pre_review",
+            CheckStage::PostReview => "post_review",
+        }
+    }
+}
+
+impl CheckSpec {
+    pub fn new(command: String) -> Self {
+        Self {
+            command,
+            timeout_secs: None,
+            workdir: None,
+            stage: CheckStage::PostReview,
+        }
+    }
+}
+
+pub const DEFAULT_OUTPUT_TAIL_BYTES: usize = 8 * 1024;
+const PIPE_DRAIN_GRACE: Duration = Duration::from_secs(1);
+
+/// Run checks in the worktree, at most `concurrency` at a time. Results keep the order of
+/// `checks` and the run passes only if every check does. Each result carries the last
+/// `output_tail_bytes` of the command's stdout and stderr. A failing command is re-run up to
+/// `retries` times and only its last try decides whether it passed.
 pub fn run_checks(
     worktree: &Path,
-    commands: &[String],
-    timeout: Duration,
+    checks: &[CheckSpec],
+    default_timeout: Duration,
+    concurrency: usize,
+    output_tail_bytes: usize,
+    retries: u32,
 ) -> Result<(bool, serde_json::Value)> {
-    let mut results = Vec::new();
-    let mut passed = true;
-
-    for cmd in commands {
-        let mut child = Command::new("sh")
-            .arg("-lc")
-            .arg(cmd)
-            .current_dir(worktree)
-            .spawn()?;
-        let start = Instant::now();
-        let mut timed_out = false;
-        let status = loop {
-            if let Some(status) = child.try_wait()? {
-                break status;
-            }
-            if start.elapsed() >= timeout {
-                timed_out = true;
-                let _ = child.kill();
-                break child.wait()?;
-            }
-            thread::sleep(Duration::from_millis(100));
-        };
-        let ok = status.success() && !timed_out;
-        if !ok {
-            passed = false;
+    let mut results = Vec::with_capacity(checks.len());
+    for batch in checks.chunks(concurrency.max(1)) {
+        let batch_results = thread::scope(|scope| {
+            let handles = batch
+                .iter()
+                .map(|check| {
+                    scope.spawn(move || {
+                        run_check_with_retries(
+                            worktree,
+                            check,
+                            default_timeout,
+                            output_tail_bytes,
+                            retries,
+                        )
+                    })
+                })
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(anyhow!("check thread panicked")))
+                })
+                .collect::<Vec<_>>()
+        });
+        for result in batch_results {
+            results.push(result?);
         }
-        results.push(json!({
+    }
+
+    let passed = results.iter().all(|r| r["ok"] == json!(true));
+    Ok((passed, json!({"passed": passed, "results": results})))
+}
+
+/// The result of the last try of `check`; when it was re-run, `tries` lists every try's result
+/// in order.
+fn run_check_with_retries(
+    worktree: &Path,
+    check: &CheckSpec,
+    default_timeout: Duration,
+    output_tail_bytes: usize,
+    retries: u32,
+) -> Result<serde_json::Value> {
+    let mut tries = Vec::new();
+    loop {
+        let result = run_check(worktree, check, default_timeout, output_tail_bytes)?;
+        let done = result["ok"] == json!(true) || tries.len() >= retries as usize;
+        if done && tries.is_empty() {
+            return Ok(result);
+        }
+        tries.push(result);
+        if done {
+            let mut last = tries.last().cloned().unwrap_or_default();
+            last["tries"] = json!(tries);
+            return Ok(last);
+        }
+    }
+}
+
+fn run_check(
+    worktree: &Path,
+    check: &CheckSpec,
+    default_timeout: Duration,
+    output_tail_bytes: usize,
+) -> Result<serde_json::Value> {
+    let cmd = &check.command;
+    let timeout = check
+        .timeout_secs
+        .map(Duration::from_secs)
+        .unwrap_or(default_timeout);
+    let dir = match check.workdir.as_deref() {
+        Some(workdir) => worktree.join(workdir),
+        None => worktree.to_path_buf(),
+    };
+    if !dir.is_dir() {
+        return Ok(json!({
             "command": cmd,
-            "ok": ok,
-            "timed_out": timed_out,
-            "timeout_secs": timeout.as_secs()
+            "ok": false,
+            "timed_out": false,
+            "timeout_secs": timeout.as_secs(),
+            "workdir": check.workdir,
+            "stdout_tail": "",
+            "stderr_tail": format!("check workdir `{}` does not exist in the worktree\n", dir.display())
         }));
     }
+    let mut child = Command::new("sh")
+        .arg("-lc")
+        .arg(cmd)
+        .current_dir(&dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let stdout = child
+        .stdout
+        .take()
+        .map(|pipe| drain_pipe(pipe, output_tail_bytes));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|pipe| drain_pipe(pipe, output_tail_bytes));
+    let start = Instant::now();
+    let mut timed_out = false;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() >= timeout {
+            timed_out = true;
+            let _ = child.kill();
+            break child.wait()?;
+        }
+        thread::sleep(Duration::from_millis(100));
+    };
+    let ok = status.success() && !timed_out;
+    let stdout_tail = tail(stdout);
+    let stderr_tail = tail(stderr);
+    Ok(json!({
+        "command": cmd,
+        "ok": ok,
+        "timed_out": timed_out,
+        "timeout_secs": timeout.as_secs(),
+        "workdir": check.workdir,
+        "stdout_tail": stdout_tail,
+        "stderr_tail": stderr_tail
+    }))
+}
 
-    Ok((passed, json!({"passed": passed, "results": results})))
+struct PipeTail {
+    buf: Arc<Mutex<Vec<u8>>>,
+    reader: thread::JoinHandle<()>,
+}
+
+/// Read a child pipe on its own thread so a chatty check cannot block on a full pipe, keeping
+/// only the last `max_bytes`.
+fn drain_pipe<R: Read + Send + 'static>(mut pipe: R, max_bytes: usize) -> PipeTail {
+    let buf = Arc::new(Mutex::new(Vec::new()));
+    let shared = Arc::clone(&buf);
+    let reader = thread::spawn(move || {
+        let mut chunk = [0u8; 4096];
+        while let Ok(n) = pipe.read(&mut chunk) {
+            if n == 0 {
+                break;
+            }
+            let mut buf = shared.lock().unwrap_or_else(|e| e.into_inner());
+            buf.extend_from_slice(&chunk[..n]);
+            let excess = buf.len().saturating_sub(max_bytes);
+            buf.drain(..excess);
+        }
+    });
+    PipeTail { buf, reader }
+}
+
+/// Collect a pipe tail, giving the reader a short grace period to hit EOF. A killed check can
+/// leave grandchildren holding the pipe open, so the reader is not joined unconditionally.
+fn tail(pipe: Option<PipeTail>) -> String {
+    let Some(pipe) = pipe else {
+        return String::new();
+    };
+    let deadline = Instant::now() + PIPE_DRAIN_GRACE;
+    while !pipe.reader.is_finished() && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(10));
+    }
+    let buf = pipe.buf.lock().unwrap_or_else(|e| e.into_inner());
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn per_check_timeout_overrides_run_default() {
+        let tmp = tempdir().unwrap();
+        let checks = vec![
+            CheckSpec {
+                command: "sleep 2".to_string(),
+                timeout_secs: Some(10),
+                ..CheckSpec::new(String::new())
+            },
+            CheckSpec::new("sleep 2".to_string()),
+        ];
+
+        let (passed, payload) = run_checks(
+            tmp.path(),
+            &checks,
+            Duration::from_secs(1),
+            1,
+            DEFAULT_OUTPUT_TAIL_BYTES,
+            0,
+        )
+        .unwrap();
+
+        assert!(!passed);
+        let results = payload["results"].as_array().unwrap();
+        assert_eq!(results[0]["ok"], json!(true));
+        assert_eq!(results[0]["timeout_secs"], json!(10));
+        assert_eq!(results[1]["ok"], json!(false));
+        assert_eq!(results[1]["timed_out"], json!(true));
+        assert_eq!(results[1]["timeout_secs"], json!(1));
+    }
+
+    #[test]
+    fn concurrent_checks_take_about_as_long_as_the_slowest() {
+        let tmp = tempdir().unwrap();
+        let checks = vec![
+            CheckSpec::new("sleep 1".to_string()),
+            CheckSpec::new("sleep 1".to_string()),
+            CheckSpec::new("sleep 1; exit 3".to_string()),
+        ];
+
+        let started = Instant::now();
+        let (passed, payload) = run_checks(
+            tmp.path(),
+            &checks,
+            Duration::from_secs(30),
+            3,
+            DEFAULT_OUTPUT_TAIL_BYTES,
+            0,
+        )
+        .unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(elapsed < Duration::from_millis(2500), "took {elapsed:?}");
+        assert!(!passed);
+        let commands = payload["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["command"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(commands, vec!["sleep 1", "sleep 1", "sleep 1; exit 3"]);
+        assert_eq!(payload["results"][2]["ok"], json!(false));
+    }
+
+    #[test]
+    fn failed_check_is_retried_and_its_last_try_decides() {
+        let tmp = tempdir().unwrap();
+        let checks = vec![
+            CheckSpec::new("test -f ran || { touch ran; exit 1; }".to_string()),
+            CheckSpec::new("exit 2".to_string()),
+        ];
+
+        let (passed, payload) = run_checks(
+            tmp.path(),
+            &checks,
+            Duration::from_secs(30),
+            1,
+            DEFAULT_OUTPUT_TAIL_BYTES,
+            1,
+        )
+        .unwrap();
+
+        assert!(!passed);
+        let results = payload["results"].as_array().unwrap();
+        assert_eq!(results[0]["ok"], json!(true));
+        let tries = results[0]["tries"].as_array().unwrap();
+        assert_eq!(tries.len(), 2);
+        assert_eq!(tries[0]["ok"], json!(false));
+        assert_eq!(results[1]["ok"], json!(false));
+        assert_eq!(results[1]["tries"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn captures_truncated_output_tails() {
+        let tmp = tempdir().unwrap();
+        let checks = vec![CheckSpec::new(
+            "echo start-of-output; echo error: widget exploded >&2; exit 1".to_string(),
+        )];
+
+        let (passed, payload) =
+            run_checks(tmp.path(), &checks, Duration::from_secs(30), 1, 10, 0).unwrap();
+
+        assert!(!passed);
+        assert_eq!(payload["results"][0]["stdout_tail"], json!("of-output\n"));
+        assert_eq!(payload["results"][0]["stderr_tail"], json!(" exploded\n"));
+    }
+
+    #[test]
+    fn check_runs_in_configured_workdir() {
+        let tmp = tempdir().unwrap();
+        std::fs::create_dir_all(tmp.path().join("crates").join("foo")).unwrap();
+        std::fs::write(tmp.path().join("crates/foo/marker"), "").unwrap();
+        let in_subdir = CheckSpec {
+            command: "test -f marker".to_string(),
+            workdir: Some(PathBuf::from("crates/foo")),
+            ..CheckSpec::new(String::new())
+        };
+        let at_root = CheckSpec::new("test -f marker".to_string());
+        let missing = CheckSpec {
+            workdir: Some(PathBuf::from("crates/bar")),
+            ..in_subdir.clone()
+        };
+
+        let (_, payload) = run_checks(
+            tmp.path(),
+            &[in_subdir, at_root, missing],
+            Duration::from_secs(30),
+            1,
+            DEFAULT_OUTPUT_TAIL_BYTES,
+            0,
+        )
+        .unwrap();
+
+        let results = payload["results"].as_array().unwrap();
+        assert_eq!(results[0]["ok"], json!(true));
+        assert_eq!(results[0]["workdir"], json!("crates/foo"));
+        assert_eq!(results[1]["ok"], json!(false));
+        assert_eq!(results[2]["ok"], json!(false));
+        assert!(
+            results[2]["stderr_tail"]
+                .as_str()
+                .unwrap()
+                .contains("does not exist")
+        );
+    }
 }
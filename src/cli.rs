@@ -1,9 +1,12 @@
+use crate::events::schema_doc;
+use crate::logging::diag;
+use crate::logging::ndjson::LogFormat;
 use crate::run;
-use anyhow::Result;
-use clap::{CommandFactory, Parser, Subcommand};
+use anyhow::{Context, Result, bail};
+use clap::{ArgGroup, Args, CommandFactory, Parser, Subcommand};
 use clap_complete::Shell;
 use std::fs;
-use std::io;
+use std::io::{self, Read};
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -15,15 +18,32 @@ use std::path::PathBuf;
 #[command(arg_required_else_help = true)]
 #[command(after_long_help = "Examples:
   thence run spec.md --agent codex --checks \"cargo check;cargo test\"
+  thence validate spec.md
   thence questions --run <RUN_ID>
   thence answer --run <RUN_ID> --question <QUESTION_ID> --text \"...\"
   thence resume --run <RUN_ID>
+  thence list --status running
+  thence export --run <RUN_ID> --format ndjson > events.ndjson
+  thence tail --run <RUN_ID> --follow
   thence completion zsh > ~/.zsh/completions/_thence
   thence man > thence.1
 
 Docs: https://github.com/David-Factor/thence#readme
 Issues: https://github.com/David-Factor/thence/issues")]
 struct Cli {
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "quiet",
+        help = "Also print supervisor progress to stderr"
+    )]
+    verbose: bool,
+    #[arg(
+        long,
+        global = true,
+        help = "Suppress warnings and next-step guidance on stderr; errors are still reported"
+    )]
+    quiet: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -38,87 +58,140 @@ enum Commands {
     #[command(after_long_help = "Examples:
   thence run spec.md
   thence run spec.md --agent codex --checks \"cargo check;cargo test\"
-  thence run spec.md --simulate")]
-    Run {
-        #[arg(value_name = "PLAN_FILE", help = "Path to markdown spec file")]
+  thence run spec.md --simulate
+  thence run spec.md --dry-run
+  cat spec.md | thence run --plan-stdin")]
+    Run(Box<RunArgs>),
+    #[command(about = "Lint a plan offline without starting a run")]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Example:
+  thence validate spec.md")]
+    Validate {
+        #[arg(value_name = "PLAN_FILE", help = "Path to markdown or YAML plan file")]
         plan_file: PathBuf,
+    },
+    #[command(about = "Check that a run could start: config, agent command, and state DB")]
+    #[command(after_long_help = "Examples:
+  thence doctor
+  thence doctor path/to/repo --agent opencode")]
+    Doctor {
+        #[arg(
+            value_name = "REPO",
+            default_value = ".",
+            help = "Directory holding .thence/config.toml (the repo root found from the plan file)"
+        )]
+        repo: PathBuf,
         #[arg(
             long,
-            default_value = "codex",
             value_name = "PROVIDER",
-            help = "Agent provider to use (only codex is supported in this version)"
+            help = "Agent provider to check (default: [agent].provider, else codex)"
         )]
-        agent: String,
+        agent: Option<String>,
         #[arg(
             long,
-            default_value_t = 2,
-            value_name = "N",
-            help = "Implementer worker count"
+            value_name = "PATH",
+            help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
         )]
-        workers: usize,
+        state_db: Option<PathBuf>,
+    },
+    #[command(about = "List runs recorded in the state DB")]
+    #[command(after_long_help = "Examples:
+  thence list
+  thence list --status running
+  thence list --resumable")]
+    List {
         #[arg(
             long,
-            default_value_t = 1,
-            value_name = "N",
-            help = "Reviewer worker count"
+            value_name = "STATUS",
+            value_parser = ["running", "completed", "failed", "cancelled"],
+            help = "Only show runs with this status"
         )]
-        reviewers: usize,
+        status: Option<String>,
         #[arg(
             long,
-            value_name = "CMDS",
-            help = "Semicolon-separated checks commands (e.g. \"cargo check;cargo test\")"
+            conflicts_with = "status",
+            help = "Show running runs with their phase, open questions, and current attempt"
         )]
-        checks: Option<String>,
-        #[arg(long, help = "Run with stubbed/simulated agent behavior")]
-        simulate: bool,
-        #[arg(long, value_name = "PATH", help = "Write NDJSON event log to file")]
-        log: Option<PathBuf>,
+        resumable: bool,
         #[arg(
             long,
-            help = "Resume flow via run command (prefer `thence resume --run <RUN_ID>`)"
+            value_name = "PATH",
+            help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
         )]
-        resume: bool,
+        state_db: Option<PathBuf>,
+    },
+    #[command(about = "List unresolved questions for a run")]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Examples:
+  thence questions --run <RUN_ID>
+  thence questions --run <RUN_ID> --json")]
+    Questions {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID to inspect")]
+        run: String,
         #[arg(
             long,
-            value_name = "RUN_ID",
-            help = "Explicit run ID for new/resumed run"
+            help = "Print open questions as a JSON array of {question_id, question}"
         )]
-        run_id: Option<String>,
+        json: bool,
         #[arg(
             long,
             value_name = "PATH",
             help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
         )]
         state_db: Option<PathBuf>,
+    },
+    #[command(about = "Answer a question opened during a run")]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Examples:
+  thence answer --run <RUN_ID> --question <QUESTION_ID> --approve
+  thence answer --run <RUN_ID> --question <QUESTION_ID> --text \"use the v2 endpoint\"
+  thence answer --run <RUN_ID> --question <QUESTION_ID> --text-file answer.md
+  pbpaste | thence answer --run <RUN_ID> --question <QUESTION_ID> --stdin")]
+    #[command(group(
+        ArgGroup::new("answer_source")
+            .required(true)
+            .args(["text", "text_file", "stdin", "approve", "reject"])
+    ))]
+    Answer {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID that owns the question")]
+        run: String,
+        #[arg(long, value_name = "QUESTION_ID", help = "Question ID to answer")]
+        question: String,
+        #[arg(long, value_name = "TEXT", help = "Answer text")]
+        text: Option<String>,
+        #[arg(long, value_name = "PATH", help = "Read answer text from a file")]
+        text_file: Option<PathBuf>,
+        #[arg(long, help = "Read answer text from stdin")]
+        stdin: bool,
         #[arg(
             long,
-            help = "Allow run completion when some tasks terminal-fail but others succeed"
+            help = "Approve the gate the question blocks (same as --text approve)"
         )]
-        allow_partial_completion: bool,
-        #[arg(long, help = "Trust per-task checks returned by plan translator")]
-        trust_plan_checks: bool,
-        #[arg(long, help = "Enable interactive mode for supporting agent adapters")]
-        interactive: bool,
+        approve: bool,
         #[arg(
             long,
-            value_name = "SECS",
-            help = "Hard timeout in seconds for implementer/reviewer attempts"
+            help = "Reject the gate; the question stays open and the run stays paused"
         )]
-        attempt_timeout_secs: Option<u64>,
+        reject: bool,
         #[arg(
             long,
             value_name = "PATH",
-            help = "Write translated SPL plan to this file for debugging"
+            help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
         )]
-        debug_dump_spl: Option<PathBuf>,
+        state_db: Option<PathBuf>,
     },
-    #[command(about = "List unresolved questions for a run")]
+    #[command(about = "Resume a paused or interrupted run")]
     #[command(arg_required_else_help = true)]
     #[command(after_long_help = "Example:
-  thence questions --run <RUN_ID>")]
-    Questions {
-        #[arg(long, value_name = "RUN_ID", help = "Run ID to inspect")]
+  thence resume --run <RUN_ID>")]
+    Resume {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID to resume")]
         run: String,
+        #[arg(
+            long,
+            help = "Echo agent stdout/stderr to stderr, prefixed with role, task and attempt (logs are still written)"
+        )]
+        attach: bool,
         #[arg(
             long,
             value_name = "PATH",
@@ -126,17 +199,13 @@ enum Commands {
         )]
         state_db: Option<PathBuf>,
     },
-    #[command(about = "Answer a question opened during a run")]
+    #[command(about = "Cancel a run so it is no longer resumable")]
     #[command(arg_required_else_help = true)]
     #[command(after_long_help = "Example:
-  thence answer --run <RUN_ID> --question <QUESTION_ID> --text \"approve\"")]
-    Answer {
-        #[arg(long, value_name = "RUN_ID", help = "Run ID that owns the question")]
+  thence cancel --run <RUN_ID>")]
+    Cancel {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID to cancel")]
         run: String,
-        #[arg(long, value_name = "QUESTION_ID", help = "Question ID to answer")]
-        question: String,
-        #[arg(long, value_name = "TEXT", help = "Answer text")]
-        text: String,
         #[arg(
             long,
             value_name = "PATH",
@@ -144,13 +213,19 @@ enum Commands {
         )]
         state_db: Option<PathBuf>,
     },
-    #[command(about = "Resume a paused or interrupted run")]
+    #[command(about = "Delete a run and its events from the state DB")]
     #[command(arg_required_else_help = true)]
-    #[command(after_long_help = "Example:
-  thence resume --run <RUN_ID>")]
-    Resume {
-        #[arg(long, value_name = "RUN_ID", help = "Run ID to resume")]
+    #[command(after_long_help = "Examples:
+  thence delete --run <RUN_ID>
+  thence delete --run <RUN_ID> --force   # also deletes a run that is not terminal")]
+    Delete {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID to delete")]
         run: String,
+        #[arg(
+            long,
+            help = "Delete the run even if it has not reached a terminal state"
+        )]
+        force: bool,
         #[arg(
             long,
             value_name = "PATH",
@@ -160,11 +235,41 @@ enum Commands {
     },
     #[command(about = "Inspect current state for a run")]
     #[command(arg_required_else_help = true)]
-    #[command(after_long_help = "Example:
-  thence inspect --run <RUN_ID>")]
+    #[command(after_long_help = "Examples:
+  thence inspect --run <RUN_ID>
+  thence inspect --run <RUN_ID> --json
+  thence inspect --run <RUN_ID> --timeline")]
     Inspect {
         #[arg(long, value_name = "RUN_ID", help = "Run ID to inspect")]
         run: String,
+        #[arg(long, help = "Print run state as a JSON object")]
+        json: bool,
+        #[arg(
+            long,
+            conflicts_with = "json",
+            help = "Print the run's events in order, one summary line each"
+        )]
+        timeline: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
+        )]
+        state_db: Option<PathBuf>,
+    },
+    #[command(about = "Print a run's state as it stood at an earlier event")]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Example:
+  thence replay --run <RUN_ID> --to-seq 42")]
+    Replay {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID to replay")]
+        run: String,
+        #[arg(
+            long,
+            value_name = "SEQ",
+            help = "Replay events up to and including this seq"
+        )]
+        to_seq: i64,
         #[arg(
             long,
             value_name = "PATH",
@@ -172,6 +277,127 @@ enum Commands {
         )]
         state_db: Option<PathBuf>,
     },
+    #[command(about = "Compare task outcomes between two runs")]
+    #[command(
+        long_about = "Compare two runs task by task: outcome (closed, failed, skipped, pending) and attempt count, plus tasks present in only one of the runs."
+    )]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Example:
+  thence diff --run <RUN_A> --run <RUN_B>")]
+    Diff {
+        #[arg(
+            long = "run",
+            value_name = "RUN_ID",
+            required = true,
+            help = "Run to compare; pass exactly twice, baseline first"
+        )]
+        runs: Vec<String>,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
+        )]
+        state_db: Option<PathBuf>,
+    },
+    #[command(about = "Print the prompt capsule for a task attempt")]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Example:
+  thence show-capsule --run <RUN_ID> --task <TASK_ID> --attempt 1 --role reviewer")]
+    ShowCapsule {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID that owns the attempt")]
+        run: String,
+        #[arg(long, value_name = "TASK_ID", help = "Task ID")]
+        task: String,
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(i64).range(1..),
+            help = "Attempt number"
+        )]
+        attempt: i64,
+        #[arg(
+            long,
+            default_value = "implementer",
+            value_name = "ROLE",
+            value_parser = ["implementer", "reviewer"],
+            help = "Which agent's capsule to show"
+        )]
+        role: String,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
+        )]
+        state_db: Option<PathBuf>,
+    },
+    #[command(about = "Export a run's full event log")]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Examples:
+  thence export --run <RUN_ID>
+  thence export --run <RUN_ID> --format ndjson > events.ndjson
+  thence export --run <RUN_ID> --from-seq 120 --to-seq 180")]
+    Export {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID to export")]
+        run: String,
+        #[arg(
+            long,
+            default_value = "json",
+            value_name = "FORMAT",
+            value_parser = ["json", "ndjson"],
+            help = "Output format"
+        )]
+        format: String,
+        #[arg(long, value_name = "SEQ", help = "Only export events with seq >= SEQ")]
+        from_seq: Option<i64>,
+        #[arg(long, value_name = "SEQ", help = "Only export events with seq <= SEQ")]
+        to_seq: Option<i64>,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
+        )]
+        state_db: Option<PathBuf>,
+    },
+    #[command(about = "Print a run's events, optionally following new ones live")]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Examples:
+  thence tail --run <RUN_ID>
+  thence tail --run <RUN_ID> --follow --interval-ms 250")]
+    Tail {
+        #[arg(long, value_name = "RUN_ID", help = "Run ID to tail")]
+        run: String,
+        #[arg(
+            long,
+            help = "Keep polling for new events until the run reaches a terminal event"
+        )]
+        follow: bool,
+        #[arg(
+            long,
+            default_value_t = 1000,
+            value_name = "MS",
+            value_parser = clap::value_parser!(u64).range(1..),
+            help = "Poll interval in milliseconds when following"
+        )]
+        interval_ms: u64,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
+        )]
+        state_db: Option<PathBuf>,
+    },
+    #[command(
+        about = "Print the JSON schema of thence's event payloads",
+        long_about = "Print a JSON document describing every event type thence records and the payload fields each one carries, for consumers of `thence export` and `thence tail`."
+    )]
+    #[command(arg_required_else_help = true)]
+    #[command(after_long_help = "Examples:
+  thence schema events
+  thence schema events > thence-events.schema.json")]
+    Schema {
+        #[arg(value_enum, value_name = "KIND", help = "Which schema to print")]
+        kind: SchemaKind,
+    },
     #[command(
         about = "Generate shell completion script",
         long_about = "Generate shell completion script for your shell. Redirect output to your shell completion directory."
@@ -202,34 +428,239 @@ enum Commands {
     },
 }
 
+#[derive(Args, Debug)]
+struct RunArgs {
+    #[arg(
+        value_name = "PLAN_FILE",
+        required_unless_present = "plan_stdin",
+        help = "Path to markdown spec file"
+    )]
+    plan_file: Option<PathBuf>,
+    #[arg(
+        long,
+        conflicts_with_all = ["plan_file", "resume"],
+        help = "Read the markdown spec from stdin; the current directory is the repo root"
+    )]
+    plan_stdin: bool,
+    #[arg(
+        long,
+        default_value = "codex",
+        value_name = "PROVIDER",
+        help = "Agent provider to use (codex or opencode)"
+    )]
+    agent: String,
+    #[arg(
+        long,
+        default_value_t = 2,
+        value_name = "N",
+        help = "Implementer worker count"
+    )]
+    workers: usize,
+    #[arg(
+        long,
+        default_value_t = 1,
+        value_name = "N",
+        help = "Reviewer worker count"
+    )]
+    reviewers: usize,
+    #[arg(
+        long,
+        value_name = "CMDS",
+        help = "Semicolon-separated checks commands (e.g. \"cargo check;cargo test\")"
+    )]
+    checks: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Load checks from a JSON {\"commands\": [...]} or TOML `commands = [...]` file (--checks wins)"
+    )]
+    checks_from_file: Option<PathBuf>,
+    #[arg(long, help = "Run with stubbed/simulated agent behavior")]
+    simulate: bool,
+    #[arg(long, value_name = "PATH", help = "Write NDJSON event log to file")]
+    log: Option<PathBuf>,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = LogFormat::Compact,
+        value_name = "FORMAT",
+        help = "NDJSON log line shape: compact summary or full event rows"
+    )]
+    log_format: LogFormat,
+    #[arg(
+        long,
+        help = "Resume flow via run command (prefer `thence resume --run <RUN_ID>`)"
+    )]
+    resume: bool,
+    #[arg(
+        long,
+        value_name = "RUN_ID",
+        help = "Explicit run ID for new/resumed run"
+    )]
+    run_id: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Path to state DB (default: $XDG_STATE_HOME/thence/state.db)"
+    )]
+    state_db: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Allow run completion when some tasks terminal-fail but others succeed"
+    )]
+    allow_partial_completion: bool,
+    #[arg(long, help = "Trust per-task checks returned by plan translator")]
+    trust_plan_checks: bool,
+    #[arg(long, help = "Enable interactive mode for supporting agent adapters")]
+    interactive: bool,
+    #[arg(
+        long,
+        help = "Echo agent stdout/stderr to stderr, prefixed with role, task and attempt (logs are still written)"
+    )]
+    attach: bool,
+    #[arg(
+        long,
+        value_name = "SECS",
+        help = "Hard timeout in seconds for implementer/reviewer attempts"
+    )]
+    attempt_timeout_secs: Option<u64>,
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Hard timeout in seconds for reviewer attempts (default: --attempt-timeout-secs)"
+    )]
+    reviewer_timeout_secs: Option<u64>,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(i64).range(1..),
+        help = "Attempts per task before it fails terminally (default: [run].max_attempts or 3)"
+    )]
+    max_attempts: Option<i64>,
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Default timeout in seconds for each check command (default: 600)"
+    )]
+    check_timeout_secs: Option<u64>,
+    #[arg(
+        long,
+        help = "Ask a spec-reviewer agent to check the spec for ambiguities before running"
+    )]
+    agent_spec_review: bool,
+    #[arg(
+        long,
+        conflicts_with = "agent_spec_review",
+        help = "Skip the spec ambiguity gate for a trusted spec; translation and validation still run"
+    )]
+    no_spec_review: bool,
+    #[arg(
+        long,
+        help = "With no checks configured, ask the agent to propose checks and pause for approval"
+    )]
+    propose_checks: bool,
+    #[arg(long, help = "Neither read nor update .thence/checks.json")]
+    no_checks_file: bool,
+    #[arg(
+        long,
+        conflicts_with = "no_checks_file",
+        help = "Ignore .thence/checks.json and ask the agent to propose checks again"
+    )]
+    reconfigure_checks: bool,
+    #[arg(
+        long,
+        help = "Copy the worktree of a task that fails terminally into the run dir under failed/<task>/<attempt>"
+    )]
+    worktree_keep_on_fail: bool,
+    #[arg(
+        long,
+        help = "Translate, validate, and review the spec, print the task graph, then stop"
+    )]
+    dry_run: bool,
+    #[arg(
+        long,
+        value_name = "ID",
+        conflicts_with = "resume",
+        help = "Run only this translated task and the tasks it depends on (repeatable)"
+    )]
+    only_task: Vec<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write translated SPL plan to this file for debugging"
+    )]
+    debug_dump_spl: Option<PathBuf>,
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Keep run artifacts in DIR instead of <repo>/.thence/runs (overrides [run].artifacts_dir)"
+    )]
+    artifacts_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum SchemaKind {
+    /// Event types and their payload fields
+    Events,
+}
+
 pub fn run() -> Result<()> {
     let cli = Cli::parse();
+    diag::set_level(if cli.quiet {
+        diag::Level::Quiet
+    } else if cli.verbose {
+        diag::Level::Verbose
+    } else {
+        diag::Level::Normal
+    });
     match cli.command {
-        Commands::Run {
-            plan_file,
-            agent,
-            workers,
-            reviewers,
-            checks,
-            simulate,
-            log,
-            resume,
-            run_id,
-            state_db,
-            allow_partial_completion,
-            trust_plan_checks,
-            interactive,
-            attempt_timeout_secs,
-            debug_dump_spl,
-        } => {
-            let cfg = run::RunCommand {
+        Commands::Run(args) => {
+            let RunArgs {
                 plan_file,
+                plan_stdin,
+                agent,
+                workers,
+                reviewers,
+                checks,
+                checks_from_file,
+                simulate,
+                log,
+                log_format,
+                resume,
+                run_id,
+                state_db,
+                allow_partial_completion,
+                trust_plan_checks,
+                interactive,
+                attach,
+                attempt_timeout_secs,
+                reviewer_timeout_secs,
+                max_attempts,
+                check_timeout_secs,
+                agent_spec_review,
+                no_spec_review,
+                propose_checks,
+                no_checks_file,
+                reconfigure_checks,
+                worktree_keep_on_fail,
+                dry_run,
+                only_task,
+                debug_dump_spl,
+                artifacts_dir,
+            } = *args;
+            diag::set_attach(attach);
+            let cfg = run::RunCommand {
+                plan_file: plan_file.unwrap_or_default(),
                 agent,
                 workers,
                 reviewers,
                 checks,
+                checks_from_file,
                 simulate,
                 log,
+                log_format,
                 resume,
                 run_id,
                 state_db,
@@ -237,28 +668,130 @@ pub fn run() -> Result<()> {
                 trust_plan_checks,
                 interactive,
                 attempt_timeout_secs,
+                reviewer_timeout_secs,
+                max_attempts,
+                check_timeout_secs,
+                agent_spec_review,
+                no_spec_review,
+                propose_checks,
+                no_checks_file,
+                reconfigure_checks,
+                worktree_keep_on_fail,
+                dry_run,
+                only_task,
                 debug_dump_spl,
+                artifacts_dir,
             };
-            run::execute_run(cfg)
+            run::install_interrupt_handler();
+            if plan_stdin {
+                let repo_root = std::env::current_dir().context("resolve current directory")?;
+                run::execute_run_from_reader(cfg, &repo_root, io::stdin().lock())
+            } else {
+                run::execute_run(cfg)
+            }
         }
+        Commands::Validate { plan_file } => run::print_plan_validation(&plan_file),
+        Commands::Doctor {
+            repo,
+            agent,
+            state_db,
+        } => run::doctor(&repo, agent.as_deref(), state_db),
+        Commands::List {
+            resumable: true,
+            state_db,
+            ..
+        } => run::list_resumable_runs(state_db),
+        Commands::List {
+            status, state_db, ..
+        } => run::list_runs(status.as_deref(), state_db),
         Commands::Questions {
             run: run_id,
+            json,
             state_db,
-        } => run::list_questions(&run_id, state_db),
+        } => run::list_questions(&run_id, json, state_db),
         Commands::Answer {
             run: run_id,
             question,
             text,
+            text_file,
+            stdin,
+            approve,
+            reject,
             state_db,
-        } => run::answer_question(&run_id, &question, &text, state_db),
+        } => {
+            if reject {
+                return run::reject_question(&run_id, &question, state_db);
+            }
+            let text = if approve {
+                run::APPROVE_ANSWER.to_string()
+            } else {
+                read_answer_text(text, text_file, stdin)?
+            };
+            run::answer_question(&run_id, &question, &text, state_db)
+        }
         Commands::Resume {
+            run: run_id,
+            attach,
+            state_db,
+        } => {
+            diag::set_attach(attach);
+            run::install_interrupt_handler();
+            run::resume_run(&run_id, state_db)
+        }
+        Commands::Cancel {
             run: run_id,
             state_db,
-        } => run::resume_run(&run_id, state_db),
+        } => run::cancel_run(&run_id, state_db),
+        Commands::Delete {
+            run: run_id,
+            force,
+            state_db,
+        } => run::delete_run(&run_id, force, state_db),
         Commands::Inspect {
             run: run_id,
+            json,
+            timeline,
+            state_db,
+        } => run::inspect_run(&run_id, json, timeline, state_db),
+        Commands::Replay {
+            run: run_id,
+            to_seq,
+            state_db,
+        } => run::replay_run(&run_id, to_seq, state_db),
+        Commands::Diff { runs, state_db } => match runs.as_slice() {
+            [run_a, run_b] => run::diff_runs(run_a, run_b, state_db),
+            _ => bail!(
+                "thence diff takes exactly two --run values (got {})",
+                runs.len()
+            ),
+        },
+        Commands::ShowCapsule {
+            run: run_id,
+            task,
+            attempt,
+            role,
+            state_db,
+        } => run::show_capsule(&run_id, &task, attempt, &role, state_db),
+        Commands::Export {
+            run: run_id,
+            format,
+            from_seq,
+            to_seq,
             state_db,
-        } => run::inspect_run(&run_id, state_db),
+        } => run::export_run(&run_id, &format, from_seq, to_seq, state_db),
+        Commands::Tail {
+            run: run_id,
+            follow,
+            interval_ms,
+            state_db,
+        } => run::tail_run(&run_id, follow, interval_ms, state_db),
+        Commands::Schema {
+            kind: SchemaKind::Events,
+        } => {
+            let doc = schema_doc::events_schema_json();
+            println!("{}", serde_json::to_string_pretty(&doc)?);
+            Ok(())
+        }
         Commands::Completion { shell } => {
             let mut cmd = Cli::command();
             let name = cmd.get_name().to_string();
@@ -282,6 +815,29 @@ pub fn run() -> Result<()> {
     }
 }
 
+/// Resolve the answer body from exactly one of `--text`, `--text-file` or `--stdin`, verbatim.
+fn read_answer_text(
+    text: Option<String>,
+    text_file: Option<PathBuf>,
+    stdin: bool,
+) -> Result<String> {
+    if let Some(text) = text {
+        return Ok(text);
+    }
+    if let Some(path) = text_file {
+        return fs::read_to_string(&path)
+            .with_context(|| format!("read answer text file {}", path.display()));
+    }
+    if stdin {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .context("read answer text from stdin")?;
+        return Ok(text);
+    }
+    bail!("one of --text, --text-file, --stdin, --approve or --reject is required")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,8 +855,6 @@ mod tests {
             .collect::<Vec<_>>();
 
         assert!(arg_ids.contains(&"simulate".to_string()));
-        assert!(!arg_ids.contains(&"reconfigure_checks".to_string()));
-        assert!(!arg_ids.contains(&"no_checks_file".to_string()));
         assert!(!arg_ids.contains(&"agent_cmd".to_string()));
         assert!(!arg_ids.contains(&"agent_cmd_codex".to_string()));
         assert!(!arg_ids.contains(&"agent_cmd_claude".to_string()));
@@ -309,7 +863,55 @@ mod tests {
 
     #[test]
     fn removed_run_flags_are_rejected_by_cli() {
-        assert!(Cli::try_parse_from(["thence", "run", "spec.md", "--reconfigure-checks"]).is_err());
         assert!(Cli::try_parse_from(["thence", "run", "spec.md", "--agent-cmd", "codex"]).is_err());
     }
+
+    #[test]
+    fn plan_stdin_replaces_the_plan_file_argument() {
+        assert!(Cli::try_parse_from(["thence", "run", "--plan-stdin"]).is_ok());
+        assert!(Cli::try_parse_from(["thence", "run", "--simulate"]).is_err());
+        assert!(Cli::try_parse_from(["thence", "run", "spec.md", "--plan-stdin"]).is_err());
+        assert!(Cli::try_parse_from(["thence", "run", "--plan-stdin", "--resume"]).is_err());
+    }
+
+    #[test]
+    fn answer_text_file_is_read_verbatim() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("answer.md");
+        let body = "Use \"strict\" mode.\n\n  - keep the old flag\r\n- trailing newline\n";
+        fs::write(&path, body).unwrap();
+
+        let cli = Cli::try_parse_from([
+            "thence",
+            "answer",
+            "--run",
+            "r1",
+            "--question",
+            "q1",
+            "--text-file",
+            path.to_str().unwrap(),
+        ])
+        .unwrap();
+        let Commands::Answer {
+            text,
+            text_file,
+            stdin,
+            ..
+        } = cli.command
+        else {
+            panic!("expected answer command");
+        };
+        assert_eq!(read_answer_text(text, text_file, stdin).unwrap(), body);
+    }
+
+    #[test]
+    fn answer_sources_are_mutually_exclusive_and_required() {
+        let base = ["thence", "answer", "--run", "r1", "--question", "q1"];
+        assert!(Cli::try_parse_from(base).is_err());
+        assert!(Cli::try_parse_from([&base[..], &["--text", "x", "--stdin"]].concat()).is_err());
+        assert!(Cli::try_parse_from([&base[..], &["--stdin"]].concat()).is_ok());
+        assert!(Cli::try_parse_from([&base[..], &["--approve"]].concat()).is_ok());
+        assert!(Cli::try_parse_from([&base[..], &["--approve", "--reject"]].concat()).is_err());
+        assert!(Cli::try_parse_from([&base[..], &["--reject", "--text", "x"]].concat()).is_err());
+    }
 }
@@ -1,6 +1,9 @@
-use anyhow::{Context, Result, bail};
+use crate::checks::runner::{CheckSpec, CheckStage};
+use crate::vcs::worktree::{sanitize_destination_path, sanitize_relative_path};
+use anyhow::{Context, Result, anyhow, bail};
 use serde::{Deserialize, Serialize};
-use std::path::{Component, Path, PathBuf};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 const CONFIG_RELATIVE_PATH: &str = ".thence/config.toml";
 
@@ -11,27 +14,135 @@ pub struct RepoConfig {
     pub checks: Option<ChecksConfig>,
     pub prompts: Option<PromptsConfig>,
     pub worktree: Option<WorktreeConfig>,
+    pub run: Option<RunSectionConfig>,
+    pub vcs: Option<VcsConfig>,
+    pub policy: Option<PolicyConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentConfig {
     pub provider: Option<String>,
     pub command: Option<String>,
+    pub spawn_retries: Option<u32>,
+    /// Extra environment variables passed to every agent subprocess.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChecksConfig {
-    pub commands: Vec<String>,
+    pub commands: Vec<CheckSpec>,
+    pub concurrency: Option<usize>,
+    pub output_tail_bytes: Option<usize>,
+    /// Re-runs of a failing check command before it counts as failed.
+    pub retries: Option<u32>,
+    pub task_checks: TaskChecksMode,
+}
+
+/// How a task's own checks combine with the run-level checks at the checks gate.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskChecksMode {
+    #[default]
+    Union,
+    Replace,
+}
+
+/// What resume does when the live plan file, or the config the run's schedule depends on, no
+/// longer matches what the run started from.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftAction {
+    #[default]
+    Warn,
+    Pause,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PromptsConfig {
     pub reviewer: Option<String>,
+    pub implementer: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSectionConfig {
+    pub max_attempts: Option<i64>,
+    pub max_tasks: Option<usize>,
+    /// Claims allowed across every task of a run before it pauses on `budget-q-attempts-<n>`.
+    pub max_total_attempts: Option<i64>,
+    pub lease_stale_secs: Option<u64>,
+    pub lease_tick_secs: Option<u64>,
+    pub attempt_backoff_secs: Option<u64>,
+    pub task_deadline_secs: Option<u64>,
+    /// Milliseconds the supervisor sleeps between checks while only in-flight attempts remain.
+    pub poll_interval_ms: Option<u64>,
+    pub spec_drift: Option<DriftAction>,
+    pub config_drift: Option<DriftAction>,
+    /// Where run artifacts live instead of `<repo>/.thence/runs`; relative to the repo root.
+    pub artifacts_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorktreeConfig {
     pub provision: Option<WorktreeProvisionConfig>,
+    pub cleanup: WorktreeCleanup,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorktreeCleanup {
+    OnClose,
+    #[default]
+    Never,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyConfig {
+    /// SPL snippet appended to the composed policy theory; relative to the repo root.
+    pub extra_rules_file: Option<PathBuf>,
+}
+
+/// Which integration branch an approved attempt merges onto.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VcsConfig {
+    pub integration_strategy: IntegrationStrategy,
+    /// Branch name template; `{run_id}` and `{task_id}` are substituted.
+    pub integration_branch: String,
+}
+
+impl Default for VcsConfig {
+    fn default() -> Self {
+        Self {
+            integration_strategy: IntegrationStrategy::PerRun,
+            integration_branch: IntegrationStrategy::PerRun.default_template().to_string(),
+        }
+    }
+}
+
+impl VcsConfig {
+    pub fn integration_branch(&self, run_id: &str, task_id: &str) -> String {
+        self.integration_branch
+            .replace("{run_id}", run_id)
+            .replace("{task_id}", task_id)
+    }
+}
+
+/// `per_run` merges every task onto one branch; `per_task` gives each task its own.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrationStrategy {
+    #[default]
+    PerRun,
+    PerTask,
+}
+
+impl IntegrationStrategy {
+    fn default_template(self) -> &'static str {
+        match self {
+            Self::PerRun => "thence/{run_id}",
+            Self::PerTask => "thence/{run_id}/{task_id}",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,27 +172,77 @@ struct RawRepoConfig {
     checks: Option<RawChecksConfig>,
     prompts: Option<RawPromptsConfig>,
     worktree: Option<RawWorktreeConfig>,
+    run: Option<RawRunSectionConfig>,
+    vcs: Option<RawVcsConfig>,
+    policy: Option<RawPolicyConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawPolicyConfig {
+    extra_rules_file: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 struct RawAgentConfig {
     provider: Option<String>,
     command: Option<String>,
+    spawn_retries: Option<u32>,
+    env: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 struct RawChecksConfig {
-    commands: Option<Vec<String>>,
+    commands: Option<Vec<RawCheckCommand>>,
+    concurrency: Option<usize>,
+    output_tail_bytes: Option<usize>,
+    retries: Option<u32>,
+    #[serde(alias = "task_override")]
+    task_checks: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum RawCheckCommand {
+    Plain(String),
+    Table {
+        command: Option<String>,
+        timeout_secs: Option<u64>,
+        workdir: Option<String>,
+        stage: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize)]
 struct RawPromptsConfig {
     reviewer: Option<String>,
+    implementer: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawRunSectionConfig {
+    max_attempts: Option<i64>,
+    max_tasks: Option<usize>,
+    max_total_attempts: Option<i64>,
+    lease_stale_secs: Option<u64>,
+    lease_tick_secs: Option<u64>,
+    attempt_backoff_secs: Option<u64>,
+    task_deadline_secs: Option<u64>,
+    poll_interval_ms: Option<u64>,
+    spec_drift: Option<String>,
+    config_drift: Option<String>,
+    artifacts_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawVcsConfig {
+    integration_strategy: Option<String>,
+    integration_branch: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 struct RawWorktreeConfig {
     provision: Option<RawWorktreeProvisionConfig>,
+    cleanup: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -130,13 +291,15 @@ fn validate_repo_config(raw: RawRepoConfig, path: &Path) -> Result<RepoConfig> {
         .map(|agent| {
             let provider = sanitize_optional(agent.provider);
             if let Some(provider) = provider.as_deref()
-                && provider != "codex"
+                && let Err(err) = crate::workers::provider::ensure_supported_provider(provider)
             {
-                bail!("only `codex` supported in this version");
+                bail!("{} has invalid `[agent].provider`: {err}", path.display());
             }
             Ok(AgentConfig {
                 provider,
                 command: sanitize_optional(agent.command),
+                spawn_retries: agent.spawn_retries,
+                env: sanitize_agent_env(agent.env.unwrap_or_default(), path)?,
             })
         })
         .transpose()?;
@@ -147,16 +310,43 @@ fn validate_repo_config(raw: RawRepoConfig, path: &Path) -> Result<RepoConfig> {
             let commands = checks.commands.ok_or_else(|| {
                 anyhow::anyhow!("{} missing `[checks].commands` in config", path.display())
             })?;
-            let commands = sanitize_commands(commands);
+            let commands = sanitize_check_commands(commands, path)?;
             if commands.is_empty() {
                 bail!("{} has empty `[checks].commands`", path.display());
             }
-            Ok(ChecksConfig { commands })
+            if checks.concurrency == Some(0) {
+                bail!(
+                    "{} has `[checks].concurrency = 0`; expected at least 1",
+                    path.display()
+                );
+            }
+            let task_checks = match checks
+                .task_checks
+                .as_deref()
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+            {
+                None | Some("union" | "append") => TaskChecksMode::Union,
+                Some("replace") => TaskChecksMode::Replace,
+                Some(other) => bail!(
+                    "{} has unsupported `[checks].task_checks = \"{}\"`; expected `union` (or `append`) or `replace`",
+                    path.display(),
+                    other
+                ),
+            };
+            Ok(ChecksConfig {
+                commands,
+                concurrency: checks.concurrency,
+                output_tail_bytes: checks.output_tail_bytes,
+                retries: checks.retries,
+                task_checks,
+            })
         })
         .transpose()?;
 
     let prompts = raw.prompts.map(|prompts| PromptsConfig {
         reviewer: sanitize_optional(prompts.reviewer),
+        implementer: sanitize_optional(prompts.implementer),
     });
 
     let worktree = raw
@@ -164,27 +354,217 @@ fn validate_repo_config(raw: RawRepoConfig, path: &Path) -> Result<RepoConfig> {
         .map(|worktree| validate_worktree_config(worktree, path))
         .transpose()?;
 
+    let run = raw
+        .run
+        .map(|run| {
+            if let Some(max_attempts) = run.max_attempts
+                && max_attempts < 1
+            {
+                bail!(
+                    "{} has `[run].max_attempts = {max_attempts}`; expected at least 1",
+                    path.display()
+                );
+            }
+            if run.max_tasks == Some(0) {
+                bail!(
+                    "{} has `[run].max_tasks = 0`; expected at least 1",
+                    path.display()
+                );
+            }
+            if let Some(cap) = run.max_total_attempts
+                && cap < 1
+            {
+                bail!(
+                    "{} has `[run].max_total_attempts = {cap}`; expected at least 1",
+                    path.display()
+                );
+            }
+            for (key, value) in [
+                ("lease_stale_secs", run.lease_stale_secs),
+                ("lease_tick_secs", run.lease_tick_secs),
+                ("task_deadline_secs", run.task_deadline_secs),
+            ] {
+                if value == Some(0) {
+                    bail!(
+                        "{} has `[run].{key} = 0`; expected a positive number of seconds",
+                        path.display()
+                    );
+                }
+            }
+            if run.poll_interval_ms == Some(0) {
+                bail!(
+                    "{} has `[run].poll_interval_ms = 0`; expected a positive number of milliseconds",
+                    path.display()
+                );
+            }
+            let stale = run
+                .lease_stale_secs
+                .unwrap_or(crate::run::lease::LEASE_STALE_AFTER_SECS);
+            let tick = run
+                .lease_tick_secs
+                .unwrap_or(crate::run::lease::LEASE_TICK_SECS);
+            if tick >= stale {
+                bail!(
+                    "{} has `[run].lease_tick_secs = {tick}` not below `lease_stale_secs = {stale}`; leases would go stale between ticks",
+                    path.display()
+                );
+            }
+            let parse_drift = |key: &str, value: Option<&str>| match value.map(str::trim) {
+                None => Ok(None),
+                Some("warn") => Ok(Some(DriftAction::Warn)),
+                Some("pause") => Ok(Some(DriftAction::Pause)),
+                Some(other) => Err(anyhow!(
+                    "{} has unsupported `[run].{key} = \"{}\"`; expected `warn` or `pause`",
+                    path.display(),
+                    other
+                )),
+            };
+            let spec_drift = parse_drift("spec_drift", run.spec_drift.as_deref())?;
+            let config_drift = parse_drift("config_drift", run.config_drift.as_deref())?;
+            Ok(RunSectionConfig {
+                max_attempts: run.max_attempts,
+                max_tasks: run.max_tasks,
+                max_total_attempts: run.max_total_attempts,
+                lease_stale_secs: run.lease_stale_secs,
+                lease_tick_secs: run.lease_tick_secs,
+                attempt_backoff_secs: run.attempt_backoff_secs,
+                task_deadline_secs: run.task_deadline_secs,
+                poll_interval_ms: run.poll_interval_ms,
+                spec_drift,
+                config_drift,
+                artifacts_dir: sanitize_optional(run.artifacts_dir).map(PathBuf::from),
+            })
+        })
+        .transpose()?;
+
+    let vcs = raw
+        .vcs
+        .map(|vcs| validate_vcs_config(vcs, path))
+        .transpose()?;
+
+    let policy = raw.policy.map(|policy| PolicyConfig {
+        extra_rules_file: sanitize_optional(policy.extra_rules_file).map(PathBuf::from),
+    });
+
     Ok(RepoConfig {
         version,
         agent,
         checks,
         prompts,
         worktree,
+        run,
+        vcs,
+        policy,
     })
 }
 
+fn validate_vcs_config(raw: RawVcsConfig, path: &Path) -> Result<VcsConfig> {
+    let integration_strategy = match raw.integration_strategy.as_deref().map(str::trim) {
+        None | Some("per_run") => IntegrationStrategy::PerRun,
+        Some("per_task") => IntegrationStrategy::PerTask,
+        Some(other) => bail!(
+            "{} has unsupported `[vcs].integration_strategy = \"{}\"`; expected `per_run` or `per_task`",
+            path.display(),
+            other
+        ),
+    };
+    let integration_branch = sanitize_optional(raw.integration_branch)
+        .unwrap_or_else(|| integration_strategy.default_template().to_string());
+    let per_task = integration_branch.contains("{task_id}");
+    if integration_strategy == IntegrationStrategy::PerTask && !per_task {
+        bail!(
+            "{} has `[vcs].integration_branch = \"{}\"` without `{{task_id}}`; `per_task` needs one branch per task",
+            path.display(),
+            integration_branch
+        );
+    }
+    if integration_strategy == IntegrationStrategy::PerRun && per_task {
+        bail!(
+            "{} has `[vcs].integration_branch = \"{}\"` with `{{task_id}}`; set `integration_strategy = \"per_task\"` for per-task branches",
+            path.display(),
+            integration_branch
+        );
+    }
+    let vcs = VcsConfig {
+        integration_strategy,
+        integration_branch,
+    };
+    if vcs.integration_branch("run", "task").contains(['{', '}']) {
+        bail!(
+            "{} has `[vcs].integration_branch = \"{}\"` with an unknown placeholder; expected only `{{run_id}}` and `{{task_id}}`",
+            path.display(),
+            vcs.integration_branch
+        );
+    }
+    Ok(vcs)
+}
+
 fn sanitize_optional(value: Option<String>) -> Option<String> {
     value
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
 }
 
-fn sanitize_commands(commands: Vec<String>) -> Vec<String> {
-    commands
-        .into_iter()
-        .map(|c| c.trim().to_string())
-        .filter(|c| !c.is_empty())
-        .collect()
+fn sanitize_check_commands(commands: Vec<RawCheckCommand>, path: &Path) -> Result<Vec<CheckSpec>> {
+    let mut specs = Vec::new();
+    for (idx, raw) in commands.into_iter().enumerate() {
+        let (command, timeout_secs, workdir, stage) = match raw {
+            RawCheckCommand::Plain(command) => (command, None, None, CheckStage::PostReview),
+            RawCheckCommand::Table {
+                command,
+                timeout_secs,
+                workdir,
+                stage,
+            } => {
+                let command = command.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "{} missing `command` for `[checks].commands` at index {idx}",
+                        path.display()
+                    )
+                })?;
+                if timeout_secs == Some(0) {
+                    bail!(
+                        "{} has `timeout_secs = 0` for `[checks].commands` at index {idx}; expected a positive number of seconds",
+                        path.display()
+                    );
+                }
+                let workdir = workdir
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty())
+                    .map(|raw| {
+                        sanitize_relative_path(Path::new(&raw), "check workdir").with_context(
+                            || {
+                                format!(
+                                    "{} invalid `workdir` for `[checks].commands` at index {idx}",
+                                    path.display()
+                                )
+                            },
+                        )
+                    })
+                    .transpose()?;
+                let stage = match stage.as_deref().map(str::trim) {
+                    None | Some("post_review") => CheckStage::PostReview,
+                    Some("pre_review") => CheckStage::PreReview,
+                    Some(other) => bail!(
+                        "{} has unsupported `stage = \"{other}\"` for `[checks].commands` at index {idx}; expected `pre_review` or `post_review`",
+                        path.display()
+                    ),
+                };
+                (command, timeout_secs, workdir, stage)
+            }
+        };
+        let command = command.trim().to_string();
+        if command.is_empty() {
+            continue;
+        }
+        specs.push(CheckSpec {
+            command,
+            timeout_secs,
+            workdir,
+            stage,
+        });
+    }
+    Ok(specs)
 }
 
 fn validate_worktree_config(raw: RawWorktreeConfig, path: &Path) -> Result<WorktreeConfig> {
@@ -192,7 +572,23 @@ fn validate_worktree_config(raw: RawWorktreeConfig, path: &Path) -> Result<Workt
         .provision
         .map(|provision| validate_worktree_provision_config(provision, path))
         .transpose()?;
-    Ok(WorktreeConfig { provision })
+    let cleanup = match raw
+        .cleanup
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        None | Some("never") => WorktreeCleanup::Never,
+        Some("on_close") => WorktreeCleanup::OnClose,
+        Some(other) => {
+            bail!(
+                "{} has unsupported `[worktree].cleanup = \"{}\"`; expected `on_close` or `never`",
+                path.display(),
+                other
+            )
+        }
+    };
+    Ok(WorktreeConfig { provision, cleanup })
 }
 
 fn validate_worktree_provision_config(
@@ -239,7 +635,7 @@ fn validate_provisioned_file(
                 path.display()
             )
         })?;
-    let to = sanitize_destination_path(&to_raw).with_context(|| {
+    let to = sanitize_destination_path(Path::new(&to_raw)).with_context(|| {
         format!(
             "{} invalid `to` for `[[worktree.provision.files]]` at index {idx}",
             path.display()
@@ -266,24 +662,23 @@ fn validate_provisioned_file(
     })
 }
 
-fn sanitize_destination_path(raw: &str) -> Result<PathBuf> {
-    let path = PathBuf::from(raw);
-    if path.is_absolute() {
-        bail!("destination path must be relative");
-    }
-    let mut clean = PathBuf::new();
-    for component in path.components() {
-        match component {
-            Component::Normal(part) => clean.push(part),
-            Component::CurDir => {}
-            Component::ParentDir => bail!("destination path must not contain `..`"),
-            Component::RootDir | Component::Prefix(_) => bail!("destination path must be relative"),
+fn sanitize_agent_env(
+    env: BTreeMap<String, String>,
+    path: &Path,
+) -> Result<BTreeMap<String, String>> {
+    for key in env.keys() {
+        if key.is_empty() || key.contains('=') || key.contains('\0') {
+            bail!("{} has invalid `[agent].env` key `{key}`", path.display());
+        }
+        let upper = key.to_ascii_uppercase();
+        if upper.starts_with("THENCE_") || upper.starts_with("WHENCE_") {
+            bail!(
+                "{} has reserved `[agent].env` key `{key}`; THENCE_* and WHENCE_* are set by thence",
+                path.display()
+            );
         }
     }
-    if clean.as_os_str().is_empty() {
-        bail!("destination path is empty");
-    }
-    Ok(clean)
+    Ok(env)
 }
 
 #[cfg(test)]
@@ -311,10 +706,220 @@ commands = ["cargo check", "cargo test"]
         assert_eq!(cfg.version, 2);
         assert_eq!(
             cfg.checks.unwrap().commands,
-            vec!["cargo check".to_string(), "cargo test".to_string()]
+            vec![
+                CheckSpec::new("cargo check".to_string()),
+                CheckSpec::new("cargo test".to_string())
+            ]
         );
     }
 
+    #[test]
+    fn parses_agent_spawn_retries() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"
+version = 2
+[agent]
+command = "codex"
+spawn_retries = 5
+[checks]
+commands = ["true"]
+"#,
+        )
+        .unwrap();
+
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(cfg.agent.unwrap().spawn_retries, Some(5));
+    }
+
+    #[test]
+    fn rejects_reserved_agent_env_keys() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"
+version = 2
+[agent]
+env = { CARGO_HOME = "/tmp/cargo", THENCE_ROLE = "reviewer" }
+[checks]
+commands = ["true"]
+"#,
+        )
+        .unwrap();
+
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("reserved `[agent].env` key `THENCE_ROLE`"));
+    }
+
+    #[test]
+    fn parses_check_concurrency_and_rejects_zero() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "version = 2\n[checks]\ncommands = [\"true\"]\nconcurrency = 4\n",
+        )
+        .unwrap();
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(cfg.checks.unwrap().concurrency, Some(4));
+
+        std::fs::write(
+            &path,
+            "version = 2\n[checks]\ncommands = [\"true\"]\nconcurrency = 0\n",
+        )
+        .unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("`[checks].concurrency = 0`"));
+    }
+
+    #[test]
+    fn parses_task_checks_mode() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "version = 2\n[checks]\ncommands = [\"true\"]\ntask_checks = \"replace\"\n",
+        )
+        .unwrap();
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(cfg.checks.unwrap().task_checks, TaskChecksMode::Replace);
+
+        std::fs::write(
+            &path,
+            "version = 2\n[checks]\ncommands = [\"true\"]\ntask_override = \"append\"\n",
+        )
+        .unwrap();
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(cfg.checks.unwrap().task_checks, TaskChecksMode::Union);
+
+        std::fs::write(
+            &path,
+            "version = 2\n[checks]\ncommands = [\"true\"]\ntask_checks = \"merge\"\n",
+        )
+        .unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("unsupported `[checks].task_checks"));
+    }
+
+    #[test]
+    fn parses_check_commands_with_timeout_tables() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"
+version = 2
+[checks]
+commands = ["cargo clippy", { command = "cargo test --release", timeout_secs = 1200 }]
+"#,
+        )
+        .unwrap();
+
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(
+            cfg.checks.unwrap().commands,
+            vec![
+                CheckSpec::new("cargo clippy".to_string()),
+                CheckSpec {
+                    command: "cargo test --release".to_string(),
+                    timeout_secs: Some(1200),
+                    workdir: None,
+                    stage: CheckStage::PostReview,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_check_stage_and_rejects_unknown_stages() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"
+version = 2
+[checks]
+commands = [{ command = "cargo build", stage = "pre_review" }, "cargo test"]
+"#,
+        )
+        .unwrap();
+        let commands = load_repo_config(repo)
+            .unwrap()
+            .unwrap()
+            .checks
+            .unwrap()
+            .commands;
+        assert_eq!(commands[0].stage, CheckStage::PreReview);
+        assert_eq!(commands[1].stage, CheckStage::PostReview);
+
+        std::fs::write(
+            &path,
+            "version = 2\n[checks]\ncommands = [{ command = \"cargo build\", stage = \"early\" }]\n",
+        )
+        .unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(
+            format!("{err}").contains("unsupported `stage = \"early\"`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn parses_check_workdir_and_rejects_escaping_paths() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"
+version = 2
+[checks]
+commands = [{ command = "cargo test", workdir = "./crates/foo" }]
+"#,
+        )
+        .unwrap();
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(
+            cfg.checks.unwrap().commands[0].workdir,
+            Some(PathBuf::from("crates/foo"))
+        );
+
+        for (workdir, reason) in [
+            ("../etc", "must not contain `..`"),
+            ("/etc", "must be relative"),
+        ] {
+            std::fs::write(
+                &path,
+                format!(
+                    "version = 2\n[checks]\ncommands = [{{ command = \"true\", workdir = \"{workdir}\" }}]\n"
+                ),
+            )
+            .unwrap();
+            let err = load_repo_config(repo).unwrap_err();
+            assert!(
+                format!("{err:#}").contains("invalid `workdir` for `[checks].commands` at index 0"),
+                "{err:#}"
+            );
+            assert!(format!("{err:#}").contains(reason), "{err:#}");
+        }
+    }
+
     #[test]
     fn rejects_invalid_version() {
         let tmp = tempdir().unwrap();
@@ -384,6 +989,178 @@ reviewer = "Return strict JSON only."
         assert_eq!(reviewer, "Return strict JSON only.");
     }
 
+    #[test]
+    fn loads_run_max_attempts_and_rejects_zero() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "version = 2\n[run]\nmax_attempts = 5\n").unwrap();
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(cfg.run.and_then(|r| r.max_attempts), Some(5));
+
+        std::fs::write(&path, "version = 2\n[run]\nmax_attempts = 0\n").unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("`[run].max_attempts = 0`"));
+
+        std::fs::write(&path, "version = 2\n[run]\nmax_tasks = 0\n").unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("`[run].max_tasks = 0`"));
+
+        std::fs::write(&path, "version = 2\n[run]\nmax_total_attempts = 12\n").unwrap();
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(cfg.run.and_then(|r| r.max_total_attempts), Some(12));
+
+        std::fs::write(&path, "version = 2\n[run]\nmax_total_attempts = 0\n").unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("`[run].max_total_attempts = 0`"));
+    }
+
+    #[test]
+    fn loads_lease_windows_and_rejects_tick_not_below_stale() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "version = 2\n[run]\nlease_stale_secs = 600\nlease_tick_secs = 30\n",
+        )
+        .unwrap();
+        let run = load_repo_config(repo).unwrap().unwrap().run.unwrap();
+        assert_eq!(run.lease_stale_secs, Some(600));
+        assert_eq!(run.lease_tick_secs, Some(30));
+
+        std::fs::write(&path, "version = 2\n[run]\nlease_stale_secs = 10\n").unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("not below `lease_stale_secs = 10`"));
+    }
+
+    #[test]
+    fn loads_attempt_backoff_base() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "version = 2\n[run]\nattempt_backoff_secs = 5\n").unwrap();
+        let run = load_repo_config(repo).unwrap().unwrap().run.unwrap();
+        assert_eq!(run.attempt_backoff_secs, Some(5));
+        assert_eq!(run.max_attempts, None);
+    }
+
+    #[test]
+    fn loads_poll_interval_and_rejects_zero() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "version = 2\n[run]\npoll_interval_ms = 50\n").unwrap();
+        let run = load_repo_config(repo).unwrap().unwrap().run.unwrap();
+        assert_eq!(run.poll_interval_ms, Some(50));
+
+        std::fs::write(&path, "version = 2\n[run]\npoll_interval_ms = 0\n").unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("`[run].poll_interval_ms = 0`"));
+    }
+
+    #[test]
+    fn parses_vcs_integration_branch_strategies() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        std::fs::write(
+            &path,
+            "version = 2\n[vcs]\nintegration_strategy = \"per_task\"\n",
+        )
+        .unwrap();
+        let vcs = load_repo_config(repo).unwrap().unwrap().vcs.unwrap();
+        assert_eq!(vcs.integration_strategy, IntegrationStrategy::PerTask);
+        assert_eq!(vcs.integration_branch("r1", "task_a"), "thence/r1/task_a");
+        assert_eq!(
+            VcsConfig::default().integration_branch("r1", "task_a"),
+            "thence/r1"
+        );
+
+        std::fs::write(
+            &path,
+            "version = 2\n[vcs]\nintegration_strategy = \"per_task\"\nintegration_branch = \"work/{run_id}\"\n",
+        )
+        .unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("without `{task_id}`"), "{err}");
+
+        std::fs::write(
+            &path,
+            "version = 2\n[vcs]\nintegration_branch = \"work/{run}\"\n",
+        )
+        .unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("unknown placeholder"), "{err}");
+    }
+
+    #[test]
+    fn parses_drift_actions() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "version = 2\n[run]\nspec_drift = \"pause\"\n").unwrap();
+        let run = load_repo_config(repo).unwrap().unwrap().run.unwrap();
+        assert_eq!(run.spec_drift, Some(DriftAction::Pause));
+
+        std::fs::write(&path, "version = 2\n[run]\nspec_drift = \"ignore\"\n").unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err}").contains("unsupported `[run].spec_drift"));
+
+        std::fs::write(&path, "version = 2\n[run]\nconfig_drift = \"pause\"\n").unwrap();
+        let run = load_repo_config(repo).unwrap().unwrap().run.unwrap();
+        assert_eq!(run.config_drift, Some(DriftAction::Pause));
+    }
+
+    #[test]
+    fn loads_run_artifacts_dir() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "version = 2\n[run]\nartifacts_dir = \" ../thence-runs \"\n",
+        )
+        .unwrap();
+        let run = load_repo_config(repo).unwrap().unwrap().run.unwrap();
+        assert_eq!(run.artifacts_dir, Some(PathBuf::from("../thence-runs")));
+
+        std::fs::write(&path, "version = 2\n[run]\nartifacts_dir = \"\"\n").unwrap();
+        let run = load_repo_config(repo).unwrap().unwrap().run.unwrap();
+        assert_eq!(run.artifacts_dir, None);
+    }
+
+    #[test]
+    fn loads_implementer_prompt_override() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            r#"
+version = 2
+[checks]
+commands = ["cargo test"]
+[prompts]
+implementer = "Keep diffs small."
+"#,
+        )
+        .unwrap();
+
+        let prompts = load_repo_config(repo).unwrap().unwrap().prompts.unwrap();
+        assert_eq!(prompts.implementer.as_deref(), Some("Keep diffs small."));
+        assert_eq!(prompts.reviewer, None);
+    }
+
     #[test]
     fn parses_worktree_provisioning_with_defaults() {
         let tmp = tempdir().unwrap();
@@ -465,6 +1242,21 @@ to = "../.env"
         assert!(format!("{err}").contains("invalid `to`"));
     }
 
+    #[test]
+    fn parses_worktree_cleanup_policy() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        let path = repo.join(".thence").join("config.toml");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "version = 2\n[worktree]\ncleanup = \"on_close\"\n").unwrap();
+        let cfg = load_repo_config(repo).unwrap().unwrap();
+        assert_eq!(cfg.worktree.unwrap().cleanup, WorktreeCleanup::OnClose);
+
+        std::fs::write(&path, "version = 2\n[worktree]\ncleanup = \"sometimes\"\n").unwrap();
+        let err = load_repo_config(repo).unwrap_err();
+        assert!(format!("{err:#}").contains("unsupported `[worktree].cleanup"));
+    }
+
     #[test]
     fn rejects_unknown_worktree_provision_mode() {
         let tmp = tempdir().unwrap();
@@ -1,11 +1,12 @@
 pub mod projector;
 pub mod schema;
+pub mod schema_doc;
 pub mod store;
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EventRow {
     pub seq: i64,
     pub run_id: String,
@@ -19,6 +20,12 @@ pub struct EventRow {
     pub dedupe_key: Option<String>,
 }
 
+/// Dedupe key for a lifecycle event that happens at most once per task attempt, so a supervisor
+/// step retried after a crash cannot record it twice. Attempt numbers are never reused.
+pub fn attempt_dedupe_key(event_type: &str, task_id: &str, attempt: i64) -> String {
+    format!("{event_type}:{task_id}:{attempt}")
+}
+
 #[derive(Debug, Clone)]
 pub struct NewEvent {
     pub event_type: String,
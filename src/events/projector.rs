@@ -2,25 +2,46 @@ use crate::events::EventRow;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap, HashSet};
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Version stamped on saved snapshots. Bump it whenever the projection's fields or
+/// `apply_event` change, so snapshots from an older build are replayed from scratch instead of
+/// read with defaulted fields.
+pub const PROJECTION_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct TaskProjection {
     pub id: String,
     pub objective: String,
     pub acceptance: String,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<String>,
     pub dependencies: Vec<String>,
     pub required_checks: Vec<String>,
+    #[serde(default)]
+    pub priority: i64,
     pub attempts: i64,
     pub claimed: bool,
     pub latest_attempt: i64,
     pub review_approved_attempts: HashSet<i64>,
     pub checks_passed_attempts: HashSet<i64>,
+    /// Commands that failed in each attempt's most recent `checks_reported`; attempts whose
+    /// latest report passed have no entry.
+    #[serde(default)]
+    pub failed_checks: BTreeMap<i64, Vec<String>>,
     pub unresolved_findings_attempts: HashSet<i64>,
     pub merged_attempts: HashSet<i64>,
     pub closed: bool,
     pub terminal_failed: bool,
+    /// Never attempted because a dependency failed terminally (or was itself skipped).
+    #[serde(default)]
+    pub skipped: bool,
+    #[serde(default)]
+    pub deadline_secs: Option<u64>,
+    /// Timestamp of the first `task_claimed`; a task deadline is measured from here.
+    #[serde(default)]
+    pub first_claimed_at: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct RunProjection {
     pub run_id: String,
     pub spec_approved: bool,
@@ -30,6 +51,9 @@ pub struct RunProjection {
     pub terminal: Option<String>,
     pub tasks: BTreeMap<String, TaskProjection>,
     pub open_questions: HashMap<String, String>,
+    /// Error detail of the most recent plan translation failure, if any.
+    #[serde(default)]
+    pub last_translation_error: Option<String>,
 }
 
 impl RunProjection {
@@ -66,6 +90,23 @@ impl RunProjection {
                         .and_then(|v| v.as_str())
                         .unwrap_or("")
                         .to_string();
+                    // Runs registered before acceptance lists carry only the joined string.
+                    let acceptance_criteria = ev
+                        .payload_json
+                        .get("acceptance_criteria")
+                        .and_then(|v| v.as_array())
+                        .map(|a| {
+                            a.iter()
+                                .filter_map(|v| v.as_str().map(ToString::to_string))
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_else(|| {
+                            if acceptance.is_empty() {
+                                Vec::new()
+                            } else {
+                                vec![acceptance.clone()]
+                            }
+                        });
                     let checks = ev
                         .payload_json
                         .get("checks")
@@ -76,12 +117,24 @@ impl RunProjection {
                                 .collect::<Vec<_>>()
                         })
                         .unwrap_or_default();
+                    let priority = ev
+                        .payload_json
+                        .get("priority")
+                        .and_then(|v| v.as_i64())
+                        .unwrap_or(0);
+                    let deadline_secs = ev
+                        .payload_json
+                        .get("deadline_secs")
+                        .and_then(|v| v.as_u64());
                     self.tasks.entry(task_id.clone()).or_insert(TaskProjection {
                         id: task_id,
                         objective,
                         acceptance,
+                        acceptance_criteria,
                         dependencies: deps,
                         required_checks: checks,
+                        priority,
+                        deadline_secs,
                         ..TaskProjection::default()
                     });
                 }
@@ -123,6 +176,9 @@ impl RunProjection {
                     task.claimed = true;
                     task.attempts += 1;
                     task.latest_attempt = ev.attempt.unwrap_or(task.attempts);
+                    if task.first_claimed_at.is_none() {
+                        task.first_claimed_at = Some(ev.ts.clone());
+                    }
                 }
             }
             "review_found_issues" => {
@@ -149,8 +205,20 @@ impl RunProjection {
                         .unwrap_or(false)
                     {
                         task.checks_passed_attempts.insert(attempt);
+                        task.failed_checks.remove(&attempt);
                     } else {
                         task.checks_passed_attempts.remove(&attempt);
+                        let failed = ev
+                            .payload_json
+                            .get("results")
+                            .and_then(|v| v.as_array())
+                            .into_iter()
+                            .flatten()
+                            .filter(|r| r.get("ok").and_then(|v| v.as_bool()) != Some(true))
+                            .filter_map(|r| r.get("command").and_then(|v| v.as_str()))
+                            .map(ToString::to_string)
+                            .collect();
+                        task.failed_checks.insert(attempt, failed);
                     }
                 }
             }
@@ -172,11 +240,27 @@ impl RunProjection {
                     task.claimed = false;
                 }
             }
+            "task_skipped" => {
+                if let Some(task) = ev.task_id.as_ref().and_then(|id| self.tasks.get_mut(id)) {
+                    task.skipped = true;
+                    task.claimed = false;
+                }
+            }
             "attempt_interrupted" => {
                 if let Some(task) = ev.task_id.as_ref().and_then(|id| self.tasks.get_mut(id)) {
                     task.claimed = false;
                 }
             }
+            "translation_failed" => {
+                self.last_translation_error = ev
+                    .payload_json
+                    .get("error")
+                    .and_then(|v| v.as_str())
+                    .map(ToString::to_string);
+            }
+            "plan_translated" => {
+                self.last_translation_error = None;
+            }
             "run_completed" | "run_failed" | "run_cancelled" => {
                 self.terminal = Some(ev.event_type.clone());
             }
@@ -191,4 +275,13 @@ impl RunProjection {
         }
         s
     }
+
+    /// Fold only the events after `snapshot_seq` on top of a previously saved projection.
+    pub fn replay_from(snapshot: (i64, Self), events: &[EventRow]) -> Self {
+        let (snapshot_seq, mut s) = snapshot;
+        for ev in events.iter().filter(|ev| ev.seq > snapshot_seq) {
+            s.apply_event(ev);
+        }
+        s
+    }
 }
@@ -1,5 +1,14 @@
 use rusqlite::{Connection, Result};
 
+/// Schema version this build writes to `PRAGMA user_version`; bump it whenever the tables
+/// below change so older builds refuse the DB instead of misreading it.
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// The version stamped by the last `migrate`, or 0 for a new or pre-versioning DB.
+pub fn stored_version(conn: &Connection) -> Result<i64> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
 pub fn migrate(conn: &Connection) -> Result<()> {
     conn.pragma_update(None, "journal_mode", "WAL")?;
     conn.pragma_update(None, "synchronous", "NORMAL")?;
@@ -43,6 +52,7 @@ pub fn migrate(conn: &Connection) -> Result<()> {
         );
         ",
     )?;
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
 
     Ok(())
 }
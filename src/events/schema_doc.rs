@@ -0,0 +1,410 @@
+use serde_json::{Map, Value, json};
+
+/// One payload field of an event type. `kind` is a JSON Schema type name.
+#[derive(Debug, Clone, Copy)]
+pub struct PayloadField {
+    pub name: &'static str,
+    pub kind: &'static str,
+    pub required: bool,
+}
+
+/// Contract for one `event_type`: what it records and the payload fields its emit sites write.
+#[derive(Debug, Clone, Copy)]
+pub struct EventSchema {
+    pub event_type: &'static str,
+    pub description: &'static str,
+    /// Whether the event row carries a `task_id`.
+    pub task_scoped: bool,
+    pub payload: &'static [PayloadField],
+}
+
+const fn req(name: &'static str, kind: &'static str) -> PayloadField {
+    PayloadField {
+        name,
+        kind,
+        required: true,
+    }
+}
+
+const fn opt(name: &'static str, kind: &'static str) -> PayloadField {
+    PayloadField {
+        name,
+        kind,
+        required: false,
+    }
+}
+
+const fn run_event(
+    event_type: &'static str,
+    description: &'static str,
+    payload: &'static [PayloadField],
+) -> EventSchema {
+    EventSchema {
+        event_type,
+        description,
+        task_scoped: false,
+        payload,
+    }
+}
+
+const fn task_event(
+    event_type: &'static str,
+    description: &'static str,
+    payload: &'static [PayloadField],
+) -> EventSchema {
+    EventSchema {
+        event_type,
+        description,
+        task_scoped: true,
+        payload,
+    }
+}
+
+/// Every event type thence appends, kept in step with the emit sites under `run/`.
+pub const EVENT_SCHEMAS: &[EventSchema] = &[
+    run_event(
+        "run_started",
+        "A new run was created from a plan file.",
+        &[
+            req("plan_file", "string"),
+            req("agent", "string"),
+            req("workers", "integer"),
+            req("reviewers", "integer"),
+            opt("config_sha256", "string"),
+            opt("config_structure", "object"),
+        ],
+    ),
+    run_event(
+        "plan_translated",
+        "The spec was translated to SPL and the translated plan frozen under the run dir.",
+        &[
+            req("source", "string"),
+            opt("spl_path", "string"),
+            opt("translated_plan_path", "string"),
+            opt("frozen_spec_path", "string"),
+            opt("task_count", "integer"),
+            opt("translator_stdout_path", "string"),
+            opt("translator_stderr_path", "string"),
+            opt("approved", "boolean"),
+        ],
+    ),
+    run_event(
+        "translation_failed",
+        "The translator could not produce a usable plan.",
+        &[
+            req("error", "string"),
+            opt("translator_stdout_tail", "string"),
+            opt("translator_stderr_tail", "string"),
+        ],
+    ),
+    run_event(
+        "plan_validated",
+        "The translated plan passed validation.",
+        &[req("ok", "boolean"), opt("source", "string")],
+    ),
+    task_event(
+        "task_registered",
+        "A plan task was registered with the run.",
+        &[
+            req("task_id", "string"),
+            req("objective", "string"),
+            req("acceptance", "string"),
+            opt("acceptance_criteria", "array"),
+            req("dependencies", "array"),
+            req("checks", "array"),
+            req("priority", "integer"),
+            opt("deadline_secs", "integer"),
+        ],
+    ),
+    run_event(
+        "spec_question_opened",
+        "A question about the spec or checks now blocks the run.",
+        &[
+            req("question_id", "string"),
+            req("question", "string"),
+            opt("source", "string"),
+            opt("translator_stdout_tail", "string"),
+            opt("translator_stderr_tail", "string"),
+        ],
+    ),
+    run_event(
+        "spec_question_resolved",
+        "An open question was answered.",
+        &[req("question_id", "string")],
+    ),
+    run_event(
+        "spec_approved",
+        "The spec gate passed; tasks may be claimed.",
+        &[req("approved", "boolean"), opt("source", "string")],
+    ),
+    run_event(
+        "spec_drift_detected",
+        "The plan file changed since the run froze it.",
+        &[
+            req("plan_path", "string"),
+            req("frozen_spec_path", "string"),
+            req("frozen_sha256", "string"),
+            req("live_sha256", "string"),
+            req("action", "string"),
+        ],
+    ),
+    run_event(
+        "config_drift_detected",
+        "Structural settings in .thence/config.toml changed since the run started.",
+        &[
+            req("changed", "array"),
+            req("recorded_sha256", "string"),
+            req("live_sha256", "string"),
+            req("action", "string"),
+        ],
+    ),
+    run_event(
+        "checks_proposed",
+        "The agent proposed check commands for human approval.",
+        &[
+            req("commands", "array"),
+            req("question_id", "string"),
+            opt("rationale", "string"),
+        ],
+    ),
+    run_event(
+        "checks_approved",
+        "The run-level check commands were fixed.",
+        &[
+            req("commands", "array"),
+            opt("source", "string"),
+            opt("source_path", "string"),
+        ],
+    ),
+    run_event(
+        "human_input_requested",
+        "The run needs an answer before it can continue.",
+        &[req("question_id", "string"), req("command", "string")],
+    ),
+    run_event(
+        "human_input_provided",
+        "A human answered an open question; a rejection leaves it open.",
+        &[
+            req("question_id", "string"),
+            req("text", "string"),
+            opt("rejected", "boolean"),
+        ],
+    ),
+    run_event(
+        "run_paused",
+        "The run stopped until resumed; `next` lists the commands to continue.",
+        &[req("next", "array"), opt("reason", "string")],
+    ),
+    run_event(
+        "run_resumed",
+        "A paused run was resumed.",
+        &[req("reason", "string")],
+    ),
+    task_event(
+        "task_claimed",
+        "A worker claimed the next attempt of a task.",
+        &[req("attempt", "integer")],
+    ),
+    task_event(
+        "work_submitted",
+        "The implementer finished an attempt.",
+        &[
+            req("exit_code", "integer"),
+            req("stdout_path", "string"),
+            req("stderr_path", "string"),
+            req("capsule_path", "string"),
+            req("lease_path", "string"),
+            opt("diff_path", "string"),
+            opt("duration_ms", "integer"),
+            req("output_valid", "boolean"),
+            opt("output_error", "string"),
+            opt("agent_model", "string"),
+            opt("agent_version", "string"),
+        ],
+    ),
+    task_event(
+        "review_requested",
+        "An attempt was handed to a reviewer.",
+        &[req("attempt", "integer"), req("capsule_path", "string")],
+    ),
+    task_event(
+        "review_found_issues",
+        "An attempt was sent back with findings.",
+        &[
+            req("reason", "string"),
+            opt("findings", "array"),
+            opt("structured_findings", "array"),
+            opt("source", "string"),
+            opt("timed_out", "boolean"),
+            opt("duration_ms", "integer"),
+            opt("agent_model", "string"),
+            opt("agent_version", "string"),
+        ],
+    ),
+    task_event(
+        "review_approved",
+        "The reviewer approved an attempt.",
+        &[
+            req("approved", "boolean"),
+            req("finding_count", "integer"),
+            opt("advisories", "array"),
+            opt("duration_ms", "integer"),
+            opt("agent_model", "string"),
+            opt("agent_version", "string"),
+        ],
+    ),
+    task_event(
+        "checks_reported",
+        "Check commands ran against an attempt's worktree.",
+        &[
+            req("passed", "boolean"),
+            req("results", "array"),
+            opt("stage", "string"),
+            opt("checks_source", "string"),
+            opt("duration_ms", "integer"),
+        ],
+    ),
+    task_event(
+        "merge_succeeded",
+        "An approved attempt merged onto the integration branch.",
+        &[req("integration_branch", "string")],
+    ),
+    task_event(
+        "merge_conflict",
+        "An approved attempt conflicted with the integration branch.",
+        &[req("reason", "string"), req("integration_branch", "string")],
+    ),
+    task_event(
+        "task_closed",
+        "A task's merged attempt closed it.",
+        &[req("closed", "boolean")],
+    ),
+    task_event(
+        "task_failed_terminal",
+        "A task ran out of attempts or time and will not be retried.",
+        &[
+            req("reason", "string"),
+            opt("failed_worktree", "string"),
+            opt("deadline_secs", "integer"),
+            opt("elapsed_secs", "integer"),
+            opt("attempts", "integer"),
+        ],
+    ),
+    task_event(
+        "task_skipped",
+        "A task was never attempted because a dependency did not finish.",
+        &[req("reason", "string"), req("dependency", "string")],
+    ),
+    task_event(
+        "attempt_interrupted",
+        "An in-flight attempt was abandoned and may be retried.",
+        &[req("reason", "string"), opt("lease", "object")],
+    ),
+    run_event(
+        "run_completed",
+        "Every task finished and the run succeeded.",
+        &[req("task_count", "integer"), req("summary", "object")],
+    ),
+    run_event(
+        "run_failed",
+        "The run ended without completing.",
+        &[
+            req("summary", "object"),
+            opt("reason", "string"),
+            opt("task_count", "integer"),
+        ],
+    ),
+    run_event(
+        "run_cancelled",
+        "A user cancelled the run.",
+        &[req("reason", "string")],
+    ),
+];
+
+pub fn event_schema(event_type: &str) -> Option<&'static EventSchema> {
+    EVENT_SCHEMAS.iter().find(|s| s.event_type == event_type)
+}
+
+/// The table as a JSON document: each event's payload is described as a JSON Schema object.
+pub fn events_schema_json() -> Value {
+    let events = EVENT_SCHEMAS
+        .iter()
+        .map(|schema| {
+            let properties = schema
+                .payload
+                .iter()
+                .map(|f| (f.name.to_string(), json!({"type": f.kind})))
+                .collect::<Map<_, _>>();
+            let required = schema
+                .payload
+                .iter()
+                .filter(|f| f.required)
+                .map(|f| f.name)
+                .collect::<Vec<_>>();
+            json!({
+                "event_type": schema.event_type,
+                "description": schema.description,
+                "task_scoped": schema.task_scoped,
+                "payload": {
+                    "type": "object",
+                    "properties": properties,
+                    "required": required
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({ "events": events })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Event types named in the projector's match arms, e.g. `"run_paused" | "human_input_requested" =>`.
+    fn projector_event_types() -> Vec<String> {
+        include_str!("projector.rs")
+            .lines()
+            .map(str::trim)
+            .filter(|line| line.starts_with('"') && line.contains("=>"))
+            .flat_map(|line| {
+                let arms = line.split("=>").next().unwrap_or("");
+                arms.split('|')
+                    .map(|arm| arm.trim().trim_matches('"').to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_projected_event_type_has_a_schema() {
+        let types = projector_event_types();
+        assert!(types.contains(&"task_registered".to_string()));
+        assert!(types.contains(&"run_cancelled".to_string()));
+        for event_type in &types {
+            assert!(
+                event_schema(event_type).is_some(),
+                "`{event_type}` is projected but missing from EVENT_SCHEMAS"
+            );
+        }
+    }
+
+    #[test]
+    fn schema_table_has_unique_event_types_and_fields() {
+        let mut seen = HashSet::new();
+        for schema in EVENT_SCHEMAS {
+            assert!(seen.insert(schema.event_type), "{}", schema.event_type);
+            let mut fields = HashSet::new();
+            for field in schema.payload {
+                assert!(
+                    fields.insert(field.name),
+                    "{}.{}",
+                    schema.event_type,
+                    field.name
+                );
+            }
+        }
+        let doc = events_schema_json();
+        assert_eq!(doc["events"].as_array().unwrap().len(), EVENT_SCHEMAS.len());
+    }
+}
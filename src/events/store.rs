@@ -1,7 +1,8 @@
+use crate::events::projector::{PROJECTION_VERSION, RunProjection};
 use crate::events::{EventRow, NewEvent, schema};
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use chrono::Utc;
-use rusqlite::{Connection, OptionalExtension, params};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, params};
 use serde_json::Value;
 use std::path::Path;
 
@@ -20,6 +21,21 @@ pub struct EventStore {
     conn: Connection,
 }
 
+/// Refuse a DB stamped by a newer thence: its tables may have changed in ways this build
+/// would misread or corrupt.
+fn ensure_supported_schema(conn: &Connection, path: &Path) -> Result<()> {
+    let version = schema::stored_version(conn)
+        .with_context(|| format!("read schema version of {}", path.display()))?;
+    if version > schema::SCHEMA_VERSION {
+        bail!(
+            "state DB {} has schema version {version}, newer than version {} supported by this thence; upgrade thence to use it",
+            path.display(),
+            schema::SCHEMA_VERSION
+        );
+    }
+    Ok(())
+}
+
 impl EventStore {
     pub fn open(path: &Path) -> Result<Self> {
         if let Some(parent) = path.parent() {
@@ -28,10 +44,23 @@ impl EventStore {
         }
         let conn =
             Connection::open(path).with_context(|| format!("open sqlite db {}", path.display()))?;
+        ensure_supported_schema(&conn, path)?;
         schema::migrate(&conn)?;
         Ok(Self { conn })
     }
 
+    /// Open an existing DB for inspection only: no migrations run and every write fails, so
+    /// it can be read safely while a run holds the DB.
+    pub fn open_read_only(path: &Path) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .with_context(|| format!("open sqlite db {} read-only", path.display()))?;
+        ensure_supported_schema(&conn, path)?;
+        Ok(Self { conn })
+    }
+
     pub fn create_run(&self, row: &RunRow) -> Result<()> {
         self.conn.execute(
             "INSERT INTO runs (id, plan_path, plan_sha256, spl_plan_path, created_at, status, config_json)
@@ -65,28 +94,42 @@ impl EventStore {
         Ok(())
     }
 
+    /// Remove a run with its events and snapshots. Returns the number of events removed.
+    pub fn delete_run(&self, run_id: &str) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let events = tx.execute("DELETE FROM events WHERE run_id = ?1", params![run_id])?;
+        tx.execute("DELETE FROM snapshots WHERE run_id = ?1", params![run_id])?;
+        tx.execute("DELETE FROM runs WHERE id = ?1", params![run_id])?;
+        tx.commit()?;
+        Ok(events)
+    }
+
     pub fn get_run(&self, run_id: &str) -> Result<Option<RunRow>> {
         self.conn
             .query_row(
                 "SELECT id, plan_path, plan_sha256, spl_plan_path, created_at, status, config_json FROM runs WHERE id = ?1",
                 params![run_id],
-                |row| {
-                    let cfg: String = row.get(6)?;
-                    Ok(RunRow {
-                        id: row.get(0)?,
-                        plan_path: row.get(1)?,
-                        plan_sha256: row.get(2)?,
-                        spl_plan_path: row.get(3)?,
-                        created_at: row.get(4)?,
-                        status: row.get(5)?,
-                        config_json: serde_json::from_str(&cfg).unwrap_or(Value::Null),
-                    })
-                },
+                run_row_from_sql,
             )
             .optional()
             .map_err(Into::into)
     }
 
+    pub fn list_all_runs(&self) -> Result<Vec<RunRow>> {
+        self.list_runs(None)
+    }
+
+    pub fn list_runs(&self, status: Option<&str>) -> Result<Vec<RunRow>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, plan_path, plan_sha256, spl_plan_path, created_at, status, config_json
+             FROM runs WHERE ?1 IS NULL OR status = ?1 ORDER BY created_at DESC",
+        )?;
+        let rows = stmt
+            .query_map(params![status], run_row_from_sql)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
     pub fn list_resumable_run_ids(&self) -> Result<Vec<String>> {
         let mut stmt = self
             .conn
@@ -125,31 +168,88 @@ impl EventStore {
     }
 
     pub fn list_events(&self, run_id: &str) -> Result<Vec<EventRow>> {
+        self.list_events_since(run_id, 0)
+    }
+
+    /// Events with `seq > after_seq`, oldest first, for consumers polling incrementally.
+    pub fn list_events_since(&self, run_id: &str, after_seq: i64) -> Result<Vec<EventRow>> {
+        self.list_events_in_range(run_id, Some(after_seq.saturating_add(1)), None)
+    }
+
+    /// Events with `from_seq <= seq <= to_seq` (both bounds inclusive and optional), oldest first.
+    pub fn list_events_in_range(
+        &self,
+        run_id: &str,
+        from_seq: Option<i64>,
+        to_seq: Option<i64>,
+    ) -> Result<Vec<EventRow>> {
+        let from = from_seq.unwrap_or(i64::MIN);
+        let to = to_seq.unwrap_or(i64::MAX);
+        if from > to {
+            bail!("invalid seq range: from {from} is greater than to {to}");
+        }
+        let mut stmt = self.conn.prepare(
+            "SELECT seq, run_id, ts, event_type, task_id, actor_role, actor_id, attempt, payload_json, dedupe_key
+             FROM events WHERE run_id = ?1 AND seq BETWEEN ?2 AND ?3 ORDER BY seq ASC",
+        )?;
+
+        let rows = stmt
+            .query_map(params![run_id, from, to], event_row_from_sql)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// Events of a single type, oldest first.
+    pub fn list_events_of_type(&self, run_id: &str, event_type: &str) -> Result<Vec<EventRow>> {
         let mut stmt = self.conn.prepare(
             "SELECT seq, run_id, ts, event_type, task_id, actor_role, actor_id, attempt, payload_json, dedupe_key
-             FROM events WHERE run_id = ?1 ORDER BY seq ASC",
+             FROM events WHERE run_id = ?1 AND event_type = ?2 ORDER BY seq ASC",
         )?;
 
         let rows = stmt
-            .query_map(params![run_id], |row| {
-                let payload_str: String = row.get(8)?;
-                Ok(EventRow {
-                    seq: row.get(0)?,
-                    run_id: row.get(1)?,
-                    ts: row.get(2)?,
-                    event_type: row.get(3)?,
-                    task_id: row.get(4)?,
-                    actor_role: row.get(5)?,
-                    actor_id: row.get(6)?,
-                    attempt: row.get(7)?,
-                    payload_json: serde_json::from_str(&payload_str).unwrap_or(Value::Null),
-                    dedupe_key: row.get(9)?,
-                })
-            })?
+            .query_map(params![run_id, event_type], event_row_from_sql)?
             .collect::<std::result::Result<Vec<_>, _>>()?;
         Ok(rows)
     }
 
+    pub fn save_snapshot(&self, run_id: &str, seq: i64, state: &RunProjection) -> Result<()> {
+        let state_json = serde_json::json!({
+            "projection_version": PROJECTION_VERSION,
+            "state": state,
+        });
+        self.conn.execute(
+            "INSERT OR REPLACE INTO snapshots (run_id, seq, state_json) VALUES (?1, ?2, ?3)",
+            params![run_id, seq, state_json.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// The latest snapshot, or `None` when there is none or it was saved by a build with a
+    /// different `PROJECTION_VERSION`; callers then replay from the first event.
+    pub fn load_latest_snapshot(&self, run_id: &str) -> Result<Option<(i64, RunProjection)>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT seq, state_json FROM snapshots WHERE run_id = ?1 ORDER BY seq DESC LIMIT 1",
+                params![run_id],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        let Some((seq, state_json)) = row else {
+            return Ok(None);
+        };
+        let mut stored: Value = serde_json::from_str(&state_json)
+            .with_context(|| format!("parse snapshot for run {run_id} at seq {seq}"))?;
+        if stored.get("projection_version").and_then(Value::as_u64)
+            != Some(u64::from(PROJECTION_VERSION))
+        {
+            return Ok(None);
+        }
+        let state = serde_json::from_value(stored["state"].take())
+            .with_context(|| format!("parse snapshot for run {run_id} at seq {seq}"))?;
+        Ok(Some((seq, state)))
+    }
+
     pub fn unresolved_questions(&self, run_id: &str) -> Result<Vec<(String, String)>> {
         let events = self.list_events(run_id)?;
         let mut opened = Vec::new();
@@ -183,3 +283,32 @@ impl EventStore {
             .collect())
     }
 }
+
+fn event_row_from_sql(row: &rusqlite::Row<'_>) -> rusqlite::Result<EventRow> {
+    let payload_str: String = row.get(8)?;
+    Ok(EventRow {
+        seq: row.get(0)?,
+        run_id: row.get(1)?,
+        ts: row.get(2)?,
+        event_type: row.get(3)?,
+        task_id: row.get(4)?,
+        actor_role: row.get(5)?,
+        actor_id: row.get(6)?,
+        attempt: row.get(7)?,
+        payload_json: serde_json::from_str(&payload_str).unwrap_or(Value::Null),
+        dedupe_key: row.get(9)?,
+    })
+}
+
+fn run_row_from_sql(row: &rusqlite::Row<'_>) -> rusqlite::Result<RunRow> {
+    let cfg: String = row.get(6)?;
+    Ok(RunRow {
+        id: row.get(0)?,
+        plan_path: row.get(1)?,
+        plan_sha256: row.get(2)?,
+        spl_plan_path: row.get(3)?,
+        created_at: row.get(4)?,
+        status: row.get(5)?,
+        config_json: serde_json::from_str(&cfg).unwrap_or(Value::Null),
+    })
+}
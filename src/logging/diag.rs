@@ -0,0 +1,67 @@
+//! Leveled diagnostics for people watching a run: pause guidance, warnings, and (with
+//! `--verbose`) progress detail. Everything goes to stderr; command output on stdout, such as
+//! tables, JSON, and the final `Run ... finished with ...` line, is never filtered.
+//! With `--attach`, agent output is echoed here too.
+
+use std::fmt::Display;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Only errors, which are returned to the caller rather than logged.
+    Quiet,
+    /// Warnings and guidance on what to run next.
+    #[default]
+    Normal,
+    /// Also progress detail from the supervisor.
+    Verbose,
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Normal as u8);
+static ATTACH: AtomicBool = AtomicBool::new(false);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Quiet,
+        1 => Level::Normal,
+        _ => Level::Verbose,
+    }
+}
+
+pub fn warn(msg: impl Display) {
+    if level() >= Level::Normal {
+        eprintln!("warning: {msg}");
+    }
+}
+
+pub fn info(msg: impl Display) {
+    if level() >= Level::Normal {
+        eprintln!("{msg}");
+    }
+}
+
+pub fn debug(msg: impl Display) {
+    if level() >= Level::Verbose {
+        eprintln!("{msg}");
+    }
+}
+
+/// Echo subprocess agent stdout/stderr to stderr as it is written (`--attach`).
+pub fn set_attach(attach: bool) {
+    ATTACH.store(attach, Ordering::Relaxed);
+}
+
+pub fn attached() -> bool {
+    ATTACH.load(Ordering::Relaxed)
+}
+
+/// One line of attached agent output; shown at every level since `--attach` asked for it.
+pub fn agent_output(prefix: &str, line: &str) {
+    let mut err = std::io::stderr().lock();
+    let _ = writeln!(err, "{prefix} {}", line.trim_end_matches(['\r', '\n']));
+}
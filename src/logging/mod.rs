@@ -1 +1,2 @@
+pub mod diag;
 pub mod ndjson;
@@ -3,13 +3,45 @@ use anyhow::Result;
 use serde_json::json;
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub fn mirror_event(path: &Path, ev: &EventRow) -> Result<()> {
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// seq/ts/event/task/attempt only.
+    #[default]
+    Compact,
+    /// The complete event row, including actors, payload, and dedupe key.
+    Full,
+}
+
+/// Live NDJSON mirror target for appended events.
+#[derive(Debug, Clone)]
+pub struct EventLog {
+    pub path: PathBuf,
+    pub format: LogFormat,
+}
+
+impl EventLog {
+    pub fn mirror(&self, ev: &EventRow) -> Result<()> {
+        match self.format {
+            LogFormat::Compact => mirror_event(&self.path, ev),
+            LogFormat::Full => {
+                let mut f = open_append(&self.path)?;
+                write_full_event(&mut f, ev)
+            }
+        }
+    }
+}
+
+fn open_append(path: &Path) -> Result<std::fs::File> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
-    let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+    Ok(OpenOptions::new().create(true).append(true).open(path)?)
+}
+
+pub fn mirror_event(path: &Path, ev: &EventRow) -> Result<()> {
+    let mut f = open_append(path)?;
     let line = json!({
         "seq": ev.seq,
         "ts": ev.ts,
@@ -20,3 +52,9 @@ pub fn mirror_event(path: &Path, ev: &EventRow) -> Result<()> {
     writeln!(f, "{}", line)?;
     Ok(())
 }
+
+/// Write the complete event row as a single NDJSON line.
+pub fn write_full_event(out: &mut impl Write, ev: &EventRow) -> Result<()> {
+    writeln!(out, "{}", serde_json::to_string(ev)?)?;
+    Ok(())
+}
@@ -2,3 +2,4 @@ pub mod review_loop;
 pub mod sanity;
 pub mod translator;
 pub mod validate;
+pub mod yaml;
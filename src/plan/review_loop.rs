@@ -1,4 +1,6 @@
 use crate::plan::translator::TranslatedPlan;
+use crate::workers::provider::{AgentProvider, AgentRequest};
+use serde_json::Value;
 
 #[derive(Debug)]
 pub enum SpecReviewOutcome {
@@ -26,3 +28,48 @@ pub fn review_spec(markdown: &str, plan: &TranslatedPlan) -> SpecReviewOutcome {
 
     SpecReviewOutcome::Approved
 }
+
+/// Ask a `spec-reviewer` agent for ambiguities. Any provider error, non-zero exit or unusable
+/// response falls back to the keyword heuristic in `review_spec`.
+pub fn review_spec_with_agent(
+    provider: &dyn AgentProvider,
+    req: AgentRequest,
+    markdown: &str,
+    plan: &TranslatedPlan,
+) -> SpecReviewOutcome {
+    provider
+        .run(req)
+        .ok()
+        .filter(|res| res.exit_code == 0)
+        .and_then(|res| res.structured_output)
+        .and_then(|output| parse_spec_review_output(&output))
+        .unwrap_or_else(|| review_spec(markdown, plan))
+}
+
+fn parse_spec_review_output(output: &Value) -> Option<SpecReviewOutcome> {
+    if output.get("approved")?.as_bool()? {
+        return Some(SpecReviewOutcome::Approved);
+    }
+    let question = output
+        .get("question")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|q| !q.is_empty())?
+        .to_string();
+    // Answers only re-open the spec gate for `spec-q-*` ids, and the translate/validate ids
+    // belong to other gates.
+    let question_id = match output
+        .get("question_id")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+    {
+        Some("spec-q-translate" | "spec-q-validate") | None => "spec-q-agent".to_string(),
+        Some(id) if id.starts_with("spec-q-") => id.to_string(),
+        Some(id) => format!("spec-q-{id}"),
+    };
+    Some(SpecReviewOutcome::Question {
+        question_id,
+        question,
+    })
+}
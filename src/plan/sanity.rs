@@ -1,12 +1,43 @@
 use crate::plan::translator::TranslatedPlan;
 use anyhow::{Result, bail};
 
-pub fn run_sanity_checks(plan: &TranslatedPlan) -> Result<()> {
+/// Structural checks on a translated plan; `max_tasks` is the `[run].max_tasks` cap, if set.
+pub fn run_sanity_checks(plan: &TranslatedPlan, max_tasks: Option<usize>) -> Result<()> {
     if plan.tasks.is_empty() {
         bail!("sanity failed: plan has zero tasks")
     }
+    if let Some(cap) = max_tasks
+        && plan.tasks.len() > cap
+    {
+        bail!(
+            "sanity failed: plan has {} tasks, more than the `[run].max_tasks` cap of {cap}",
+            plan.tasks.len()
+        )
+    }
     if !plan.tasks.iter().any(|t| t.dependencies.is_empty()) {
         bail!("sanity failed: no initially ready task")
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plan::translator::translate_markdown_to_spl;
+
+    #[test]
+    fn rejects_plans_above_the_task_cap() {
+        let markdown = (1..=4)
+            .map(|n| format!("- [ ] task-{n}: step {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let plan = translate_markdown_to_spl(&markdown, &["true".to_string()]).unwrap();
+
+        run_sanity_checks(&plan, Some(4)).unwrap();
+        let err = run_sanity_checks(&plan, Some(3)).unwrap_err();
+        assert!(
+            format!("{err}").contains("plan has 4 tasks, more than the `[run].max_tasks` cap of 3"),
+            "{err}"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::policy::spindle_bridge::RESERVED_ATOMS;
 use anyhow::{Context, Result, anyhow, bail};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -9,9 +10,18 @@ use std::path::Path;
 pub struct PlanTask {
     pub id: String,
     pub objective: String,
+    /// `acceptance_criteria` joined with `"; "`, for consumers that expect a single string.
     pub acceptance: String,
+    #[serde(default)]
+    pub acceptance_criteria: Vec<String>,
     pub dependencies: Vec<String>,
     pub checks: Vec<String>,
+    /// Higher runs first when several tasks are claimable at once.
+    #[serde(default)]
+    pub priority: i64,
+    /// Wall-clock budget from the first claim; overrides `[run].task_deadline_secs`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,15 +37,52 @@ struct RawTranslatedPlan {
 }
 
 #[derive(Debug, Clone, Deserialize)]
-struct RawTask {
+pub(crate) struct RawTask {
     id: String,
     objective: String,
     #[serde(default)]
-    acceptance: Option<String>,
+    acceptance: Option<RawAcceptance>,
     #[serde(default)]
     dependencies: Vec<String>,
     #[serde(default)]
     checks: Vec<String>,
+    #[serde(default)]
+    priority: i64,
+    #[serde(default)]
+    deadline_secs: Option<u64>,
+}
+
+/// `acceptance` may be a single criterion or a list of them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum RawAcceptance {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl RawAcceptance {
+    fn into_criteria(self) -> Vec<String> {
+        match self {
+            Self::One(one) => vec![one],
+            Self::Many(many) => many,
+        }
+    }
+}
+
+/// Trimmed non-empty criteria, defaulting to completing the objective, and their joined form.
+fn acceptance_criteria(
+    criteria: impl IntoIterator<Item = String>,
+    objective: &str,
+) -> (String, Vec<String>) {
+    let mut criteria = criteria
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<_>>();
+    if criteria.is_empty() {
+        criteria.push(format!("Complete objective: {objective}"));
+    }
+    (criteria.join("; "), criteria)
 }
 
 fn sanitize_ident(input: &str) -> String {
@@ -54,14 +101,34 @@ fn sanitize_ident(input: &str) -> String {
     }
 }
 
+/// Lines of a markdown plan after a leading `---` front-matter block, if any. An unterminated
+/// block is treated as ordinary text.
+fn strip_front_matter(markdown: &str) -> Vec<&str> {
+    let lines = markdown.lines().collect::<Vec<_>>();
+    let Some(start) = lines.iter().position(|line| !line.trim().is_empty()) else {
+        return lines;
+    };
+    if lines[start].trim() != "---" {
+        return lines;
+    }
+    match lines[start + 1..]
+        .iter()
+        .position(|line| line.trim() == "---")
+    {
+        Some(end) => lines[start + end + 2..].to_vec(),
+        None => lines,
+    }
+}
+
 pub fn translate_markdown_to_spl(
     markdown: &str,
     default_checks: &[String],
 ) -> Result<TranslatedPlan> {
     let mut tasks = Vec::new();
     let mut seen_ids: HashMap<String, String> = HashMap::new();
+    let lines = strip_front_matter(markdown);
 
-    for line in markdown.lines() {
+    for line in &lines {
         let trimmed = line.trim();
         let body = if let Some(rest) = trimmed.strip_prefix("- [ ]") {
             rest.trim()
@@ -72,10 +139,11 @@ pub fn translate_markdown_to_spl(
         } else {
             continue;
         };
-        if body.is_empty() {
+        // `- # ...` bullets are notes, not tasks.
+        if body.is_empty() || body.starts_with('#') {
             continue;
         }
-        // Format: task-id: objective | deps=a,b | checks=cmd1,cmd2
+        // Format: task-id: objective | deps=a,b | checks=cmd1,cmd2 | prio=N | acc=c1;c2
         let mut parts = body.split('|').map(str::trim);
         let first = parts.next().unwrap_or("");
         let (id, objective, source_id) = if let Some((id, obj)) = first.split_once(':') {
@@ -88,6 +156,7 @@ pub fn translate_markdown_to_spl(
             let generated = format!("task{}", tasks.len() + 1);
             (generated.clone(), first.to_string(), generated)
         };
+        ensure_not_reserved(&id)?;
         if let Some(prev) = seen_ids.insert(id.clone(), source_id.clone()) {
             bail!(
                 "translation failed: duplicate task ID after sanitization: '{}' (from '{}' and '{}')",
@@ -99,13 +168,18 @@ pub fn translate_markdown_to_spl(
 
         let mut deps = Vec::new();
         let mut checks = default_checks.to_vec();
+        let mut priority = 0;
+        let mut acceptance = Vec::new();
         for p in parts {
             if let Some(d) = p.strip_prefix("deps=") {
+                // Keep the first occurrence of each dependency, like the translator-output path.
+                let mut deps_seen = HashSet::<String>::new();
                 deps = d
                     .split(',')
                     .map(str::trim)
                     .filter(|x| !x.is_empty())
                     .map(sanitize_ident)
+                    .filter(|dep| deps_seen.insert(dep.clone()))
                     .collect();
             }
             if let Some(c) = p.strip_prefix("checks=") {
@@ -116,21 +190,37 @@ pub fn translate_markdown_to_spl(
                     .map(ToString::to_string)
                     .collect();
             }
+            if let Some(a) = p.strip_prefix("acc=") {
+                acceptance = a.split(';').map(ToString::to_string).collect();
+            }
+            if let Some(n) = p.strip_prefix("prio=") {
+                priority = n.trim().parse::<i64>().map_err(|_| {
+                    anyhow!(
+                        "translation failed: task '{}' has invalid prio '{}'; expected an integer",
+                        source_id,
+                        n.trim()
+                    )
+                })?;
+            }
         }
 
+        let (acceptance, acceptance_criteria) = acceptance_criteria(acceptance, &objective);
         tasks.push(PlanTask {
             id,
-            objective: objective.clone(),
-            acceptance: format!("Complete objective: {objective}"),
+            objective,
+            acceptance,
+            acceptance_criteria,
             dependencies: deps,
             checks,
+            priority,
+            deadline_secs: None,
         });
     }
 
     if tasks.is_empty() {
-        let objective = markdown
-            .lines()
-            .map(str::trim)
+        let objective = lines
+            .iter()
+            .map(|line| line.trim())
             .filter(|line| !line.is_empty())
             .map(|line| line.trim_start_matches('#').trim())
             .find(|line| !line.is_empty())
@@ -138,17 +228,28 @@ pub fn translate_markdown_to_spl(
         let objective = objective.ok_or_else(|| {
             anyhow!("translation failed: specification is empty; add concrete requirements")
         })?;
+        let (acceptance, acceptance_criteria) = acceptance_criteria(Vec::new(), &objective);
         tasks.push(PlanTask {
             id: "task1".to_string(),
-            objective: objective.clone(),
-            acceptance: format!("Complete objective: {objective}"),
+            objective,
+            acceptance,
+            acceptance_criteria,
             dependencies: Vec::new(),
             checks: default_checks.to_vec(),
+            priority: 0,
+            deadline_secs: None,
         });
     }
 
+    validate_dependency_edges(&tasks).context("translation failed")?;
+    let spl = render_plan_spl(&tasks);
+    Ok(TranslatedPlan { tasks, spl })
+}
+
+/// Render the canonical SPL facts and readiness rules for an already-normalized task list.
+pub(crate) fn render_plan_spl(tasks: &[PlanTask]) -> String {
     let mut spl = String::from("; generated plan.spl\n");
-    for t in &tasks {
+    for t in tasks {
         spl.push_str(&format!("(given (task {}))\n", t.id));
         spl.push_str(&format!("(given (has-objective {}))\n", t.id));
         spl.push_str(&format!("(given (has-acceptance {}))\n", t.id));
@@ -173,7 +274,7 @@ pub fn translate_markdown_to_spl(
         }
     }
 
-    Ok(TranslatedPlan { tasks, spl })
+    spl
 }
 
 pub fn parse_translated_plan_output(
@@ -189,23 +290,39 @@ pub fn parse_translated_plan_output(
     }
     validate_no_import_directives(&spl)?;
 
+    let tasks = normalize_raw_tasks(raw.tasks, default_checks, "translator output")?;
+
+    let translated = TranslatedPlan { tasks, spl };
+    validate_canonical_facts(&translated)?;
+    Ok(translated)
+}
+
+/// Validate ids and dependencies of raw tasks and fill in default acceptance and checks.
+/// `source` names the input in error messages.
+pub(crate) fn normalize_raw_tasks(
+    raw_tasks: Vec<RawTask>,
+    default_checks: &[String],
+    source: &str,
+) -> Result<Vec<PlanTask>> {
     let mut seen_ids = HashSet::<String>::new();
-    let mut tasks = Vec::<PlanTask>::with_capacity(raw.tasks.len());
-    for task in raw.tasks {
+    let mut tasks = Vec::<PlanTask>::with_capacity(raw_tasks.len());
+    for task in raw_tasks {
         let id = task.id.trim().to_string();
         if !is_valid_task_id(&id) {
             bail!("invalid task id '{id}'; allowed chars: [A-Za-z0-9_-]");
         }
+        ensure_not_reserved(&id)?;
         if !seen_ids.insert(id.clone()) {
-            bail!("duplicate task id '{id}' in translator output");
+            bail!("duplicate task id '{id}' in {source}");
         }
 
         let objective = task.objective.trim().to_string();
-        let acceptance = task
-            .acceptance
-            .unwrap_or_else(|| format!("Complete objective: {objective}"))
-            .trim()
-            .to_string();
+        let (acceptance, acceptance_criteria) = acceptance_criteria(
+            task.acceptance
+                .map(RawAcceptance::into_criteria)
+                .unwrap_or_default(),
+            &objective,
+        );
 
         let mut deps_seen = HashSet::<String>::new();
         let mut dependencies = Vec::<String>::new();
@@ -237,23 +354,39 @@ pub fn parse_translated_plan_output(
             checks
         };
 
+        if task.deadline_secs == Some(0) {
+            bail!("task '{id}' has deadline_secs = 0; expected a positive number of seconds");
+        }
+
         tasks.push(PlanTask {
             id,
             objective,
             acceptance,
+            acceptance_criteria,
             dependencies,
             checks,
+            priority: task.priority,
+            deadline_secs: task.deadline_secs,
         });
     }
 
     if tasks.is_empty() {
-        bail!("translator output has empty 'tasks'");
+        bail!("{source} has empty 'tasks'");
     }
 
-    let ids = tasks.iter().map(|t| t.id.clone()).collect::<HashSet<_>>();
-    for task in &tasks {
+    validate_dependency_edges(&tasks)?;
+    Ok(tasks)
+}
+
+/// Every dependency must name another task in the plan.
+fn validate_dependency_edges(tasks: &[PlanTask]) -> Result<()> {
+    let ids = tasks.iter().map(|t| t.id.as_str()).collect::<HashSet<_>>();
+    for task in tasks {
         for dep in &task.dependencies {
-            if !ids.contains(dep) {
+            if dep == &task.id {
+                bail!("task '{}' cannot depend on itself", task.id);
+            }
+            if !ids.contains(dep.as_str()) {
                 bail!(
                     "task '{}' depends on unknown task '{}'; all dependencies must reference known task ids",
                     task.id,
@@ -262,10 +395,7 @@ pub fn parse_translated_plan_output(
             }
         }
     }
-
-    let translated = TranslatedPlan { tasks, spl };
-    validate_canonical_facts(&translated)?;
-    Ok(translated)
+    Ok(())
 }
 
 pub fn save_translated_plan(path: &Path, translated: &TranslatedPlan) -> Result<()> {
@@ -286,6 +416,13 @@ pub fn load_translated_plan(path: &Path) -> Result<TranslatedPlan> {
     Ok(parsed)
 }
 
+fn ensure_not_reserved(id: &str) -> Result<()> {
+    if RESERVED_ATOMS.contains(&id) {
+        bail!("task id '{id}' is reserved by the scheduling policy; rename the task");
+    }
+    Ok(())
+}
+
 fn is_valid_task_id(id: &str) -> bool {
     !id.is_empty()
         && id
@@ -339,7 +476,7 @@ fn validate_no_import_directives(spl: &str) -> Result<()> {
     Ok(())
 }
 
-fn validate_canonical_facts(translated: &TranslatedPlan) -> Result<()> {
+pub(crate) fn validate_canonical_facts(translated: &TranslatedPlan) -> Result<()> {
     let theory = parse_spl(&translated.spl).context("SPL parse failed during canonical checks")?;
     let mut task_facts = HashSet::<String>::new();
     let mut dep_facts = HashSet::<(String, String)>::new();
@@ -429,6 +566,111 @@ mod tests {
         assert_eq!(translated.tasks.len(), 2);
     }
 
+    #[test]
+    fn rejects_reserved_task_id() {
+        let out = json!({
+            "spl": "(given (task closed))\n(given (ready closed))\n",
+            "tasks": [
+                {
+                    "id": "closed",
+                    "objective": "only",
+                    "acceptance": "done",
+                    "dependencies": [],
+                    "checks": ["true"]
+                }
+            ]
+        });
+        let err = parse_translated_plan_output(&out, &["true".to_string()]).unwrap_err();
+        assert_eq!(
+            format!("{err}"),
+            "task id 'closed' is reserved by the scheduling policy; rename the task"
+        );
+
+        let err = super::translate_markdown_to_spl("- [ ] closed: do it", &[]).unwrap_err();
+        assert!(format!("{err}").contains("task id 'closed' is reserved"));
+    }
+
+    #[test]
+    fn markdown_rejects_unknown_and_self_dependencies() {
+        let err = super::translate_markdown_to_spl("- [ ] a: x | deps=nope", &[]).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("task 'a' depends on unknown task 'nope'"),
+            "{err:#}"
+        );
+
+        let err = super::translate_markdown_to_spl("- [ ] a: x | deps=a", &[]).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("task 'a' cannot depend on itself"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn markdown_dedupes_repeated_dependencies_in_first_seen_order() {
+        let translated = super::translate_markdown_to_spl(
+            "- [ ] a: x\n- [ ] b: y\n- [ ] c: z | deps=a,a,b",
+            &[],
+        )
+        .unwrap();
+        let c = translated.tasks.iter().find(|t| t.id == "c").unwrap();
+        assert_eq!(c.dependencies, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(translated.spl.matches("(depends-on c a)").count(), 1);
+    }
+
+    #[test]
+    fn markdown_skips_front_matter_and_note_bullets() {
+        let translated = super::translate_markdown_to_spl(
+            "---\ntitle: Parser work\n- owner: infra\n---\n# Plan\n\nSome prose.\n- # Note: deploy later\n* # reviewed by ops\n- [ ] parse: build the parser\n- [ ] cli: wire the CLI | deps=parse",
+            &[],
+        )
+        .unwrap();
+        let ids = translated
+            .tasks
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["parse", "cli"]);
+
+        let translated =
+            super::translate_markdown_to_spl("---\ntitle: x\n---\nShip the importer", &[]).unwrap();
+        assert_eq!(translated.tasks[0].objective, "Ship the importer");
+
+        // Without a closing `---` the block is ordinary text.
+        let translated = super::translate_markdown_to_spl("---\n- [ ] a: kept", &[]).unwrap();
+        assert_eq!(translated.tasks[0].id, "a");
+    }
+
+    #[test]
+    fn acceptance_accepts_a_string_or_a_list() {
+        let out = json!({
+            "spl": "(given (task a))\n(given (task b))\n(given (ready a))\n(given (ready b))\n",
+            "tasks": [
+                {"id": "a", "objective": "x", "acceptance": ["parses input", " ", "reports errors"]},
+                {"id": "b", "objective": "y", "acceptance": "done"}
+            ]
+        });
+        let translated = parse_translated_plan_output(&out, &[]).unwrap();
+        assert_eq!(
+            translated.tasks[0].acceptance_criteria,
+            vec!["parses input".to_string(), "reports errors".to_string()]
+        );
+        assert_eq!(
+            translated.tasks[0].acceptance,
+            "parses input; reports errors"
+        );
+        assert_eq!(
+            translated.tasks[1].acceptance_criteria,
+            vec!["done".to_string()]
+        );
+
+        let translated =
+            super::translate_markdown_to_spl("- [ ] a: x | acc=fast; correct", &[]).unwrap();
+        assert_eq!(
+            translated.tasks[0].acceptance_criteria,
+            vec!["fast".to_string(), "correct".to_string()]
+        );
+    }
+
     #[test]
     fn rejects_mismatched_canonical_task_facts() {
         let out = json!({
@@ -0,0 +1,96 @@
+use crate::plan::translator::{
+    RawTask, TranslatedPlan, normalize_raw_tasks, render_plan_spl, validate_canonical_facts,
+};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawYamlPlan {
+    tasks: Vec<RawTask>,
+}
+
+pub fn is_yaml_plan(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("yaml" | "yml")
+    )
+}
+
+/// Parse a structured `tasks:` plan into the same `TranslatedPlan` the markdown and agent
+/// translators produce. Tasks without `checks` fall back to the run's configured checks.
+pub fn parse_yaml_plan(raw: &str) -> Result<TranslatedPlan> {
+    let plan: RawYamlPlan = serde_yaml::from_str(raw)
+        .context("YAML plan must be a mapping with a 'tasks' list of {id, objective, ...}")?;
+    let tasks = normalize_raw_tasks(plan.tasks, &[], "YAML plan")?;
+    let spl = render_plan_spl(&tasks);
+    let translated = TranslatedPlan { tasks, spl };
+    validate_canonical_facts(&translated)?;
+    Ok(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_yaml_plan;
+
+    #[test]
+    fn parses_valid_yaml_plan() {
+        let plan = parse_yaml_plan(
+            r#"
+tasks:
+  - id: task-a
+    objective: build the parser
+    acceptance: parser handles nested lists
+    checks: ["cargo test parser"]
+    deadline_secs: 600
+  - id: task-b
+    objective: wire the CLI
+    dependencies: [task-a]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(plan.tasks.len(), 2);
+        assert_eq!(plan.tasks[0].acceptance, "parser handles nested lists");
+        assert_eq!(plan.tasks[0].checks, vec!["cargo test parser".to_string()]);
+        assert_eq!(plan.tasks[0].deadline_secs, Some(600));
+        assert_eq!(plan.tasks[1].deadline_secs, None);
+        assert_eq!(plan.tasks[1].dependencies, vec!["task-a".to_string()]);
+        assert_eq!(plan.tasks[1].acceptance, "Complete objective: wire the CLI");
+        assert!(plan.spl.contains("(given (depends-on task-b task-a))"));
+        assert!(
+            plan.spl
+                .contains("(always r-ready-task-b (closed task-a) (ready task-b))")
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_dependency() {
+        let err = parse_yaml_plan(
+            r#"
+tasks:
+  - id: task-a
+    objective: build the parser
+    dependencies: [task-z]
+"#,
+        )
+        .unwrap_err();
+
+        assert!(format!("{err}").contains("depends on unknown task 'task-z'"));
+    }
+
+    #[test]
+    fn rejects_zero_task_deadline() {
+        let err = parse_yaml_plan(
+            r#"
+tasks:
+  - id: task-a
+    objective: build the parser
+    deadline_secs: 0
+"#,
+        )
+        .unwrap_err();
+
+        assert!(format!("{err}").contains("task 'task-a' has deadline_secs = 0"));
+    }
+}
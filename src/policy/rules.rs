@@ -5,7 +5,7 @@ pub fn claimable(run: &RunProjection, task: &TaskProjection) -> bool {
     {
         return false;
     }
-    if task.closed || task.terminal_failed || task.claimed {
+    if task.closed || task.terminal_failed || task.skipped || task.claimed {
         return false;
     }
     task.dependencies
@@ -1,5 +1,5 @@
 use crate::events::projector::RunProjection;
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use spindle_core::literal::Literal;
 use spindle_core::mode::Mode;
 use spindle_core::query::{QueryStatus, query};
@@ -15,6 +15,37 @@ pub struct PolicySnapshot {
     pub merge_ready: HashSet<String>,
 }
 
+/// Atoms the policy and projected lifecycle facts rely on; a task id equal to one of these
+/// would be indistinguishable from the predicate in the composed theory.
+pub const RESERVED_ATOMS: &[&str] = &[
+    "task",
+    "ready",
+    "closed",
+    "claimed",
+    "unclaimed",
+    "unclosed",
+    "unfailed",
+    "terminal-failed",
+    "claimable",
+    "closable",
+    "merge-ready",
+    "spec-approved",
+    "checks-approved",
+    "no-open-questions",
+    "run-active",
+    "run-paused",
+    "latest-attempt",
+    "review-approved",
+    "checks-passed",
+    "findings-clear",
+    "findings-open",
+    "has-objective",
+    "has-acceptance",
+    "depends-on",
+    "claim-blocked",
+    "close-blocked",
+];
+
 const STATIC_POLICY_RULES: &str = r#"
 (always policy-claimable
   (and
@@ -47,13 +78,44 @@ const STATIC_POLICY_RULES: &str = r#"
   (merge-ready ?t))
 "#;
 
-pub fn derive_policy_state(run: &RunProjection, plan_spl: &str) -> Result<PolicySnapshot> {
+/// Check a `[policy].extra_rules_file` snippet: it must parse on its own and may not pull in
+/// other theories with `(import ...)`.
+pub fn validate_extra_rules(src: &str) -> Result<()> {
+    let uncommented = src
+        .lines()
+        .map(|line| line.split(';').next().unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if uncommented
+        .split('(')
+        .skip(1)
+        .any(|form| form.trim_start().starts_with("import"))
+    {
+        bail!("extra policy rules may not contain `(import ...)`")
+    }
+    parse_spl(src).context("extra policy rules parse failed")?;
+    Ok(())
+}
+
+/// Compose the static rules, the plan, `extra_rules` from `[policy].extra_rules_file` and the
+/// projected lifecycle facts, then query each task. Extra rules can derive `claimable` or
+/// `closable` directly, or hold a task back with `(claim-blocked ?t)` / `(close-blocked ?t)`.
+pub fn derive_policy_state(
+    run: &RunProjection,
+    plan_spl: &str,
+    extra_rules: Option<&str>,
+) -> Result<PolicySnapshot> {
     let mut composed = String::new();
-    composed.push_str("; static policy rules\n");
+    let mut sections = Vec::new();
+    begin_section(&mut composed, &mut sections, "static policy rules");
     composed.push_str(STATIC_POLICY_RULES);
-    composed.push_str("\n; translated plan facts/rules\n");
+    begin_section(&mut composed, &mut sections, "translated plan facts/rules");
     composed.push_str(plan_spl);
-    composed.push_str("\n; lifecycle projected facts\n");
+    if let Some(extra) = extra_rules {
+        begin_section(&mut composed, &mut sections, "extra policy rules");
+        composed.push_str(extra);
+    }
+    begin_section(&mut composed, &mut sections, "lifecycle projected facts");
 
     if run.spec_approved {
         composed.push_str("(given spec-approved)\n");
@@ -124,7 +186,8 @@ pub fn derive_policy_state(run: &RunProjection, plan_spl: &str) -> Result<Policy
         }
     }
 
-    let theory = parse_spl(&composed).context("policy SPL parse failed")?;
+    let theory =
+        parse_spl(&composed).with_context(|| describe_parse_failure(&composed, &sections))?;
 
     let mut snapshot = PolicySnapshot {
         run_paused: run.paused || !run.open_questions.is_empty(),
@@ -132,13 +195,17 @@ pub fn derive_policy_state(run: &RunProjection, plan_spl: &str) -> Result<Policy
     };
 
     for task_id in run.tasks.keys() {
-        if is_provable(&theory, "claimable", &[task_id.as_str()])? {
+        let args = [task_id.as_str()];
+        if is_provable(&theory, "claimable", &args)?
+            && !is_provable(&theory, "claim-blocked", &args)?
+        {
             snapshot.claimable.insert(task_id.clone());
         }
-        if is_provable(&theory, "closable", &[task_id.as_str()])? {
+        let close_blocked = is_provable(&theory, "close-blocked", &args)?;
+        if is_provable(&theory, "closable", &args)? && !close_blocked {
             snapshot.closable.insert(task_id.clone());
         }
-        if is_provable(&theory, "merge-ready", &[task_id.as_str()])? {
+        if is_provable(&theory, "merge-ready", &args)? && !close_blocked {
             snapshot.merge_ready.insert(task_id.clone());
         }
     }
@@ -146,6 +213,64 @@ pub fn derive_policy_state(run: &RunProjection, plan_spl: &str) -> Result<Policy
     Ok(snapshot)
 }
 
+/// Start a `; <name>` section of the composed theory, recording the line its body starts on.
+fn begin_section(
+    composed: &mut String,
+    sections: &mut Vec<(&'static str, usize)>,
+    name: &'static str,
+) {
+    if !composed.is_empty() {
+        composed.push('\n');
+    }
+    composed.push_str(&format!("; {name}\n"));
+    sections.push((name, composed.matches('\n').count() + 1));
+}
+
+/// Lines of the failing section shown in a parse error before it is cut short.
+const PARSE_ERROR_EXCERPT_LINES: usize = 40;
+
+/// Context for a failed parse of the composed theory: which section fails to parse on its own,
+/// with its line range and a numbered excerpt.
+fn describe_parse_failure(composed: &str, sections: &[(&'static str, usize)]) -> String {
+    let lines = composed.lines().collect::<Vec<_>>();
+    let ranges = sections.iter().enumerate().map(|(i, (name, start))| {
+        let end = sections
+            .get(i + 1)
+            .map_or(lines.len(), |(_, next)| next - 2)
+            .max(*start);
+        (*name, *start, end)
+    });
+    let layout = ranges
+        .clone()
+        .map(|(name, start, end)| format!("{name} {start}-{end}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let culprit = ranges.clone().find(|(_, start, end)| {
+        let body = lines.get(start - 1..*end).unwrap_or_default().join("\n");
+        parse_spl(&body).is_err()
+    });
+    let Some((name, start, end)) = culprit else {
+        return format!(
+            "policy SPL parse failed; composed policy has {} lines ({layout})",
+            lines.len()
+        );
+    };
+    let mut excerpt = (start..=end)
+        .take(PARSE_ERROR_EXCERPT_LINES)
+        .filter_map(|n| lines.get(n - 1).map(|line| format!("{n:>5} | {line}")))
+        .collect::<Vec<_>>();
+    if end + 1 - start > PARSE_ERROR_EXCERPT_LINES {
+        excerpt.push(format!(
+            "      ... {} more lines",
+            end + 1 - start - PARSE_ERROR_EXCERPT_LINES
+        ));
+    }
+    format!(
+        "policy SPL parse failed in the {name} section (lines {start}-{end} of the composed policy):\n{}",
+        excerpt.join("\n")
+    )
+}
+
 fn is_provable(theory: &spindle_core::theory::Theory, name: &str, args: &[&str]) -> Result<bool> {
     let lit = Literal::new(
         name,
@@ -0,0 +1,161 @@
+use super::default_state_db;
+use super::r#loop::task_outcome;
+use crate::events::projector::RunProjection;
+use crate::events::store::EventStore;
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// A task's end state in one run.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TaskOutcome {
+    pub outcome: String,
+    pub attempts: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct TaskOutcomeChange {
+    pub task_id: String,
+    pub a: TaskOutcome,
+    pub b: TaskOutcome,
+}
+
+/// How run `b` differed from run `a`, task by task. All lists are sorted by task id.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RunDiff {
+    pub run_a: String,
+    pub run_b: String,
+    pub status_a: String,
+    pub status_b: String,
+    /// Tasks in both runs whose outcome or attempt count differs.
+    pub changed: Vec<TaskOutcomeChange>,
+    pub unchanged: Vec<String>,
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+}
+
+pub fn diff_run_outcomes(run_a: &str, run_b: &str, state_db: Option<PathBuf>) -> Result<RunDiff> {
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    let load = |run_id: &str| -> Result<(String, RunProjection)> {
+        let run = store
+            .get_run(run_id)?
+            .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+        Ok((
+            run.status,
+            RunProjection::replay(&store.list_events(run_id)?),
+        ))
+    };
+    let (status_a, a) = load(run_a)?;
+    let (status_b, b) = load(run_b)?;
+    Ok(diff_projections(run_a, status_a, &a, run_b, status_b, &b))
+}
+
+fn diff_projections(
+    run_a: &str,
+    status_a: String,
+    a: &RunProjection,
+    run_b: &str,
+    status_b: String,
+    b: &RunProjection,
+) -> RunDiff {
+    let outcome = |state: &RunProjection, task_id: &str| {
+        state.tasks.get(task_id).map(|t| TaskOutcome {
+            outcome: task_outcome(t).to_string(),
+            attempts: t.attempts,
+        })
+    };
+    let mut task_ids = a.tasks.keys().chain(b.tasks.keys()).collect::<Vec<_>>();
+    task_ids.sort();
+    task_ids.dedup();
+
+    let mut diff = RunDiff {
+        run_a: run_a.to_string(),
+        run_b: run_b.to_string(),
+        status_a,
+        status_b,
+        changed: Vec::new(),
+        unchanged: Vec::new(),
+        only_in_a: Vec::new(),
+        only_in_b: Vec::new(),
+    };
+    for task_id in task_ids {
+        match (outcome(a, task_id), outcome(b, task_id)) {
+            (Some(in_a), Some(in_b)) if in_a == in_b => diff.unchanged.push(task_id.clone()),
+            (Some(in_a), Some(in_b)) => diff.changed.push(TaskOutcomeChange {
+                task_id: task_id.clone(),
+                a: in_a,
+                b: in_b,
+            }),
+            (Some(_), None) => diff.only_in_a.push(task_id.clone()),
+            (None, _) => diff.only_in_b.push(task_id.clone()),
+        }
+    }
+    diff
+}
+
+/// `thence diff`: print how run `b`'s task outcomes differ from run `a`'s.
+pub fn diff_runs(run_a: &str, run_b: &str, state_db: Option<PathBuf>) -> Result<()> {
+    let diff = diff_run_outcomes(run_a, run_b, state_db)?;
+    println!("run_a: {} ({})", diff.run_a, diff.status_a);
+    println!("run_b: {} ({})", diff.run_b, diff.status_b);
+    if !diff.changed.is_empty() {
+        println!("changed:");
+        for change in &diff.changed {
+            println!(
+                "  - task={} outcome={}->{} attempts={}->{}",
+                change.task_id,
+                change.a.outcome,
+                change.b.outcome,
+                change.a.attempts,
+                change.b.attempts
+            );
+        }
+    }
+    for (label, tasks) in [
+        ("only_in_a", &diff.only_in_a),
+        ("only_in_b", &diff.only_in_b),
+    ] {
+        if !tasks.is_empty() {
+            println!("{label}:");
+            for task_id in tasks {
+                println!("  - {task_id}");
+            }
+        }
+    }
+    println!("unchanged: {}", diff.unchanged.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::projector::TaskProjection;
+
+    fn projection(tasks: &[(&str, bool, i64)]) -> RunProjection {
+        let mut state = RunProjection::default();
+        for (id, closed, attempts) in tasks {
+            state.tasks.insert(
+                id.to_string(),
+                TaskProjection {
+                    id: id.to_string(),
+                    closed: *closed,
+                    terminal_failed: !closed,
+                    attempts: *attempts,
+                    ..TaskProjection::default()
+                },
+            );
+        }
+        state
+    }
+
+    #[test]
+    fn disjoint_task_sets_are_reported_per_side() {
+        let a = projection(&[("shared", true, 1), ("old", true, 1)]);
+        let b = projection(&[("shared", true, 1), ("new", false, 2)]);
+        let diff = diff_projections("a", "completed".into(), &a, "b", "failed".into(), &b);
+        assert!(diff.changed.is_empty());
+        assert_eq!(diff.unchanged, vec!["shared".to_string()]);
+        assert_eq!(diff.only_in_a, vec!["old".to_string()]);
+        assert_eq!(diff.only_in_b, vec!["new".to_string()]);
+    }
+}
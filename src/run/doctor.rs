@@ -0,0 +1,121 @@
+use crate::config::load_repo_config;
+use crate::events::store::EventStore;
+use crate::workers::provider::provider_for;
+use anyhow::{Context, Result, bail};
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+
+/// Outcome of one `thence doctor` preflight check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl DoctorCheck {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            ok: true,
+            detail: detail.into(),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            ok: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Check what a run in `repo_root` would need before it starts: the repo config loads, the
+/// agent command is runnable, and the state DB is writable. Nothing is run or recorded.
+pub fn run_doctor_checks(
+    repo_root: &Path,
+    agent: Option<&str>,
+    state_db: Option<PathBuf>,
+) -> Vec<DoctorCheck> {
+    let mut checks = Vec::new();
+
+    let repo_cfg = match load_repo_config(repo_root) {
+        Ok(Some(cfg)) => {
+            checks.push(DoctorCheck::pass("config", "loaded .thence/config.toml"));
+            Some(cfg)
+        }
+        Ok(None) => {
+            checks.push(DoctorCheck::pass(
+                "config",
+                "no .thence/config.toml; using defaults",
+            ));
+            None
+        }
+        Err(err) => {
+            checks.push(DoctorCheck::fail("config", format!("{err:#}")));
+            None
+        }
+    };
+
+    let agent_cfg = repo_cfg.as_ref().and_then(|cfg| cfg.agent.as_ref());
+    let provider = agent
+        .map(ToString::to_string)
+        .or_else(|| agent_cfg.and_then(|a| a.provider.clone()))
+        .unwrap_or_else(|| "codex".to_string());
+    let command = agent_cfg.and_then(|a| a.command.as_deref());
+    checks.push(match provider_for(&provider, false, command, 0) {
+        Ok(_) => DoctorCheck::pass(
+            "agent",
+            format!("{provider}: {}", command.unwrap_or("default command")),
+        ),
+        Err(err) => DoctorCheck::fail("agent", format!("{provider}: {err:#}")),
+    });
+
+    let db = state_db.unwrap_or_else(super::default_state_db);
+    checks.push(match check_state_db(&db) {
+        Ok(detail) => DoctorCheck::pass("state_db", format!("{} {detail}", db.display())),
+        Err(err) => DoctorCheck::fail("state_db", format!("{}: {err:#}", db.display())),
+    });
+
+    checks
+}
+
+/// An existing DB must open read-only and be writable; a missing one must be creatable in the
+/// nearest existing ancestor directory. Neither case creates or migrates the DB.
+fn check_state_db(db: &Path) -> Result<&'static str> {
+    if db.exists() {
+        EventStore::open_read_only(db)?;
+        OpenOptions::new()
+            .write(true)
+            .open(db)
+            .context("open for writing")?;
+        return Ok("is writable");
+    }
+    let dir = db
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| Path::new("."));
+    let probe = dir.join(format!(".thence-doctor-{}", std::process::id()));
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .with_context(|| format!("cannot create files in {}", dir.display()))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok("does not exist yet; it can be created")
+}
+
+pub fn doctor(repo_root: &Path, agent: Option<&str>, state_db: Option<PathBuf>) -> Result<()> {
+    let checks = run_doctor_checks(repo_root, agent, state_db);
+    for check in &checks {
+        let status = if check.ok { "ok" } else { "FAIL" };
+        println!("{status:<4}  {:<8}  {}", check.name, check.detail);
+    }
+    let failed = checks.iter().filter(|check| !check.ok).count();
+    if failed > 0 {
+        bail!("{failed} preflight check(s) failed");
+    }
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static STOP_REQUESTED: AtomicBool = AtomicBool::new(false);
+static INSTALL: Once = Once::new();
+
+/// Route SIGINT to a stop flag the supervisor checks between phases, so Ctrl-C releases leases
+/// and pauses the run instead of abandoning in-flight attempts. Safe to call more than once.
+pub fn install_interrupt_handler() {
+    INSTALL.call_once(|| {
+        if let Err(err) = ctrlc::set_handler(|| STOP_REQUESTED.store(true, Ordering::SeqCst)) {
+            crate::logging::diag::warn(format_args!("could not install Ctrl-C handler: {err}"));
+        }
+    });
+}
+
+pub(crate) fn stop_requested() -> bool {
+    STOP_REQUESTED.load(Ordering::SeqCst)
+}
@@ -1,9 +1,10 @@
 use crate::run::run_artifact_dir;
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use std::fs;
+use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::mpsc;
@@ -11,8 +12,10 @@ use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
 pub(crate) const LEASE_SCHEMA_VERSION: u32 = 1;
+/// Default for `[run].lease_tick_secs`.
 pub(crate) const LEASE_TICK_SECS: u64 = 15;
-pub(crate) const LEASE_STALE_AFTER_SECS: i64 = 90;
+/// Default for `[run].lease_stale_secs`.
+pub(crate) const LEASE_STALE_AFTER_SECS: u64 = 90;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -29,6 +32,9 @@ struct AttemptLeaseRecord {
     attempt: i64,
     role: String,
     owner_pid: u32,
+    /// Host that owns `owner_pid`; leases written before this field existed are treated as local.
+    #[serde(default)]
+    hostname: Option<String>,
     started_at: String,
     last_seen_at: String,
     state: LeaseState,
@@ -40,6 +46,7 @@ struct ParsedLease {
     record: AttemptLeaseRecord,
     last_seen_at: DateTime<Utc>,
     age_secs: i64,
+    owner_local: bool,
     owner_alive: bool,
 }
 
@@ -83,14 +90,104 @@ impl LeaseTicker {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RunLockRecord {
+    run_id: String,
+    owner_pid: u32,
+    hostname: String,
+    acquired_at: String,
+}
+
+/// Exclusive claim on driving a run, held for the life of one `continue_run`; the lock file
+/// is removed when this is dropped.
+#[derive(Debug)]
+pub(crate) struct RunLock {
+    path: PathBuf,
+}
+
+impl Drop for RunLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+pub(crate) fn run_lock_path(runs_root: &Path, run_id: &str) -> PathBuf {
+    run_artifact_dir(runs_root, run_id).join("run.lock")
+}
+
+/// Take the run lock, refusing while another live process holds it. A lock left by a dead
+/// local process is replaced; a lock from another host cannot be checked and is respected.
+pub(crate) fn acquire_run_lock(runs_root: &Path, run_id: &str) -> Result<RunLock> {
+    let path = run_lock_path(runs_root, run_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("create run lock dir {}", parent.display()))?;
+    }
+    let host = local_hostname();
+    let record = RunLockRecord {
+        run_id: run_id.to_string(),
+        owner_pid: std::process::id(),
+        hostname: host.clone(),
+        acquired_at: Utc::now().to_rfc3339(),
+    };
+    // The record is written in full before it is linked into place, so a lock file is never
+    // seen half-written by a competing process.
+    let tmp = path.with_extension(format!("tmp-{}", std::process::id()));
+    fs::write(&tmp, serde_json::to_string_pretty(&record)?)
+        .with_context(|| format!("write temp run lock {}", tmp.display()))?;
+    let acquired = loop {
+        match fs::hard_link(&tmp, &path) {
+            Ok(()) => break Ok(RunLock { path }),
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+                // Locks are linked in whole, so an unreadable one was damaged after the fact.
+                let holder = fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|raw| serde_json::from_str::<RunLockRecord>(&raw).ok());
+                if let Some(holder) = holder {
+                    if holder.hostname != host {
+                        break Err(anyhow!(
+                            "run already in progress: {run_id} is locked by pid {} on host '{}' since {}; remove {} if that process is gone",
+                            holder.owner_pid,
+                            holder.hostname,
+                            holder.acquired_at,
+                            path.display()
+                        ));
+                    }
+                    if process_alive(holder.owner_pid) {
+                        break Err(anyhow!(
+                            "run already in progress: {run_id} is driven by pid {} since {}",
+                            holder.owner_pid,
+                            holder.acquired_at
+                        ));
+                    }
+                }
+                // Another process may have cleared the same stale lock first.
+                match fs::remove_file(&path) {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == ErrorKind::NotFound => {}
+                    Err(err) => {
+                        break Err(err)
+                            .with_context(|| format!("remove stale run lock {}", path.display()));
+                    }
+                }
+            }
+            Err(err) => {
+                break Err(err).with_context(|| format!("create run lock {}", path.display()));
+            }
+        }
+    };
+    let _ = fs::remove_file(&tmp);
+    acquired
+}
+
 pub(crate) fn lease_path(
-    repo_root: &Path,
+    runs_root: &Path,
     run_id: &str,
     task_id: &str,
     attempt: i64,
     role: &str,
 ) -> PathBuf {
-    run_artifact_dir(repo_root, run_id)
+    run_artifact_dir(runs_root, run_id)
         .join("leases")
         .join(task_id)
         .join(format!("attempt{attempt}"))
@@ -98,13 +195,13 @@ pub(crate) fn lease_path(
 }
 
 pub(crate) fn init_active_lease(
-    repo_root: &Path,
+    runs_root: &Path,
     run_id: &str,
     task_id: &str,
     attempt: i64,
     role: &str,
 ) -> Result<PathBuf> {
-    let path = lease_path(repo_root, run_id, task_id, attempt, role);
+    let path = lease_path(runs_root, run_id, task_id, attempt, role);
     let now = Utc::now().to_rfc3339();
     let record = AttemptLeaseRecord {
         version: LEASE_SCHEMA_VERSION,
@@ -113,6 +210,7 @@ pub(crate) fn init_active_lease(
         attempt,
         role: role.to_string(),
         owner_pid: std::process::id(),
+        hostname: Some(local_hostname()),
         started_at: now.clone(),
         last_seen_at: now,
         state: LeaseState::Active,
@@ -142,49 +240,83 @@ pub(crate) fn release_lease(path: &Path) -> Result<()> {
 }
 
 pub(crate) fn evaluate_orphan_attempt(
-    repo_root: &Path,
+    runs_root: &Path,
     run_id: &str,
     task_id: &str,
     attempt: i64,
+    stale_after_secs: u64,
 ) -> Result<OrphanLeaseDecision> {
-    evaluate_orphan_attempt_at(repo_root, run_id, task_id, attempt, Utc::now())
+    evaluate_orphan_attempt_at(
+        runs_root,
+        run_id,
+        task_id,
+        attempt,
+        stale_after_secs,
+        Utc::now(),
+    )
 }
 
 pub(crate) fn evaluate_orphan_attempt_at(
-    repo_root: &Path,
+    runs_root: &Path,
     run_id: &str,
     task_id: &str,
     attempt: i64,
+    stale_after_secs: u64,
     now: DateTime<Utc>,
 ) -> Result<OrphanLeaseDecision> {
+    let stale_after = i64::try_from(stale_after_secs).unwrap_or(i64::MAX);
+    let host = local_hostname();
     let mut parsed = Vec::<ParsedLease>::new();
+    // Leases are written atomically, so an unreadable one predates that or was damaged in a
+    // crash; either way no live owner is heartbeating through it.
+    let mut unreadable = Vec::<String>::new();
     for role in ["implementer", "reviewer"] {
-        let path = lease_path(repo_root, run_id, task_id, attempt, role);
+        let path = lease_path(runs_root, run_id, task_id, attempt, role);
         if !path.exists() {
             continue;
         }
-        let record = read_lease(&path)
-            .with_context(|| format!("read lease for {task_id} attempt {attempt} role {role}"))?;
-        let last_seen_at = DateTime::parse_from_rfc3339(&record.last_seen_at)
-            .with_context(|| format!("parse lease last_seen_at from {}", path.display()))?
-            .with_timezone(&Utc);
+        let read = read_lease(&path).and_then(|record| {
+            let last_seen_at = DateTime::parse_from_rfc3339(&record.last_seen_at)
+                .with_context(|| format!("parse lease last_seen_at from {}", path.display()))?;
+            Ok((record, last_seen_at.with_timezone(&Utc)))
+        });
+        let (record, last_seen_at) = match read {
+            Ok(read) => read,
+            Err(err) => {
+                unreadable.push(format!("{err:#}"));
+                continue;
+            }
+        };
         let age_secs = now.signed_duration_since(last_seen_at).num_seconds().max(0);
-        let owner_alive = process_alive(record.owner_pid);
+        // A PID from another host says nothing about local processes; only staleness applies.
+        let owner_local = record.hostname.as_deref().is_none_or(|h| h == host);
+        let owner_alive = owner_local && process_alive(record.owner_pid);
         parsed.push(ParsedLease {
             path,
             record,
             last_seen_at,
             age_secs,
+            owner_local,
             owner_alive,
         });
     }
 
+    if parsed.is_empty() && !unreadable.is_empty() {
+        return Ok(OrphanLeaseDecision::Interrupt {
+            reason: "orphaned in-flight attempt detected on resume (lease unreadable)".to_string(),
+            details: json!({
+                "state": "unreadable",
+                "errors": unreadable,
+                "stale_after_secs": stale_after
+            }),
+        });
+    }
     if parsed.is_empty() {
         return Ok(OrphanLeaseDecision::Interrupt {
             reason: "orphaned in-flight attempt detected on resume (no lease found)".to_string(),
             details: json!({
                 "state": "missing",
-                "stale_after_secs": LEASE_STALE_AFTER_SECS
+                "stale_after_secs": stale_after
             }),
         });
     }
@@ -198,11 +330,13 @@ pub(crate) fn evaluate_orphan_attempt_at(
         "path": newest.path,
         "role": newest.record.role,
         "owner_pid": newest.record.owner_pid,
+        "hostname": newest.record.hostname,
+        "owner_local": newest.owner_local,
         "owner_alive": newest.owner_alive,
         "started_at": newest.record.started_at,
         "last_seen_at": newest.record.last_seen_at,
         "age_secs": newest.age_secs,
-        "stale_after_secs": LEASE_STALE_AFTER_SECS,
+        "stale_after_secs": stale_after,
         "state": match newest.record.state {
             LeaseState::Active => "active",
             LeaseState::Released => "released",
@@ -217,8 +351,18 @@ pub(crate) fn evaluate_orphan_attempt_at(
         });
     }
 
-    if newest.age_secs <= LEASE_STALE_AFTER_SECS {
-        let reason = if newest.owner_alive {
+    if newest.age_secs <= stale_after {
+        let reason = if !newest.owner_local {
+            format!(
+                "run has recent active lease for task '{}' attempt {} held by host '{}' (pid {} cannot be checked from here; age={}s). wait until stale window ({}s) before resuming",
+                task_id,
+                attempt,
+                newest.record.hostname.as_deref().unwrap_or_default(),
+                newest.record.owner_pid,
+                newest.age_secs,
+                stale_after
+            )
+        } else if newest.owner_alive {
             format!(
                 "run appears active: recent active lease for task '{}' attempt {} (owner pid {} alive; age={}s)",
                 task_id, attempt, newest.record.owner_pid, newest.age_secs
@@ -226,7 +370,7 @@ pub(crate) fn evaluate_orphan_attempt_at(
         } else {
             format!(
                 "run has recent active lease for task '{}' attempt {} (owner pid {} not alive; age={}s). wait until stale window ({}s) before resuming",
-                task_id, attempt, newest.record.owner_pid, newest.age_secs, LEASE_STALE_AFTER_SECS
+                task_id, attempt, newest.record.owner_pid, newest.age_secs, stale_after
             )
         };
         return Ok(OrphanLeaseDecision::LikelyActive { reason, details });
@@ -253,6 +397,23 @@ pub(crate) fn process_alive(pid: u32) -> bool {
     cmd.status().map(|status| status.success()).unwrap_or(false)
 }
 
+/// Best-effort name of this machine, used to tell whether a lease's `owner_pid` is local.
+fn local_hostname() -> String {
+    fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .or_else(|| {
+            Command::new("hostname")
+                .stderr(Stdio::null())
+                .output()
+                .ok()
+                .filter(|out| out.status.success())
+                .map(|out| String::from_utf8_lossy(&out.stdout).into_owned())
+        })
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
 fn read_lease(path: &Path) -> Result<AttemptLeaseRecord> {
     let raw = fs::read_to_string(path).with_context(|| format!("read lease {}", path.display()))?;
     let record = serde_json::from_str(&raw)
@@ -283,6 +444,35 @@ mod tests {
     use chrono::Duration as ChronoDuration;
     use tempfile::tempdir;
 
+    #[test]
+    fn run_lock_excludes_live_holder_and_replaces_dead_one() {
+        let tmp = tempdir().unwrap();
+        let lock = acquire_run_lock(tmp.path(), "run-1").unwrap();
+        let err = acquire_run_lock(tmp.path(), "run-1").unwrap_err();
+        assert!(format!("{err}").contains("run already in progress"));
+        let lock_dir = run_lock_path(tmp.path(), "run-1");
+        let entries = fs::read_dir(lock_dir.parent().unwrap()).unwrap().count();
+        assert_eq!(
+            entries, 1,
+            "refused acquire must not leave its temp lock behind"
+        );
+        drop(lock);
+        assert!(!run_lock_path(tmp.path(), "run-1").exists());
+
+        let dead = RunLockRecord {
+            run_id: "run-1".to_string(),
+            owner_pid: 999_999,
+            hostname: local_hostname(),
+            acquired_at: Utc::now().to_rfc3339(),
+        };
+        fs::write(
+            run_lock_path(tmp.path(), "run-1"),
+            serde_json::to_string(&dead).unwrap(),
+        )
+        .unwrap();
+        let _lock = acquire_run_lock(tmp.path(), "run-1").unwrap();
+    }
+
     #[test]
     fn lease_lifecycle_roundtrip() {
         let tmp = tempdir().unwrap();
@@ -297,16 +487,41 @@ mod tests {
     fn recent_active_lease_is_likely_active() {
         let tmp = tempdir().unwrap();
         let _ = init_active_lease(tmp.path(), "run-1", "task-a", 1, "implementer").unwrap();
-        let decision = evaluate_orphan_attempt(tmp.path(), "run-1", "task-a", 1).unwrap();
+        let decision =
+            evaluate_orphan_attempt(tmp.path(), "run-1", "task-a", 1, LEASE_STALE_AFTER_SECS)
+                .unwrap();
         assert!(matches!(decision, OrphanLeaseDecision::LikelyActive { .. }));
     }
 
+    #[test]
+    fn missing_or_unreadable_lease_interrupts() {
+        let tmp = tempdir().unwrap();
+        let decision =
+            evaluate_orphan_attempt(tmp.path(), "run-1", "task-a", 1, LEASE_STALE_AFTER_SECS)
+                .unwrap();
+        let OrphanLeaseDecision::Interrupt { details, .. } = decision else {
+            panic!("missing lease should interrupt");
+        };
+        assert_eq!(details["state"], "missing");
+
+        let path = lease_path(tmp.path(), "run-1", "task-a", 1, "implementer");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{\"version\": 1, \"run_id\"").unwrap();
+        let decision =
+            evaluate_orphan_attempt(tmp.path(), "run-1", "task-a", 1, LEASE_STALE_AFTER_SECS)
+                .unwrap();
+        let OrphanLeaseDecision::Interrupt { details, .. } = decision else {
+            panic!("unreadable lease should interrupt");
+        };
+        assert_eq!(details["state"], "unreadable");
+    }
+
     #[test]
     fn stale_active_lease_interrupts() {
         let tmp = tempdir().unwrap();
         let path = lease_path(tmp.path(), "run-1", "task-a", 1, "implementer");
         let now = Utc::now();
-        let stale = now - ChronoDuration::seconds(LEASE_STALE_AFTER_SECS + 5);
+        let stale = now - ChronoDuration::seconds(LEASE_STALE_AFTER_SECS as i64 + 5);
         let record = AttemptLeaseRecord {
             version: LEASE_SCHEMA_VERSION,
             run_id: "run-1".to_string(),
@@ -314,12 +529,112 @@ mod tests {
             attempt: 1,
             role: "implementer".to_string(),
             owner_pid: 999_999,
+            hostname: None,
             started_at: stale.to_rfc3339(),
             last_seen_at: stale.to_rfc3339(),
             state: LeaseState::Active,
         };
         write_lease(&path, &record).unwrap();
-        let decision = evaluate_orphan_attempt_at(tmp.path(), "run-1", "task-a", 1, now).unwrap();
+        let decision = evaluate_orphan_attempt_at(
+            tmp.path(),
+            "run-1",
+            "task-a",
+            1,
+            LEASE_STALE_AFTER_SECS,
+            now,
+        )
+        .unwrap();
         assert!(matches!(decision, OrphanLeaseDecision::Interrupt { .. }));
     }
+
+    #[test]
+    fn foreign_host_lease_with_live_pid_interrupts_once_stale() {
+        let tmp = tempdir().unwrap();
+        let path = lease_path(tmp.path(), "run-1", "task-a", 1, "implementer");
+        let now = Utc::now();
+        let stale = now - ChronoDuration::seconds(LEASE_STALE_AFTER_SECS as i64 + 5);
+        let record = AttemptLeaseRecord {
+            version: LEASE_SCHEMA_VERSION,
+            run_id: "run-1".to_string(),
+            task_id: "task-a".to_string(),
+            attempt: 1,
+            role: "implementer".to_string(),
+            owner_pid: std::process::id(),
+            hostname: Some(format!("{}-elsewhere", local_hostname())),
+            started_at: stale.to_rfc3339(),
+            last_seen_at: stale.to_rfc3339(),
+            state: LeaseState::Active,
+        };
+        write_lease(&path, &record).unwrap();
+
+        let decision = evaluate_orphan_attempt_at(
+            tmp.path(),
+            "run-1",
+            "task-a",
+            1,
+            LEASE_STALE_AFTER_SECS,
+            now,
+        )
+        .unwrap();
+        let OrphanLeaseDecision::Interrupt { details, .. } = decision else {
+            panic!("foreign stale lease should interrupt");
+        };
+        assert_eq!(details["owner_local"], false);
+        assert_eq!(details["owner_alive"], false);
+
+        let fresh = evaluate_orphan_attempt_at(
+            tmp.path(),
+            "run-1",
+            "task-a",
+            1,
+            LEASE_STALE_AFTER_SECS,
+            stale + ChronoDuration::seconds(1),
+        )
+        .unwrap();
+        let OrphanLeaseDecision::LikelyActive { reason, .. } = fresh else {
+            panic!("recent foreign lease should wait for the stale window");
+        };
+        assert!(reason.contains("cannot be checked from here"));
+    }
+
+    #[test]
+    fn custom_stale_window_keeps_slow_ticking_lease_active() {
+        let tmp = tempdir().unwrap();
+        let path = lease_path(tmp.path(), "run-1", "task-a", 1, "implementer");
+        let now = Utc::now();
+        let last_seen = now - ChronoDuration::seconds(LEASE_STALE_AFTER_SECS as i64 + 30);
+        let record = AttemptLeaseRecord {
+            version: LEASE_SCHEMA_VERSION,
+            run_id: "run-1".to_string(),
+            task_id: "task-a".to_string(),
+            attempt: 1,
+            role: "implementer".to_string(),
+            owner_pid: 999_999,
+            hostname: None,
+            started_at: last_seen.to_rfc3339(),
+            last_seen_at: last_seen.to_rfc3339(),
+            state: LeaseState::Active,
+        };
+        write_lease(&path, &record).unwrap();
+
+        let default_window = evaluate_orphan_attempt_at(
+            tmp.path(),
+            "run-1",
+            "task-a",
+            1,
+            LEASE_STALE_AFTER_SECS,
+            now,
+        )
+        .unwrap();
+        assert!(matches!(
+            default_window,
+            OrphanLeaseDecision::Interrupt { .. }
+        ));
+        let widened =
+            evaluate_orphan_attempt_at(tmp.path(), "run-1", "task-a", 1, 300, now).unwrap();
+        let OrphanLeaseDecision::LikelyActive { details, .. } = widened else {
+            panic!("lease inside the configured window should stay active");
+        };
+        assert_eq!(details["stale_after_secs"], 300);
+    }
 }
@@ -1,25 +1,39 @@
 use crate::checks;
-use crate::events::NewEvent;
-use crate::events::projector::RunProjection;
+use crate::checks::runner::CheckStage;
+use crate::config::{IntegrationStrategy, TaskChecksMode, WorktreeCleanup};
+use crate::events::projector::{RunProjection, TaskProjection};
 use crate::events::store::EventStore;
+use crate::events::{EventRow, NewEvent, attempt_dedupe_key};
+use crate::logging::diag;
+use crate::logging::ndjson::EventLog;
 use crate::policy;
 use crate::run::lease::{self, LeaseTicker};
-use crate::run::{RunConfig, append_event, packet, run_artifact_dir, scheduler, sha256_hex};
+use crate::run::{
+    BUDGET_QUESTION_PREFIX, RunConfig, append_event, attempt_logs_dir, capsule_path, packet,
+    pause_for_question, project_run, run_artifact_dir, scheduler, sha256_hex, stop_requested,
+};
 use crate::vcs;
-use crate::workers::provider::{AgentProvider, AgentRequest, AgentResult, provider_for};
-use anyhow::{Context, Result};
+use crate::workers::provider::{
+    AgentOutcome, AgentProvider, AgentRequest, AgentResult, provider_for,
+};
+use crate::workers::reviewer::{Finding, ReportedFinding};
+use anyhow::{Context, Result, anyhow};
 use serde::Deserialize;
 use serde_json::json;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub struct LoopInput {
     pub run_id: String,
     pub cfg: RunConfig,
+    /// Repository the run works against; worktrees and merges are made from it.
     pub base_dir: PathBuf,
+    /// Directory holding every run's artifacts (`<repo>/.thence/runs` unless configured).
+    pub runs_dir: PathBuf,
     pub plan_spl: String,
-    pub ndjson_log: Option<PathBuf>,
+    pub ndjson_log: Option<EventLog>,
 }
 
 pub fn run_supervisor_loop(store: &EventStore, input: LoopInput) -> Result<String> {
@@ -27,13 +41,17 @@ pub fn run_supervisor_loop(store: &EventStore, input: LoopInput) -> Result<Strin
         &input.cfg.agent,
         input.cfg.simulate,
         input.cfg.agent_command.as_deref(),
+        input.cfg.agent_spawn_retries,
     )?;
+    let merge_queue = vcs::merge::MergeQueue::default();
 
     loop {
-        let events = store.list_events(&input.run_id)?;
-        let projected = RunProjection::replay(&events);
-        let policy_state =
-            policy::spindle_bridge::derive_policy_state(&projected, &input.plan_spl)?;
+        let projected = project_run(store, &input.run_id)?;
+        let policy_state = policy::spindle_bridge::derive_policy_state(
+            &projected,
+            &input.plan_spl,
+            input.cfg.extra_policy_rules()?.as_deref(),
+        )?;
 
         if let Some(term) = projected.terminal {
             return Ok(term);
@@ -41,638 +59,1316 @@ pub fn run_supervisor_loop(store: &EventStore, input: LoopInput) -> Result<Strin
         if policy_state.run_paused {
             return Ok("run_paused".to_string());
         }
+        if stop_requested() {
+            return pause_for_interrupt(store, &input);
+        }
+        if fail_tasks_past_deadline(store, &input, &projected)? {
+            continue;
+        }
+        if skip_tasks_blocked_by_failures(store, &input, &projected)? {
+            continue;
+        }
 
-        if let Some(task_id) =
-            scheduler::next_claimable_task(&projected, &policy_state, input.cfg.max_attempts)
-        {
-            let task = projected.tasks.get(&task_id).expect("task exists");
-            let attempt = task.attempts + 1;
-            let worker_id = format!("impl-{}", (attempt as usize % input.cfg.workers) + 1);
+        let claims_so_far = projected.tasks.values().map(|t| t.attempts).sum::<i64>();
+        let budget_left = input
+            .cfg
+            .max_total_attempts
+            .map(|cap| usize::try_from(cap - claims_so_far).unwrap_or(0));
+        let mut task_ids = scheduler::next_claimable_tasks(
+            &projected,
+            &policy_state,
+            input.cfg.max_attempts,
+            input.cfg.workers,
+        );
+        if let Some(left) = budget_left {
+            if left == 0 && !task_ids.is_empty() {
+                return pause_for_attempt_budget(store, &input, claims_so_far);
+            }
+            task_ids.truncate(left);
+        }
+        if !task_ids.is_empty() {
+            let backoff = task_ids
+                .iter()
+                .filter_map(|task_id| projected.tasks.get(task_id))
+                .map(|task| attempt_backoff(input.cfg.attempt_backoff_secs, task))
+                .max()
+                .unwrap_or_default();
+            if !backoff.is_zero() && !sleep_unless_interrupted(backoff) {
+                return pause_for_interrupt(store, &input);
+            }
+
+            // Prompts carry earlier findings, so only a claiming iteration reads the full log.
+            let events = store.list_events(&input.run_id)?;
+            let mut claimed = Vec::new();
+            for task_id in &task_ids {
+                let task = projected.tasks.get(task_id).expect("task exists");
+                let worker_id = scheduler::worker_slot_id("impl", claimed.len(), input.cfg.workers);
+                if let Some(attempt) = claim_attempt(store, &input, task, worker_id)? {
+                    claimed.push(attempt);
+                }
+            }
 
+            let implemented =
+                run_implementers(store, &input, &*provider, &projected, &events, claimed)?;
+            let reviewed =
+                run_reviewers(store, &input, &*provider, &projected, &events, implemented)?;
+            for item in reviewed {
+                if let Some(merge) = settle_reviewed_attempt(store, &input, &projected, item)? {
+                    merge_queue.enqueue(merge);
+                }
+            }
+            merge_queue.drain(|merge| merge_attempt(store, &input, merge))?;
+
+            continue;
+        }
+
+        let all_done = !projected.tasks.is_empty()
+            && projected
+                .tasks
+                .values()
+                .all(|t| t.closed || t.terminal_failed || t.skipped);
+        if all_done {
+            let any_closed = projected.tasks.values().any(|t| t.closed);
+            let has_unfinished = projected
+                .tasks
+                .values()
+                .any(|t| t.terminal_failed || t.skipped);
+            let final_event =
+                if !any_closed || (has_unfinished && !input.cfg.allow_partial_completion) {
+                    "run_failed"
+                } else {
+                    "run_completed"
+                };
+            let mut payload = json!({
+                "task_count": projected.tasks.len(),
+                "summary": run_summary(&projected)
+            });
+            if !any_closed {
+                payload["reason"] = json!("no task closed");
+            }
             append_event(
                 store,
                 &input.run_id,
-                &NewEvent {
-                    event_type: "task_claimed".to_string(),
-                    task_id: Some(task_id.clone()),
-                    actor_role: Some("implementer".to_string()),
-                    actor_id: Some(worker_id.clone()),
-                    attempt: Some(attempt),
-                    payload_json: json!({"attempt": attempt}),
-                    dedupe_key: None,
-                },
-                input.ndjson_log.as_deref(),
+                &NewEvent::simple(final_event, payload),
+                input.ndjson_log.as_ref(),
             )?;
+            return Ok(final_event.to_string());
+        }
 
-            let worktree = match vcs::worktree::prepare_worktree(
-                &input.base_dir,
-                &input.run_id,
-                &task_id,
-                attempt,
-                &worker_id,
-                &input.cfg.worktree_provision_files,
-            ) {
-                Ok(path) => path,
-                Err(err) => {
-                    let findings = vec![format!("worktree provisioning failed: {err}")];
-                    let reason = findings[0].clone();
-                    append_event(
-                        store,
-                        &input.run_id,
-                        &NewEvent {
-                            event_type: "review_found_issues".to_string(),
-                            task_id: Some(task_id.clone()),
-                            actor_role: Some("supervisor".to_string()),
-                            actor_id: Some("worktree-provisioner".to_string()),
-                            attempt: Some(attempt),
-                            payload_json: json!({
-                                "reason": reason,
-                                "findings": findings,
-                                "source": "worktree_provisioning"
-                            }),
-                            dedupe_key: None,
-                        },
-                        input.ndjson_log.as_deref(),
-                    )?;
-                    if attempt >= input.cfg.max_attempts {
-                        append_event(
-                            store,
-                            &input.run_id,
-                            &NewEvent {
-                                event_type: "task_failed_terminal".to_string(),
-                                task_id: Some(task_id),
-                                actor_role: Some("supervisor".to_string()),
-                                actor_id: Some("supervisor-1".to_string()),
-                                attempt: Some(attempt),
-                                payload_json: json!({"reason": "max attempts reached after worktree provisioning failure"}),
-                                dedupe_key: None,
-                            },
-                            input.ndjson_log.as_deref(),
-                        )?;
-                    }
-                    continue;
-                }
-            };
+        if attempts_in_flight(&input, &projected)? {
+            if !sleep_unless_interrupted(Duration::from_millis(input.cfg.poll_interval_ms)) {
+                return pause_for_interrupt(store, &input);
+            }
+            continue;
+        }
 
-            let implementer_payload = parse_prompt_json(&packet::build_implementer_prompt(
-                &projected,
-                &events,
-                task,
-                attempt,
-                &projected.checks_commands,
-            ));
-            let spec_ref = frozen_spec_ref(&input.base_dir, &input.run_id);
-            let implementer_capsule = json!({
-                "capsule_version": 1,
-                "role": "implementer",
-                "run_id": input.run_id,
-                "task_id": task_id,
-                "attempt": attempt,
-                "spec_ref": spec_ref,
-                "payload": implementer_payload
+        let pending_tasks = projected
+            .tasks
+            .values()
+            .filter(|t| !t.closed && !t.terminal_failed && !t.skipped)
+            .count();
+        if pending_tasks > 0 {
+            let any_attempt_room = projected.tasks.values().any(|t| {
+                !t.closed && !t.terminal_failed && !t.skipped && t.attempts < input.cfg.max_attempts
             });
-            let (implementer_capsule_path, implementer_capsule_sha) = write_capsule(
-                &input.base_dir,
-                &input.run_id,
-                &task_id,
-                attempt,
-                "implementer",
-                &implementer_capsule,
-            )?;
-            let implementer_capsule_file = implementer_capsule_path.display().to_string();
+            if !any_attempt_room {
+                append_event(
+                    store,
+                    &input.run_id,
+                    &NewEvent::simple(
+                        "run_failed",
+                        json!({
+                            "reason": "no schedulable tasks and no attempt budget",
+                            "summary": run_summary(&projected)
+                        }),
+                    ),
+                    input.ndjson_log.as_ref(),
+                )?;
+                return Ok("run_failed".to_string());
+            }
 
-            let (implementer_res, implementer_lease_path) = run_with_attempt_lease(
-                &*provider,
-                AgentRequest {
-                    role: "implementer".to_string(),
-                    task_id: task_id.clone(),
-                    attempt,
-                    worktree_path: worktree.clone(),
-                    prompt: json!({
-                        "role": "implementer",
-                        "capsule_file": implementer_capsule_file,
-                        "critical": {
-                            "task_id": task_id,
-                            "attempt": attempt,
-                            "objective": task.objective,
-                            "acceptance": task.acceptance,
-                            "spec_path": spec_ref["path"]
-                        }
+            // Deadlock on unresolved dependencies (e.g. dependency failed terminal)
+            let block_all = projected
+                .tasks
+                .values()
+                .filter(|t| !t.closed && !t.terminal_failed && !t.skipped)
+                .all(|t| {
+                    t.dependencies.iter().any(|dep| {
+                        projected
+                            .tasks
+                            .get(dep)
+                            .map(|d| d.terminal_failed || d.skipped)
+                            .unwrap_or(true)
                     })
-                    .to_string(),
-                    env: capsule_env(
-                        &implementer_capsule_path,
-                        &implementer_capsule_sha,
-                        "implementer",
+                });
+            if block_all {
+                append_event(
+                    store,
+                    &input.run_id,
+                    &NewEvent::simple(
+                        "run_failed",
+                        json!({"reason": "dependency deadlock", "summary": run_summary(&projected)}),
                     ),
-                    timeout: Duration::from_secs(input.cfg.attempt_timeout_secs),
-                },
-                &input.base_dir,
-                &input.run_id,
-                &task_id,
-                attempt,
-                "implementer",
-            )?;
-            let implementer_lease_file = implementer_lease_path.display().to_string();
-            let implementer_output =
-                validate_implementer_output(implementer_res.structured_output.as_ref());
-            let implementer_output_error = implementer_output.as_ref().err().cloned();
+                    input.ndjson_log.as_ref(),
+                )?;
+                return Ok("run_failed".to_string());
+            }
+        }
+
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent::simple(
+                "run_failed",
+                json!({"reason": "unschedulable state", "summary": run_summary(&projected)}),
+            ),
+            input.ndjson_log.as_ref(),
+        )?;
+        return Ok("run_failed".to_string());
+    }
+}
 
+/// Record the attempts cut short by Ctrl-C so resume can retry them without waiting for stale
+/// leases; their leases were already released when the agent call returned.
+fn interrupt_attempts<'a>(
+    store: &EventStore,
+    input: &LoopInput,
+    attempts: impl Iterator<Item = (&'a String, i64)>,
+) -> Result<()> {
+    for (task_id, attempt) in attempts {
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "attempt_interrupted".to_string(),
+                task_id: Some(task_id.clone()),
+                actor_role: Some("supervisor".to_string()),
+                actor_id: Some("supervisor-1".to_string()),
+                attempt: Some(attempt),
+                payload_json: json!({"reason": "run interrupted (SIGINT); lease released"}),
+                dedupe_key: Some(attempt_dedupe_key("attempt_interrupted", task_id, attempt)),
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Record `task_failed_terminal` for an attempt that used up the task's attempts. With
+/// `--worktree-keep-on-fail` the attempt's worktree is first copied to `failed/<task>/<attempt>`
+/// under the run dir and the copy's path is recorded as `failed_worktree`.
+fn fail_task_terminal(
+    store: &EventStore,
+    input: &LoopInput,
+    task_id: &str,
+    attempt: i64,
+    worktree: &Path,
+    reason: &str,
+) -> Result<()> {
+    let mut payload = json!({"reason": reason});
+    if input.cfg.worktree_keep_on_fail && worktree.exists() {
+        let dest = run_artifact_dir(&input.runs_dir, &input.run_id)
+            .join("failed")
+            .join(task_id)
+            .join(attempt.to_string());
+        vcs::worktree::preserve_worktree(worktree, &dest)
+            .with_context(|| format!("preserve failed worktree for {task_id} attempt {attempt}"))?;
+        payload["failed_worktree"] = json!(dest);
+    }
+    append_event(
+        store,
+        &input.run_id,
+        &NewEvent {
+            event_type: "task_failed_terminal".to_string(),
+            task_id: Some(task_id.to_string()),
+            actor_role: Some("supervisor".to_string()),
+            actor_id: Some("supervisor-1".to_string()),
+            attempt: Some(attempt),
+            payload_json: payload,
+            dedupe_key: None,
+        },
+        input.ndjson_log.as_ref(),
+    )?;
+    Ok(())
+}
+
+/// Fails every unclaimed open task whose deadline, measured from its first claim, has passed.
+/// Returns whether any task was failed.
+fn fail_tasks_past_deadline(
+    store: &EventStore,
+    input: &LoopInput,
+    projected: &RunProjection,
+) -> Result<bool> {
+    let now = chrono::Utc::now();
+    let mut failed = false;
+    for task in projected.tasks.values() {
+        if task.closed || task.terminal_failed || task.claimed {
+            continue;
+        }
+        let Some(deadline_secs) = task.deadline_secs.or(input.cfg.task_deadline_secs) else {
+            continue;
+        };
+        let Some(started) = task
+            .first_claimed_at
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+        else {
+            continue;
+        };
+        let elapsed_secs = (now - started.with_timezone(&chrono::Utc))
+            .num_seconds()
+            .max(0) as u64;
+        if elapsed_secs < deadline_secs {
+            continue;
+        }
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "task_failed_terminal".to_string(),
+                task_id: Some(task.id.clone()),
+                actor_role: Some("supervisor".to_string()),
+                actor_id: Some("supervisor-1".to_string()),
+                attempt: Some(task.latest_attempt),
+                payload_json: json!({
+                    "reason": "task deadline exceeded",
+                    "deadline_secs": deadline_secs,
+                    "elapsed_secs": elapsed_secs,
+                    "attempts": task.attempts
+                }),
+                dedupe_key: None,
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+        failed = true;
+    }
+    Ok(failed)
+}
+
+/// Records `task_skipped` for every unclaimed open task with a dependency that failed terminally
+/// or was itself skipped, so failures propagate down the graph instead of deadlocking the run.
+/// Returns whether any task was skipped.
+fn skip_tasks_blocked_by_failures(
+    store: &EventStore,
+    input: &LoopInput,
+    projected: &RunProjection,
+) -> Result<bool> {
+    let mut skipped = false;
+    for task in projected.tasks.values() {
+        if task.closed || task.terminal_failed || task.skipped || task.claimed {
+            continue;
+        }
+        let Some(dep) = task
+            .dependencies
+            .iter()
+            .filter_map(|dep| projected.tasks.get(dep))
+            .find(|d| d.terminal_failed || d.skipped)
+        else {
+            continue;
+        };
+        let reason = if dep.skipped {
+            format!("dependency '{}' was skipped", dep.id)
+        } else {
+            format!("dependency '{}' failed", dep.id)
+        };
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "task_skipped".to_string(),
+                task_id: Some(task.id.clone()),
+                actor_role: Some("supervisor".to_string()),
+                actor_id: Some("supervisor-1".to_string()),
+                attempt: None,
+                payload_json: json!({"reason": reason, "dependency": dep.id}),
+                dedupe_key: Some(format!("task_skipped:{}", task.id)),
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+        skipped = true;
+    }
+    Ok(skipped)
+}
+
+/// Upper bound on the delay between attempts, however many attempts have failed review.
+const ATTEMPT_BACKOFF_CAP_SECS: u64 = 300;
+
+/// `base * 2^(attempt-1)` seconds (capped) when the task's latest attempt left findings.
+fn attempt_backoff(base_secs: u64, task: &TaskProjection) -> Duration {
+    if base_secs == 0
+        || task.attempts == 0
+        || !task.unresolved_findings_attempts.contains(&task.attempts)
+    {
+        return Duration::ZERO;
+    }
+    let exponent = (task.attempts - 1).clamp(0, 31) as u32;
+    let secs = base_secs
+        .saturating_mul(1u64 << exponent)
+        .min(ATTEMPT_BACKOFF_CAP_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Whether a claimed task's attempt still holds a live lease: its outcome is yet to be recorded
+/// by whoever runs it, so the supervisor should wait rather than declare the run unschedulable.
+fn attempts_in_flight(input: &LoopInput, projected: &RunProjection) -> Result<bool> {
+    for task in projected
+        .tasks
+        .values()
+        .filter(|t| t.claimed && !t.closed && !t.terminal_failed && !t.skipped)
+    {
+        if let lease::OrphanLeaseDecision::LikelyActive { .. } = lease::evaluate_orphan_attempt(
+            &input.runs_dir,
+            &input.run_id,
+            &task.id,
+            task.latest_attempt,
+            input.cfg.lease_stale_secs,
+        )? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Sleeps in short slices so an interrupt is honoured; returns `false` if one arrived.
+fn sleep_unless_interrupted(total: Duration) -> bool {
+    let slice = Duration::from_millis(100);
+    let mut remaining = total;
+    while !remaining.is_zero() {
+        if stop_requested() {
+            return false;
+        }
+        let step = remaining.min(slice);
+        thread::sleep(step);
+        remaining -= step;
+    }
+    !stop_requested()
+}
+
+/// Open a question asking for a larger `max_total_attempts` and pause; the answer raises the cap.
+fn pause_for_attempt_budget(
+    store: &EventStore,
+    input: &LoopInput,
+    claims_so_far: i64,
+) -> Result<String> {
+    let question_id = format!("{BUDGET_QUESTION_PREFIX}{claims_so_far}");
+    append_event(
+        store,
+        &input.run_id,
+        &NewEvent::simple(
+            "spec_question_opened",
+            json!({
+                "question_id": question_id,
+                "question": format!(
+                    "The run has used its budget of {claims_so_far} attempts across all tasks and work remains. Answer with a larger total attempt budget to continue, or cancel the run."
+                ),
+                "source": "attempt_budget"
+            }),
+        ),
+        input.ndjson_log.as_ref(),
+    )?;
+    pause_for_question(
+        store,
+        &input.run_id,
+        &question_id,
+        input.ndjson_log.as_ref(),
+    )?;
+    Ok("run_paused".to_string())
+}
+
+fn pause_for_interrupt(store: &EventStore, input: &LoopInput) -> Result<String> {
+    let resume = format!("thence resume --run {}", input.run_id);
+    append_event(
+        store,
+        &input.run_id,
+        &NewEvent::simple(
+            "run_paused",
+            json!({"reason": "interrupted", "next": [resume.clone()]}),
+        ),
+        input.ndjson_log.as_ref(),
+    )?;
+    diag::info(format_args!(
+        "Run interrupted; in-flight attempts released. Resume with: {resume}"
+    ));
+    Ok("run_paused".to_string())
+}
+
+/// A task attempt that has been claimed and has a prepared worktree.
+struct ClaimedAttempt<'a> {
+    task: &'a TaskProjection,
+    attempt: i64,
+    worker_id: String,
+    worktree: PathBuf,
+    spec_ref: serde_json::Value,
+}
+
+/// An attempt whose implementer submission passed the output gate.
+struct ImplementedAttempt<'a> {
+    claimed: ClaimedAttempt<'a>,
+    implementer_res: AgentResult,
+    implementer_capsule_file: String,
+    diff_path: Option<String>,
+}
+
+struct ReviewedAttempt<'a> {
+    claimed: ClaimedAttempt<'a>,
+    reviewer_id: String,
+    reviewer_res: AgentResult,
+    duration_ms: u64,
+}
+
+fn claim_attempt<'a>(
+    store: &EventStore,
+    input: &LoopInput,
+    task: &'a TaskProjection,
+    worker_id: String,
+) -> Result<Option<ClaimedAttempt<'a>>> {
+    let task_id = task.id.clone();
+    let attempt = task.attempts + 1;
+
+    append_event(
+        store,
+        &input.run_id,
+        &NewEvent {
+            event_type: "task_claimed".to_string(),
+            task_id: Some(task_id.clone()),
+            actor_role: Some("implementer".to_string()),
+            actor_id: Some(worker_id.clone()),
+            attempt: Some(attempt),
+            payload_json: json!({"attempt": attempt}),
+            dedupe_key: None,
+        },
+        input.ndjson_log.as_ref(),
+    )?;
+    diag::debug(format_args!(
+        "Claimed {task_id} attempt {attempt} as {worker_id}"
+    ));
+
+    let worktree = match vcs::worktree::prepare_worktree(
+        &input.base_dir,
+        &run_artifact_dir(&input.runs_dir, &input.run_id),
+        &task_id,
+        attempt,
+        &worker_id,
+        &input.cfg.vcs.integration_branch(&input.run_id, &task_id),
+        &input.cfg.worktree_provision_files,
+    ) {
+        Ok(path) => path,
+        Err(err) => {
+            let findings = vec![format!("worktree provisioning failed: {err}")];
+            let reason = findings[0].clone();
             append_event(
                 store,
                 &input.run_id,
                 &NewEvent {
-                    event_type: "work_submitted".to_string(),
+                    event_type: "review_found_issues".to_string(),
                     task_id: Some(task_id.clone()),
-                    actor_role: Some("implementer".to_string()),
-                    actor_id: Some(worker_id),
+                    actor_role: Some("supervisor".to_string()),
+                    actor_id: Some("worktree-provisioner".to_string()),
                     attempt: Some(attempt),
                     payload_json: json!({
-                        "exit_code": implementer_res.exit_code,
-                        "stdout_path": implementer_res.stdout_path,
-                        "stderr_path": implementer_res.stderr_path,
-                        "capsule_path": implementer_capsule_file,
-                        "lease_path": implementer_lease_file,
-                        "output_valid": implementer_output.is_ok(),
-                        "output_error": implementer_output_error
+                        "reason": reason,
+                        "findings": findings,
+                        "source": "worktree_provisioning"
                     }),
                     dedupe_key: None,
                 },
-                input.ndjson_log.as_deref(),
+                input.ndjson_log.as_ref(),
             )?;
-
-            if implementer_res.exit_code != 0 || implementer_output.is_err() {
-                let mut findings = Vec::new();
-                if implementer_res.exit_code != 0 {
-                    findings.push(format!(
-                        "implementer exited non-zero (exit_code={})",
-                        implementer_res.exit_code
-                    ));
-                }
-                if let Err(err) = implementer_output {
-                    findings.push(format!("invalid implementer output: {err}"));
-                }
-                if findings.is_empty() {
-                    findings
-                        .push("implementer did not produce valid submission output".to_string());
-                }
-                let reason = findings[0].clone();
+            if attempt >= input.cfg.max_attempts {
                 append_event(
                     store,
                     &input.run_id,
                     &NewEvent {
-                        event_type: "review_found_issues".to_string(),
-                        task_id: Some(task_id.clone()),
+                        event_type: "task_failed_terminal".to_string(),
+                        task_id: Some(task_id),
                         actor_role: Some("supervisor".to_string()),
-                        actor_id: Some("implementer-output-gate".to_string()),
+                        actor_id: Some("supervisor-1".to_string()),
                         attempt: Some(attempt),
-                        payload_json: json!({"reason": reason, "findings": findings, "source": "implementer_output_validation"}),
+                        payload_json: json!({"reason": "max attempts reached after worktree provisioning failure"}),
                         dedupe_key: None,
                     },
-                    input.ndjson_log.as_deref(),
+                    input.ndjson_log.as_ref(),
                 )?;
-                if attempt >= input.cfg.max_attempts {
-                    append_event(
-                        store,
-                        &input.run_id,
-                        &NewEvent {
-                            event_type: "task_failed_terminal".to_string(),
-                            task_id: Some(task_id),
-                            actor_role: Some("supervisor".to_string()),
-                            actor_id: Some("supervisor-1".to_string()),
-                            attempt: Some(attempt),
-                            payload_json: json!({"reason": "max attempts reached after implementer gate failure"}),
-                            dedupe_key: None,
-                        },
-                        input.ndjson_log.as_deref(),
-                    )?;
-                }
-                continue;
             }
+            return Ok(None);
+        }
+    };
 
-            let reviewer_id = format!("rev-{}", (attempt as usize % input.cfg.reviewers) + 1);
-            let submission_refs = json!({
-                "work_submitted": {
+    Ok(Some(ClaimedAttempt {
+        task,
+        attempt,
+        worker_id,
+        worktree,
+        spec_ref: frozen_spec_ref(&input.runs_dir, &input.run_id),
+    }))
+}
+
+fn run_implementers<'a>(
+    store: &EventStore,
+    input: &LoopInput,
+    provider: &dyn AgentProvider,
+    projected: &RunProjection,
+    events: &[EventRow],
+    claimed: Vec<ClaimedAttempt<'a>>,
+) -> Result<Vec<ImplementedAttempt<'a>>> {
+    let mut requests = Vec::with_capacity(claimed.len());
+    let mut capsule_files = Vec::with_capacity(claimed.len());
+    for c in &claimed {
+        let task = c.task;
+        let implementer_payload = parse_prompt_json(&packet::build_implementer_prompt(
+            projected,
+            events,
+            task,
+            c.attempt,
+            &task_check_commands(&input.cfg, projected, task),
+            input.cfg.implementer_prompt_override.as_deref(),
+        ));
+        let implementer_capsule = json!({
+            "capsule_version": 1,
+            "role": "implementer",
+            "run_id": input.run_id,
+            "task_id": task.id,
+            "attempt": c.attempt,
+            "spec_ref": c.spec_ref,
+            "payload": implementer_payload
+        });
+        let (implementer_capsule_path, implementer_capsule_sha) = write_capsule(
+            &input.runs_dir,
+            &input.run_id,
+            &task.id,
+            c.attempt,
+            "implementer",
+            &implementer_capsule,
+        )?;
+        let implementer_capsule_file = implementer_capsule_path.display().to_string();
+        requests.push(AgentRequest {
+            role: "implementer".to_string(),
+            task_id: task.id.clone(),
+            attempt: c.attempt,
+            worktree_path: c.worktree.clone(),
+            prompt: json!({
+                "role": "implementer",
+                "capsule_file": implementer_capsule_file,
+                "critical": {
+                    "task_id": task.id,
+                    "attempt": c.attempt,
+                    "objective": task.objective,
+                    "acceptance": task.acceptance,
+                    "acceptance_criteria": task.acceptance_criteria,
+                    "spec_path": c.spec_ref["path"]
+                }
+            })
+            .to_string(),
+            env: input.cfg.agent_env(capsule_env(
+                &implementer_capsule_path,
+                &implementer_capsule_sha,
+                "implementer",
+            )),
+            timeout: Duration::from_secs(input.cfg.attempt_timeout_secs),
+        });
+        capsule_files.push(implementer_capsule_file);
+    }
+
+    let results = run_attempts_concurrently(
+        provider,
+        &input.runs_dir,
+        &input.run_id,
+        requests,
+        input.cfg.workers,
+        Duration::from_secs(input.cfg.lease_tick_secs),
+    );
+    if stop_requested() {
+        interrupt_attempts(
+            store,
+            input,
+            claimed.iter().map(|c| (&c.task.id, c.attempt)),
+        )?;
+        return Ok(Vec::new());
+    }
+
+    let mut implemented = Vec::new();
+    for ((claimed, implementer_capsule_file), result) in
+        claimed.into_iter().zip(capsule_files).zip(results)
+    {
+        let (implementer_res, implementer_lease_path, duration_ms) = result?;
+        let task_id = claimed.task.id.clone();
+        let attempt = claimed.attempt;
+        let implementer_lease_file = implementer_lease_path.display().to_string();
+        let implementer_output =
+            validate_implementer_output(implementer_res.structured_output.as_ref());
+        let implementer_output_error = implementer_output.as_ref().err().cloned();
+        let diff_path = if implementer_res.exit_code == 0 && implementer_output.is_ok() {
+            save_attempt_diff(Path::new(&implementer_capsule_file), &claimed)
+        } else {
+            None
+        };
+
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "work_submitted".to_string(),
+                task_id: Some(task_id.clone()),
+                actor_role: Some("implementer".to_string()),
+                actor_id: Some(claimed.worker_id.clone()),
+                attempt: Some(attempt),
+                payload_json: json!({
+                    "exit_code": implementer_res.exit_code,
                     "stdout_path": implementer_res.stdout_path,
                     "stderr_path": implementer_res.stderr_path,
-                    "exit_code": implementer_res.exit_code,
-                    "capsule_path": implementer_capsule_file
+                    "capsule_path": implementer_capsule_file,
+                    "lease_path": implementer_lease_file,
+                    "diff_path": diff_path,
+                    "duration_ms": duration_ms,
+                    "output_valid": implementer_output.is_ok(),
+                    "output_error": implementer_output_error,
+                    "agent_model": implementer_res.agent_model,
+                    "agent_version": implementer_res.agent_version
+                }),
+                dedupe_key: Some(attempt_dedupe_key("work_submitted", &task_id, attempt)),
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+
+        if implementer_res.exit_code != 0 || implementer_output.is_err() {
+            let mut findings = Vec::new();
+            match implementer_res.outcome() {
+                AgentOutcome::TimedOut => findings.push(format!(
+                    "implementer timed out after {}s",
+                    input.cfg.attempt_timeout_secs
+                )),
+                AgentOutcome::Exited(0) => {}
+                AgentOutcome::Exited(code) => {
+                    findings.push(format!("implementer exited non-zero (exit_code={code})"))
                 }
-            });
-            let reviewer_payload = parse_prompt_json(&packet::build_reviewer_prompt(
-                &events,
-                task,
-                attempt,
-                &projected.checks_commands,
-                submission_refs,
-                input.cfg.effective_reviewer_instruction(),
-            ));
-            let reviewer_capsule = json!({
-                "capsule_version": 1,
-                "role": "reviewer",
-                "run_id": input.run_id,
-                "task_id": task_id,
-                "attempt": attempt,
-                "spec_ref": spec_ref,
-                "payload": reviewer_payload
-            });
-            let (reviewer_capsule_path, reviewer_capsule_sha) = write_capsule(
-                &input.base_dir,
-                &input.run_id,
-                &task_id,
-                attempt,
-                "reviewer",
-                &reviewer_capsule,
-            )?;
-            let reviewer_capsule_file = reviewer_capsule_path.display().to_string();
+            }
+            if let Err(err) = implementer_output {
+                findings.push(format!("invalid implementer output: {err}"));
+            }
+            if findings.is_empty() {
+                findings.push("implementer did not produce valid submission output".to_string());
+            }
+            let reason = findings[0].clone();
             append_event(
                 store,
                 &input.run_id,
                 &NewEvent {
-                    event_type: "review_requested".to_string(),
+                    event_type: "review_found_issues".to_string(),
                     task_id: Some(task_id.clone()),
                     actor_role: Some("supervisor".to_string()),
-                    actor_id: Some("supervisor-1".to_string()),
+                    actor_id: Some("implementer-output-gate".to_string()),
                     attempt: Some(attempt),
-                    payload_json: json!({"attempt": attempt, "capsule_path": reviewer_capsule_file}),
+                    payload_json: json!({"reason": reason, "findings": findings, "source": "implementer_output_validation"}),
                     dedupe_key: None,
                 },
-                input.ndjson_log.as_deref(),
+                input.ndjson_log.as_ref(),
             )?;
-            let (reviewer_res, _reviewer_lease_path) = run_with_attempt_lease(
-                &*provider,
-                AgentRequest {
-                    role: "reviewer".to_string(),
-                    task_id: task_id.clone(),
+            if attempt >= input.cfg.max_attempts {
+                fail_task_terminal(
+                    store,
+                    input,
+                    &task_id,
                     attempt,
-                    worktree_path: worktree.clone(),
-                    prompt: json!({
-                        "role": "reviewer",
-                        "instruction": input.cfg.effective_reviewer_instruction(),
-                        "capsule_file": reviewer_capsule_file,
-                        "critical": {
-                            "task_id": task_id,
-                            "attempt": attempt,
-                            "objective": task.objective,
-                            "acceptance": task.acceptance,
-                            "spec_path": spec_ref["path"]
-                        }
-                    })
-                    .to_string(),
-                    env: capsule_env(&reviewer_capsule_path, &reviewer_capsule_sha, "reviewer"),
-                    timeout: Duration::from_secs(input.cfg.attempt_timeout_secs),
-                },
-                &input.base_dir,
-                &input.run_id,
-                &task_id,
-                attempt,
-                "reviewer",
+                    &claimed.worktree,
+                    "max attempts reached after implementer gate failure",
+                )?;
+            }
+            continue;
+        }
+
+        if let Some(findings) =
+            run_check_stage(store, input, projected, &claimed, CheckStage::PreReview)?
+        {
+            record_checks_failure(
+                store,
+                input,
+                &claimed,
+                findings,
+                "pre_review_checks",
+                "max attempts reached after failed pre-review checks",
             )?;
+            continue;
+        }
 
-            let reviewer_output = match validate_reviewer_output(
-                reviewer_res.structured_output.as_ref(),
-            ) {
-                Ok(output) => output,
-                Err(err) => {
-                    let findings = vec![format!("invalid reviewer output: {err}")];
-                    let reason = findings[0].clone();
-                    append_event(
-                        store,
-                        &input.run_id,
-                        &NewEvent {
-                            event_type: "review_found_issues".to_string(),
-                            task_id: Some(task_id.clone()),
-                            actor_role: Some("reviewer".to_string()),
-                            actor_id: Some(reviewer_id.clone()),
-                            attempt: Some(attempt),
-                            payload_json: json!({
-                                "reason": reason,
-                                "findings": findings,
-                                "source": "reviewer_output_validation"
-                            }),
-                            dedupe_key: None,
-                        },
-                        input.ndjson_log.as_deref(),
-                    )?;
-                    if attempt >= input.cfg.max_attempts {
-                        append_event(
-                            store,
-                            &input.run_id,
-                            &NewEvent {
-                                event_type: "task_failed_terminal".to_string(),
-                                task_id: Some(task_id),
-                                actor_role: Some("supervisor".to_string()),
-                                actor_id: Some("supervisor-1".to_string()),
-                                attempt: Some(attempt),
-                                payload_json: json!({"reason": "max attempts reached after invalid reviewer output"}),
-                                dedupe_key: None,
-                            },
-                            input.ndjson_log.as_deref(),
-                        )?;
-                    }
-                    continue;
+        implemented.push(ImplementedAttempt {
+            claimed,
+            implementer_res,
+            implementer_capsule_file,
+            diff_path,
+        });
+    }
+    Ok(implemented)
+}
+
+fn run_reviewers<'a>(
+    store: &EventStore,
+    input: &LoopInput,
+    provider: &dyn AgentProvider,
+    projected: &RunProjection,
+    events: &[EventRow],
+    implemented: Vec<ImplementedAttempt<'a>>,
+) -> Result<Vec<ReviewedAttempt<'a>>> {
+    let mut requests = Vec::with_capacity(implemented.len());
+    let mut reviewer_ids = Vec::with_capacity(implemented.len());
+    for (slot, item) in implemented.iter().enumerate() {
+        let c = &item.claimed;
+        let task = c.task;
+        let reviewer_id = scheduler::worker_slot_id("rev", slot, input.cfg.reviewers);
+        let submission_refs = json!({
+            "work_submitted": {
+                "stdout_path": item.implementer_res.stdout_path,
+                "stderr_path": item.implementer_res.stderr_path,
+                "exit_code": item.implementer_res.exit_code,
+                "capsule_path": item.implementer_capsule_file,
+                "diff_path": item.diff_path.as_deref().unwrap_or(NO_DIFF_AVAILABLE)
+            }
+        });
+        let reviewer_payload = parse_prompt_json(&packet::build_reviewer_prompt(
+            events,
+            task,
+            c.attempt,
+            &task_check_commands(&input.cfg, projected, task),
+            submission_refs,
+            input.cfg.effective_reviewer_instruction(),
+        ));
+        let reviewer_capsule = json!({
+            "capsule_version": 1,
+            "role": "reviewer",
+            "run_id": input.run_id,
+            "task_id": task.id,
+            "attempt": c.attempt,
+            "spec_ref": c.spec_ref,
+            "payload": reviewer_payload
+        });
+        let (reviewer_capsule_path, reviewer_capsule_sha) = write_capsule(
+            &input.runs_dir,
+            &input.run_id,
+            &task.id,
+            c.attempt,
+            "reviewer",
+            &reviewer_capsule,
+        )?;
+        let reviewer_capsule_file = reviewer_capsule_path.display().to_string();
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "review_requested".to_string(),
+                task_id: Some(task.id.clone()),
+                actor_role: Some("supervisor".to_string()),
+                actor_id: Some("supervisor-1".to_string()),
+                attempt: Some(c.attempt),
+                payload_json: json!({"attempt": c.attempt, "capsule_path": reviewer_capsule_file}),
+                dedupe_key: Some(attempt_dedupe_key("review_requested", &task.id, c.attempt)),
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+        requests.push(AgentRequest {
+            role: "reviewer".to_string(),
+            task_id: task.id.clone(),
+            attempt: c.attempt,
+            worktree_path: c.worktree.clone(),
+            prompt: json!({
+                "role": "reviewer",
+                "instruction": input.cfg.effective_reviewer_instruction(),
+                "capsule_file": reviewer_capsule_file,
+                "critical": {
+                    "task_id": task.id,
+                    "attempt": c.attempt,
+                    "objective": task.objective,
+                    "acceptance": task.acceptance,
+                    "acceptance_criteria": task.acceptance_criteria,
+                    "spec_path": c.spec_ref["path"]
                 }
-            };
+            })
+            .to_string(),
+            env: input.cfg.agent_env(capsule_env(
+                &reviewer_capsule_path,
+                &reviewer_capsule_sha,
+                "reviewer",
+            )),
+            timeout: Duration::from_secs(input.cfg.effective_reviewer_timeout_secs()),
+        });
+        reviewer_ids.push(reviewer_id);
+    }
 
-            if !reviewer_output.approved {
-                let findings = if reviewer_output.findings.is_empty() {
-                    vec!["reviewer rejected submission without findings".to_string()]
-                } else {
-                    reviewer_output.findings
-                };
-                let reason = findings[0].clone();
-                append_event(
-                    store,
-                    &input.run_id,
-                    &NewEvent {
-                        event_type: "review_found_issues".to_string(),
-                        task_id: Some(task_id.clone()),
-                        actor_role: Some("reviewer".to_string()),
-                        actor_id: Some(reviewer_id),
-                        attempt: Some(attempt),
-                        payload_json: json!({"reason": reason, "findings": findings, "source": "reviewer"}),
-                        dedupe_key: None,
-                    },
-                    input.ndjson_log.as_deref(),
-                )?;
+    let results = run_attempts_concurrently(
+        provider,
+        &input.runs_dir,
+        &input.run_id,
+        requests,
+        input.cfg.reviewers,
+        Duration::from_secs(input.cfg.lease_tick_secs),
+    );
+    if stop_requested() {
+        interrupt_attempts(
+            store,
+            input,
+            implemented
+                .iter()
+                .map(|item| (&item.claimed.task.id, item.claimed.attempt)),
+        )?;
+        return Ok(Vec::new());
+    }
 
-                if attempt >= input.cfg.max_attempts {
-                    append_event(
-                        store,
-                        &input.run_id,
-                        &NewEvent {
-                            event_type: "task_failed_terminal".to_string(),
-                            task_id: Some(task_id),
-                            actor_role: Some("supervisor".to_string()),
-                            actor_id: Some("supervisor-1".to_string()),
-                            attempt: Some(attempt),
-                            payload_json: json!({"reason": "max attempts reached after review findings"}),
-                            dedupe_key: None,
-                        },
-                        input.ndjson_log.as_deref(),
-                    )?;
-                }
-                continue;
-            }
+    let mut reviewed = Vec::new();
+    for ((item, reviewer_id), result) in implemented.into_iter().zip(reviewer_ids).zip(results) {
+        let (reviewer_res, _reviewer_lease_path, duration_ms) = result?;
+        reviewed.push(ReviewedAttempt {
+            claimed: item.claimed,
+            reviewer_id,
+            reviewer_res,
+            duration_ms,
+        });
+    }
+    Ok(reviewed)
+}
 
-            append_event(
-                store,
-                &input.run_id,
-                &NewEvent {
-                    event_type: "review_approved".to_string(),
-                    task_id: Some(task_id.clone()),
-                    actor_role: Some("reviewer".to_string()),
-                    actor_id: Some(reviewer_id),
-                    attempt: Some(attempt),
-                    payload_json: json!({"approved": true, "finding_count": reviewer_output.findings.len()}),
-                    dedupe_key: None,
-                },
-                input.ndjson_log.as_deref(),
-            )?;
+fn settle_reviewed_attempt(
+    store: &EventStore,
+    input: &LoopInput,
+    projected: &RunProjection,
+    reviewed: ReviewedAttempt<'_>,
+) -> Result<Option<vcs::merge::MergeRequest>> {
+    let ReviewedAttempt {
+        claimed,
+        reviewer_id,
+        reviewer_res,
+        duration_ms,
+    } = reviewed;
+    let task = claimed.task;
+    let task_id = task.id.clone();
+    let attempt = claimed.attempt;
+    let worktree = claimed.worktree.clone();
 
-            let checks = if !projected.checks_commands.is_empty() {
-                projected.checks_commands.clone()
-            } else if task.required_checks.is_empty() {
-                input.cfg.checks.clone()
-            } else {
-                task.required_checks.clone()
-            };
-            let (checks_ok, checks_payload) = checks::runner::run_checks(
-                &worktree,
-                &checks,
-                Duration::from_secs(input.cfg.check_timeout_secs),
-            )?;
-            let checks_findings = if checks_ok {
-                Vec::new()
-            } else {
-                checks_failure_findings(&checks_payload)
-            };
+    let reviewer_timed_out = reviewer_res.timed_out;
+    let reviewer_output = if reviewer_timed_out {
+        Err(format!(
+            "reviewer timed out after {}s",
+            input.cfg.effective_reviewer_timeout_secs()
+        ))
+    } else {
+        validate_reviewer_output(reviewer_res.structured_output.as_ref())
+    };
+    let reviewer_output = match reviewer_output {
+        Ok(output) => output,
+        Err(err) => {
+            let findings = vec![format!("invalid reviewer output: {err}")];
+            let reason = findings[0].clone();
             append_event(
                 store,
                 &input.run_id,
                 &NewEvent {
-                    event_type: "checks_reported".to_string(),
+                    event_type: "review_found_issues".to_string(),
                     task_id: Some(task_id.clone()),
-                    actor_role: Some("supervisor".to_string()),
-                    actor_id: Some("checks-1".to_string()),
+                    actor_role: Some("reviewer".to_string()),
+                    actor_id: Some(reviewer_id.clone()),
                     attempt: Some(attempt),
-                    payload_json: checks_payload,
+                    payload_json: json!({
+                        "reason": reason,
+                        "findings": findings,
+                        "source": "reviewer_output_validation",
+                        "timed_out": reviewer_timed_out,
+                        "duration_ms": duration_ms,
+                        "agent_model": reviewer_res.agent_model,
+                        "agent_version": reviewer_res.agent_version
+                    }),
                     dedupe_key: None,
                 },
-                input.ndjson_log.as_deref(),
+                input.ndjson_log.as_ref(),
             )?;
-
-            if !checks_ok {
-                let findings = checks_findings;
-                let reason = findings
-                    .first()
-                    .cloned()
-                    .unwrap_or_else(|| "checks failed".to_string());
-                append_event(
+            if attempt >= input.cfg.max_attempts {
+                fail_task_terminal(
                     store,
-                    &input.run_id,
-                    &NewEvent {
-                        event_type: "review_found_issues".to_string(),
-                        task_id: Some(task_id.clone()),
-                        actor_role: Some("supervisor".to_string()),
-                        actor_id: Some("checks-gate".to_string()),
-                        attempt: Some(attempt),
-                        payload_json: json!({"reason": reason, "findings": findings, "source": "checks_gate"}),
-                        dedupe_key: None,
-                    },
-                    input.ndjson_log.as_deref(),
-                )?;
-                if attempt >= input.cfg.max_attempts {
-                    append_event(
-                        store,
-                        &input.run_id,
-                        &NewEvent {
-                            event_type: "task_failed_terminal".to_string(),
-                            task_id: Some(task_id),
-                            actor_role: Some("supervisor".to_string()),
-                            actor_id: Some("supervisor-1".to_string()),
-                            attempt: Some(attempt),
-                            payload_json: json!({"reason": "max attempts reached after failed checks"}),
-                            dedupe_key: None,
-                        },
-                        input.ndjson_log.as_deref(),
-                    )?;
-                }
-                continue;
-            }
-
-            let current = RunProjection::replay(&store.list_events(&input.run_id)?);
-            let policy_after_checks =
-                policy::spindle_bridge::derive_policy_state(&current, &input.plan_spl)?;
-            if !policy_after_checks.merge_ready.contains(&task_id) {
-                continue;
-            }
-
-            let merged = vcs::merge::attempt_merge(&task.objective, attempt);
-            if merged {
-                append_event(
-                    store,
-                    &input.run_id,
-                    &NewEvent {
-                        event_type: "merge_succeeded".to_string(),
-                        task_id: Some(task_id.clone()),
-                        actor_role: Some("supervisor".to_string()),
-                        actor_id: Some("merge-queue".to_string()),
-                        attempt: Some(attempt),
-                        payload_json: json!({"integration_branch": format!("thence/{}", input.run_id)}),
-                        dedupe_key: None,
-                    },
-                    input.ndjson_log.as_deref(),
-                )?;
-                append_event(
-                    store,
-                    &input.run_id,
-                    &NewEvent {
-                        event_type: "task_closed".to_string(),
-                        task_id: Some(task_id),
-                        actor_role: Some("supervisor".to_string()),
-                        actor_id: Some("supervisor-1".to_string()),
-                        attempt: Some(attempt),
-                        payload_json: json!({"closed": true}),
-                        dedupe_key: None,
-                    },
-                    input.ndjson_log.as_deref(),
-                )?;
-            } else {
-                append_event(
-                    store,
-                    &input.run_id,
-                    &NewEvent {
-                        event_type: "merge_conflict".to_string(),
-                        task_id: Some(task_id.clone()),
-                        actor_role: Some("supervisor".to_string()),
-                        actor_id: Some("merge-queue".to_string()),
-                        attempt: Some(attempt),
-                        payload_json: json!({"reason": "simulated conflict"}),
-                        dedupe_key: None,
-                    },
-                    input.ndjson_log.as_deref(),
-                )?;
-                append_event(
-                    store,
-                    &input.run_id,
-                    &NewEvent {
-                        event_type: "review_found_issues".to_string(),
-                        task_id: Some(task_id),
-                        actor_role: Some("supervisor".to_string()),
-                        actor_id: Some("merge-queue".to_string()),
-                        attempt: Some(attempt),
-                        payload_json: json!({"reason": "merge conflict; reopen"}),
-                        dedupe_key: None,
-                    },
-                    input.ndjson_log.as_deref(),
+                    input,
+                    &task_id,
+                    attempt,
+                    &worktree,
+                    "max attempts reached after invalid reviewer output",
                 )?;
             }
-
-            continue;
+            return Ok(None);
         }
+    };
 
-        let all_done = !projected.tasks.is_empty()
-            && projected
-                .tasks
-                .values()
-                .all(|t| t.closed || t.terminal_failed);
-        if all_done {
-            let has_terminal_failed = projected.tasks.values().any(|t| t.terminal_failed);
-            let final_event = if has_terminal_failed && !input.cfg.allow_partial_completion {
-                "run_failed"
-            } else {
-                "run_completed"
-            };
-            append_event(
+    if !reviewer_output.approved {
+        let findings = reviewer_output
+            .findings
+            .iter()
+            .map(|f| f.message.clone())
+            .collect::<Vec<_>>();
+        let reason = findings[0].clone();
+        let structured_findings = reviewer_output
+            .findings
+            .iter()
+            .any(Finding::is_structured)
+            .then_some(&reviewer_output.findings);
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "review_found_issues".to_string(),
+                task_id: Some(task_id.clone()),
+                actor_role: Some("reviewer".to_string()),
+                actor_id: Some(reviewer_id),
+                attempt: Some(attempt),
+                payload_json: json!({
+                    "reason": reason,
+                    "findings": findings,
+                    "structured_findings": structured_findings,
+                    "source": "reviewer",
+                    "duration_ms": duration_ms,
+                    "agent_model": reviewer_res.agent_model,
+                    "agent_version": reviewer_res.agent_version
+                }),
+                dedupe_key: None,
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+
+        if attempt >= input.cfg.max_attempts {
+            fail_task_terminal(
                 store,
-                &input.run_id,
-                &NewEvent::simple(final_event, json!({"task_count": projected.tasks.len()})),
-                input.ndjson_log.as_deref(),
+                input,
+                &task_id,
+                attempt,
+                &worktree,
+                "max attempts reached after review findings",
             )?;
-            return Ok(final_event.to_string());
         }
+        return Ok(None);
+    }
 
-        let pending_tasks = projected
-            .tasks
-            .values()
-            .filter(|t| !t.closed && !t.terminal_failed)
-            .count();
-        if pending_tasks > 0 {
-            let any_attempt_room = projected
-                .tasks
-                .values()
-                .any(|t| !t.closed && !t.terminal_failed && t.attempts < input.cfg.max_attempts);
-            if !any_attempt_room {
-                append_event(
-                    store,
-                    &input.run_id,
-                    &NewEvent::simple(
-                        "run_failed",
-                        json!({"reason": "no schedulable tasks and no attempt budget"}),
-                    ),
-                    input.ndjson_log.as_deref(),
-                )?;
-                return Ok("run_failed".to_string());
-            }
+    let advisories = reviewer_output
+        .findings
+        .iter()
+        .filter(|f| f.is_advisory())
+        .collect::<Vec<_>>();
+    let advisories = (!advisories.is_empty()).then_some(advisories);
+    append_event(
+        store,
+        &input.run_id,
+        &NewEvent {
+            event_type: "review_approved".to_string(),
+            task_id: Some(task_id.clone()),
+            actor_role: Some("reviewer".to_string()),
+            actor_id: Some(reviewer_id),
+            attempt: Some(attempt),
+            payload_json: json!({
+                "approved": true,
+                "finding_count": reviewer_output.findings.len(),
+                "advisories": advisories,
+                "duration_ms": duration_ms,
+                "agent_model": reviewer_res.agent_model,
+                "agent_version": reviewer_res.agent_version
+            }),
+            dedupe_key: Some(attempt_dedupe_key("review_approved", &task_id, attempt)),
+        },
+        input.ndjson_log.as_ref(),
+    )?;
 
-            // Deadlock on unresolved dependencies (e.g. dependency failed terminal)
-            let block_all = projected
-                .tasks
-                .values()
-                .filter(|t| !t.closed && !t.terminal_failed)
-                .all(|t| {
-                    t.dependencies.iter().any(|dep| {
-                        projected
-                            .tasks
-                            .get(dep)
-                            .map(|d| d.terminal_failed)
-                            .unwrap_or(true)
-                    })
-                });
-            if block_all {
-                append_event(
-                    store,
-                    &input.run_id,
-                    &NewEvent::simple("run_failed", json!({"reason": "dependency deadlock"})),
-                    input.ndjson_log.as_deref(),
-                )?;
-                return Ok("run_failed".to_string());
-            }
-        }
+    if let Some(findings) =
+        run_check_stage(store, input, projected, &claimed, CheckStage::PostReview)?
+    {
+        record_checks_failure(
+            store,
+            input,
+            &claimed,
+            findings,
+            "checks_gate",
+            "max attempts reached after failed checks",
+        )?;
+        return Ok(None);
+    }
+
+    Ok(Some(vcs::merge::MergeRequest {
+        task_id,
+        attempt,
+        worktree,
+        objective: task.objective.clone(),
+    }))
+}
+
+/// Run the task's checks for `stage` in the attempt worktree and record `checks_reported`.
+/// Returns the failure findings, or `None` when every check passed or none belong to the stage.
+fn run_check_stage(
+    store: &EventStore,
+    input: &LoopInput,
+    projected: &RunProjection,
+    claimed: &ClaimedAttempt<'_>,
+    stage: CheckStage,
+) -> Result<Option<Vec<String>>> {
+    let specs = input
+        .cfg
+        .check_specs(&task_check_commands(&input.cfg, projected, claimed.task))
+        .into_iter()
+        .filter(|spec| spec.stage == stage)
+        .collect::<Vec<_>>();
+    if specs.is_empty() && stage == CheckStage::PreReview {
+        return Ok(None);
+    }
+    let started = Instant::now();
+    let (checks_ok, mut checks_payload) = checks::runner::run_checks(
+        &claimed.worktree,
+        &specs,
+        Duration::from_secs(input.cfg.check_timeout_secs),
+        input.cfg.check_concurrency,
+        input.cfg.check_output_tail_bytes,
+        input.cfg.check_retries,
+    )?;
+    let checks_findings = if checks_ok {
+        None
+    } else {
+        Some(checks_failure_findings(&checks_payload))
+    };
+    checks_payload["stage"] = json!(stage.as_str());
+    checks_payload["checks_source"] =
+        json!(task_checks_source(&input.cfg, projected, claimed.task));
+    checks_payload["duration_ms"] = json!(elapsed_ms(started));
+    let dedupe_key = format!(
+        "{}:{}",
+        attempt_dedupe_key("checks_reported", &claimed.task.id, claimed.attempt),
+        stage.as_str()
+    );
+    append_event(
+        store,
+        &input.run_id,
+        &NewEvent {
+            event_type: "checks_reported".to_string(),
+            task_id: Some(claimed.task.id.clone()),
+            actor_role: Some("supervisor".to_string()),
+            actor_id: Some("checks-1".to_string()),
+            attempt: Some(claimed.attempt),
+            payload_json: checks_payload,
+            dedupe_key: Some(dedupe_key),
+        },
+        input.ndjson_log.as_ref(),
+    )?;
+    Ok(checks_findings)
+}
 
+/// Send an attempt back with its failed checks, failing the task once attempts run out.
+fn record_checks_failure(
+    store: &EventStore,
+    input: &LoopInput,
+    claimed: &ClaimedAttempt<'_>,
+    findings: Vec<String>,
+    source: &str,
+    terminal_reason: &str,
+) -> Result<()> {
+    let reason = findings
+        .first()
+        .and_then(|f| f.lines().next())
+        .unwrap_or("checks failed")
+        .to_string();
+    append_event(
+        store,
+        &input.run_id,
+        &NewEvent {
+            event_type: "review_found_issues".to_string(),
+            task_id: Some(claimed.task.id.clone()),
+            actor_role: Some("supervisor".to_string()),
+            actor_id: Some("checks-gate".to_string()),
+            attempt: Some(claimed.attempt),
+            payload_json: json!({"reason": reason, "findings": findings, "source": source}),
+            dedupe_key: None,
+        },
+        input.ndjson_log.as_ref(),
+    )?;
+    if claimed.attempt >= input.cfg.max_attempts {
+        fail_task_terminal(
+            store,
+            input,
+            &claimed.task.id,
+            claimed.attempt,
+            &claimed.worktree,
+            terminal_reason,
+        )?;
+    }
+    Ok(())
+}
+
+/// Merge one queued attempt, re-checking `merge_ready` against freshly replayed state since
+/// earlier merges in the queue may have changed it. A conflict reopens the task.
+fn merge_attempt(
+    store: &EventStore,
+    input: &LoopInput,
+    merge: vcs::merge::MergeRequest,
+) -> Result<()> {
+    let vcs::merge::MergeRequest {
+        task_id,
+        attempt,
+        worktree,
+        objective,
+    } = merge;
+    let current = project_run(store, &input.run_id)?;
+    let policy_now = policy::spindle_bridge::derive_policy_state(
+        &current,
+        &input.plan_spl,
+        input.cfg.extra_policy_rules()?.as_deref(),
+    )?;
+    if !policy_now.merge_ready.contains(&task_id) {
+        return Ok(());
+    }
+
+    let integration_branch = input.cfg.vcs.integration_branch(&input.run_id, &task_id);
+    let integration_dir = match input.cfg.vcs.integration_strategy {
+        IntegrationStrategy::PerRun => {
+            run_artifact_dir(&input.runs_dir, &input.run_id).join("integration")
+        }
+        IntegrationStrategy::PerTask => run_artifact_dir(&input.runs_dir, &input.run_id)
+            .join("integration")
+            .join(&task_id),
+    };
+    let merged = if input.cfg.simulate {
+        vcs::merge::simulate_merge(&objective, attempt)
+    } else {
+        vcs::merge::attempt_merge(
+            &input.base_dir,
+            &integration_dir,
+            &integration_branch,
+            &worktree,
+            &format!("thence: {task_id} attempt {attempt}"),
+        )
+        .with_context(|| format!("merge {task_id} attempt {attempt} into {integration_branch}"))?
+    };
+    if merged {
         append_event(
             store,
             &input.run_id,
-            &NewEvent::simple("run_failed", json!({"reason": "unschedulable state"})),
-            input.ndjson_log.as_deref(),
+            &NewEvent {
+                event_type: "merge_succeeded".to_string(),
+                task_id: Some(task_id.clone()),
+                actor_role: Some("supervisor".to_string()),
+                actor_id: Some("merge-queue".to_string()),
+                attempt: Some(attempt),
+                payload_json: json!({"integration_branch": integration_branch}),
+                dedupe_key: None,
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+        diag::debug(format_args!(
+            "Merged {task_id} attempt {attempt} into {integration_branch}"
+        ));
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "task_closed".to_string(),
+                task_id: Some(task_id.clone()),
+                actor_role: Some("supervisor".to_string()),
+                actor_id: Some("supervisor-1".to_string()),
+                attempt: Some(attempt),
+                payload_json: json!({"closed": true}),
+                dedupe_key: None,
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+        if input.cfg.worktree_cleanup == WorktreeCleanup::OnClose {
+            let logs_dir = attempt_logs_dir(
+                &run_artifact_dir(&input.runs_dir, &input.run_id),
+                &task_id,
+                attempt,
+            );
+            vcs::worktree::preserve_attempt_logs(&worktree, &logs_dir)
+                .with_context(|| format!("keep agent logs for {task_id} attempt {attempt}"))?;
+            vcs::worktree::remove_worktree(&input.base_dir, &worktree)
+                .with_context(|| format!("clean up worktree for {task_id} attempt {attempt}"))?;
+        }
+    } else {
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "merge_conflict".to_string(),
+                task_id: Some(task_id.clone()),
+                actor_role: Some("supervisor".to_string()),
+                actor_id: Some("merge-queue".to_string()),
+                attempt: Some(attempt),
+                payload_json: json!({
+                    "reason": if input.cfg.simulate { "simulated conflict" } else { "git merge conflict" },
+                    "integration_branch": integration_branch,
+                }),
+                dedupe_key: None,
+            },
+            input.ndjson_log.as_ref(),
+        )?;
+        append_event(
+            store,
+            &input.run_id,
+            &NewEvent {
+                event_type: "review_found_issues".to_string(),
+                task_id: Some(task_id),
+                actor_role: Some("supervisor".to_string()),
+                actor_id: Some("merge-queue".to_string()),
+                attempt: Some(attempt),
+                payload_json: json!({"reason": "merge conflict; reopen"}),
+                dedupe_key: None,
+            },
+            input.ndjson_log.as_ref(),
         )?;
-        return Ok("run_failed".to_string());
     }
+
+    Ok(())
+}
+
+/// Run agent requests on scoped threads, at most `limit` at a time, returning results in
+/// request order so callers can append events deterministically on the main thread.
+fn run_attempts_concurrently(
+    provider: &dyn AgentProvider,
+    runs_root: &Path,
+    run_id: &str,
+    requests: Vec<AgentRequest>,
+    limit: usize,
+    lease_tick: Duration,
+) -> Vec<Result<(AgentResult, PathBuf, u64)>> {
+    let mut results = Vec::with_capacity(requests.len());
+    let mut pending = requests.into_iter().peekable();
+    while pending.peek().is_some() {
+        let batch = pending.by_ref().take(limit.max(1)).collect::<Vec<_>>();
+        thread::scope(|scope| {
+            let handles = batch
+                .into_iter()
+                .map(|req| {
+                    scope.spawn(move || {
+                        run_with_attempt_lease(provider, req, runs_root, run_id, lease_tick)
+                    })
+                })
+                .collect::<Vec<_>>();
+            for handle in handles {
+                results.push(
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err(anyhow!("agent worker thread panicked"))),
+                );
+            }
+        });
+    }
+    results
 }
 
 fn run_with_attempt_lease(
     provider: &dyn AgentProvider,
     req: AgentRequest,
-    repo_root: &Path,
+    runs_root: &Path,
     run_id: &str,
-    task_id: &str,
-    attempt: i64,
-    role: &str,
-) -> Result<(AgentResult, PathBuf)> {
-    let lease_path = lease::init_active_lease(repo_root, run_id, task_id, attempt, role)
+    lease_tick: Duration,
+) -> Result<(AgentResult, PathBuf, u64)> {
+    let task_id = req.task_id.clone();
+    let attempt = req.attempt;
+    let role = req.role.clone();
+    let lease_path = lease::init_active_lease(runs_root, run_id, &task_id, attempt, &role)
         .with_context(|| {
             format!(
                 "initialize lease for task '{}' attempt {} role {}",
                 task_id, attempt, role
             )
         })?;
-    let ticker = LeaseTicker::start(
-        lease_path.clone(),
-        Duration::from_secs(lease::LEASE_TICK_SECS),
-    );
+    let ticker = LeaseTicker::start(lease_path.clone(), lease_tick);
 
+    let started = Instant::now();
     let res = provider.run(req);
+    let duration_ms = elapsed_ms(started);
     ticker.stop();
     let release_res = lease::release_lease(&lease_path);
 
@@ -683,7 +1379,12 @@ fn run_with_attempt_lease(
             task_id, attempt, role
         )
     })?;
-    Ok((result, lease_path))
+    Ok((result, lease_path, duration_ms))
+}
+
+/// Wall-clock milliseconds since `started`, as recorded in phase `duration_ms` payloads.
+fn elapsed_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
 }
 
 fn parse_prompt_json(raw: &str) -> serde_json::Value {
@@ -699,7 +1400,13 @@ struct ImplementerOutput {
 struct ReviewerOutput {
     approved: bool,
     #[serde(default)]
-    findings: Vec<String>,
+    findings: Vec<ReportedFinding>,
+}
+
+#[derive(Debug)]
+struct ReviewVerdict {
+    approved: bool,
+    findings: Vec<Finding>,
 }
 
 fn validate_implementer_output(
@@ -718,24 +1425,118 @@ fn validate_implementer_output(
 
 fn validate_reviewer_output(
     output: Option<&serde_json::Value>,
-) -> std::result::Result<ReviewerOutput, String> {
+) -> std::result::Result<ReviewVerdict, String> {
     let raw = output
         .cloned()
         .ok_or_else(|| "missing structured JSON output".to_string())?;
-    let mut parsed: ReviewerOutput =
+    let parsed: ReviewerOutput =
         serde_json::from_value(raw).map_err(|err| format!("output schema mismatch: {err}"))?;
-    parsed.findings = parsed
+    let mut findings = parsed
         .findings
         .into_iter()
-        .map(|f| f.trim().to_string())
-        .filter(|f| !f.is_empty())
-        .collect();
-    if !parsed.approved && parsed.findings.is_empty() {
-        parsed
-            .findings
-            .push("reviewer rejected submission without findings".to_string());
+        .filter_map(Finding::normalized)
+        .collect::<Vec<_>>();
+    if !parsed.approved && findings.is_empty() {
+        findings.push(Finding::new(
+            "reviewer rejected submission without findings",
+        ));
+    }
+    // A rejection that only carries advisory findings does not need another attempt.
+    let advisory_only = !findings.is_empty() && findings.iter().all(Finding::is_advisory);
+    Ok(ReviewVerdict {
+        approved: parsed.approved || advisory_only,
+        findings,
+    })
+}
+
+/// `closed`, `failed`, `skipped`, or `pending` while the task can still be attempted.
+pub(super) fn task_outcome(t: &TaskProjection) -> &'static str {
+    if t.closed {
+        "closed"
+    } else if t.terminal_failed {
+        "failed"
+    } else if t.skipped {
+        "skipped"
+    } else {
+        "pending"
+    }
+}
+
+/// Roll-up of task outcomes attached to terminal run events.
+fn run_summary(projected: &RunProjection) -> serde_json::Value {
+    let tasks = projected.tasks.values().collect::<Vec<_>>();
+    let count = |want: &str| tasks.iter().filter(|t| task_outcome(t) == want).count();
+    json!({
+        "closed": count("closed"),
+        "failed": count("failed"),
+        "skipped": count("skipped"),
+        "pending": count("pending"),
+        "tasks": tasks
+            .iter()
+            .map(|t| json!({
+                "task_id": t.id,
+                "attempts": t.attempts,
+                "latest_attempt": t.latest_attempt,
+                "outcome": task_outcome(t)
+            }))
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Checks gating a task: the approved run-level commands (or configured ones before approval),
+/// combined with the task's own `required_checks` per `task_checks_mode`.
+fn task_check_commands(
+    cfg: &RunConfig,
+    projected: &RunProjection,
+    task: &TaskProjection,
+) -> Vec<String> {
+    let run_checks = run_check_commands(cfg, projected);
+    if task.required_checks.is_empty() {
+        return run_checks.clone();
+    }
+    match cfg.task_checks_mode {
+        TaskChecksMode::Replace => task.required_checks.clone(),
+        TaskChecksMode::Union => {
+            let mut merged = run_checks.clone();
+            for check in &task.required_checks {
+                if !merged.contains(check) {
+                    merged.push(check.clone());
+                }
+            }
+            merged
+        }
+    }
+}
+
+fn run_check_commands<'a>(cfg: &'a RunConfig, projected: &'a RunProjection) -> &'a Vec<String> {
+    if projected.checks_commands.is_empty() {
+        &cfg.checks
+    } else {
+        &projected.checks_commands
+    }
+}
+
+/// Which set `task_check_commands` picked, recorded as `checks_source` on `checks_reported`:
+/// `task` under `task_checks = "replace"`, `merged` when the union added task checks, else `run`.
+fn task_checks_source(
+    cfg: &RunConfig,
+    projected: &RunProjection,
+    task: &TaskProjection,
+) -> &'static str {
+    if task.required_checks.is_empty() {
+        return "run";
+    }
+    match cfg.task_checks_mode {
+        TaskChecksMode::Replace => "task",
+        TaskChecksMode::Union => {
+            let run_checks = run_check_commands(cfg, projected);
+            if task.required_checks.iter().all(|c| run_checks.contains(c)) {
+                "run"
+            } else {
+                "merged"
+            }
+        }
     }
-    Ok(parsed)
 }
 
 fn checks_failure_findings(checks_payload: &serde_json::Value) -> Vec<String> {
@@ -758,11 +1559,22 @@ fn checks_failure_findings(checks_payload: &serde_json::Value) -> Vec<String> {
                         .get("command")
                         .and_then(|v| v.as_str())
                         .unwrap_or("<unknown>");
-                    if timed_out {
-                        Some(format!("check timed out: {command}"))
+                    let mut finding = if timed_out {
+                        format!("check timed out: {command}")
                     } else {
-                        Some(format!("check failed: {command}"))
+                        format!("check failed: {command}")
+                    };
+                    for stream in ["stdout", "stderr"] {
+                        let output = entry
+                            .get(format!("{stream}_tail").as_str())
+                            .and_then(|v| v.as_str())
+                            .map(str::trim_end)
+                            .unwrap_or("");
+                        if !output.is_empty() {
+                            finding.push_str(&format!("\n{stream} (tail):\n{output}"));
+                        }
                     }
+                    Some(finding)
                 })
                 .collect::<Vec<_>>()
         })
@@ -774,18 +1586,14 @@ fn checks_failure_findings(checks_payload: &serde_json::Value) -> Vec<String> {
 }
 
 fn write_capsule(
-    repo_root: &Path,
+    runs_root: &Path,
     run_id: &str,
     task_id: &str,
     attempt: i64,
     role: &str,
     capsule: &serde_json::Value,
 ) -> Result<(PathBuf, String)> {
-    let path = run_artifact_dir(repo_root, run_id)
-        .join("capsules")
-        .join(task_id)
-        .join(format!("attempt{attempt}"))
-        .join(format!("{role}.json"));
+    let path = capsule_path(&run_artifact_dir(runs_root, run_id), task_id, attempt, role);
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -795,6 +1603,29 @@ fn write_capsule(
     Ok((path, digest))
 }
 
+/// Stands in for the reviewer's `diff_path` when the attempt has no captured diff.
+const NO_DIFF_AVAILABLE: &str = "no diff available";
+
+/// Write the implementer's changes to `diff.patch` beside the attempt's capsules. `None` when
+/// the worktree is not a git worktree or the diff could not be taken.
+fn save_attempt_diff(capsule_file: &Path, claimed: &ClaimedAttempt<'_>) -> Option<String> {
+    let path = capsule_file.with_file_name("diff.patch");
+    let saved = vcs::merge::worktree_diff(&claimed.worktree).and_then(|diff| {
+        diff.map(|patch| {
+            fs::write(&path, patch)?;
+            Ok(path.display().to_string())
+        })
+        .transpose()
+    });
+    saved.unwrap_or_else(|err| {
+        diag::warn(format_args!(
+            "could not capture diff for {} attempt {}: {err:#}",
+            claimed.task.id, claimed.attempt
+        ));
+        None
+    })
+}
+
 fn capsule_env(path: &Path, digest: &str, role: &str) -> Vec<(String, String)> {
     vec![
         (
@@ -806,8 +1637,8 @@ fn capsule_env(path: &Path, digest: &str, role: &str) -> Vec<(String, String)> {
     ]
 }
 
-fn frozen_spec_ref(repo_root: &Path, run_id: &str) -> serde_json::Value {
-    let spec_path = run_artifact_dir(repo_root, run_id).join("spec.md");
+fn frozen_spec_ref(runs_root: &Path, run_id: &str) -> serde_json::Value {
+    let spec_path = run_artifact_dir(runs_root, run_id).join("spec.md");
     let spec_sha256 = fs::read_to_string(&spec_path)
         .ok()
         .map(|raw| sha256_hex(&raw));
@@ -1,39 +1,74 @@
+mod diff;
+mod doctor;
+mod interrupt;
 pub(crate) mod lease;
 mod r#loop;
 pub mod packet;
 pub mod scheduler;
+mod timeline;
 mod transitions;
 
+use crate::checks::config as checks_config;
+use crate::checks::runner::{CheckSpec, CheckStage};
 use crate::events::projector::RunProjection;
 use crate::events::store::{EventStore, RunRow};
-use crate::events::{EventRow, NewEvent};
-use crate::logging::ndjson;
-use crate::plan::{review_loop, sanity, translator, validate};
-use crate::workers::provider::{AgentRequest, provider_for};
+use crate::events::{EventRow, NewEvent, attempt_dedupe_key};
+use crate::logging::diag;
+use crate::logging::ndjson::{self, EventLog, LogFormat};
+use crate::plan::{review_loop, sanity, translator, validate, yaml};
+use crate::workers::provider::{
+    AgentRequest, agent_log_paths, ensure_supported_provider, provider_for,
+};
 use anyhow::{Context, Result, anyhow, bail};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use sha2::{Digest, Sha256};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
+use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use uuid::Uuid;
 
+pub use diff::{RunDiff, TaskOutcome, TaskOutcomeChange, diff_run_outcomes, diff_runs};
+pub use doctor::{DoctorCheck, doctor, run_doctor_checks};
+pub use interrupt::install_interrupt_handler;
+pub(crate) use interrupt::stop_requested;
+pub use timeline::run_timeline;
+
 const NO_CHECKS_CONFIGURED_ERROR: &str =
     "No checks configured. Set `--checks` or `[checks].commands` in `.thence/config.toml`.";
-const DEFAULT_REVIEWER_INSTRUCTION: &str = "Review implementation against objective/acceptance.\nReturn strict JSON with: approved (bool), findings (string[]).";
+const CHECKS_QUESTION_ID: &str = "checks-q-propose";
+const SPEC_DRIFT_QUESTION_ID: &str = "spec-q-drift";
+const CONFIG_DRIFT_QUESTION_ID: &str = "config-q-drift";
+/// Followed by the attempts claimed when the budget ran out, so each pause has a fresh id.
+pub(crate) const BUDGET_QUESTION_PREFIX: &str = "budget-q-attempts-";
+const SNAPSHOT_EVERY_EVENTS: usize = 50;
+/// Answer recorded by `thence answer --approve`.
+pub const APPROVE_ANSWER: &str = "approve";
+/// Note recorded by `thence answer --reject`; the question stays open.
+const REJECT_NOTE: &str = "rejected; answer again with --text or --approve";
+const TRANSLATOR_LOG_TAIL_BYTES: usize = 4 * 1024;
+/// `spec_approved` source when `--no-spec-review` bypassed the spec gate.
+const SPEC_REVIEW_SKIPPED_SOURCE: &str = "skipped";
+/// Where `thence run --plan-stdin` saves the piped spec inside the run dir.
+const STDIN_SPEC_FILE: &str = "stdin-spec.md";
+const DEFAULT_REVIEWER_INSTRUCTION: &str = "Review implementation against objective/acceptance.\nReturn strict JSON with: approved (bool), findings (string[], or {message, severity?, file?, line?, blocking?}[]); findings with blocking=false are advisory.";
 
 #[derive(Debug, Clone)]
 pub struct RunCommand {
+    /// Spec to run; replaced by the saved copy when the spec comes from [`execute_run_from_reader`].
     pub plan_file: PathBuf,
     pub agent: String,
     pub workers: usize,
     pub reviewers: usize,
     pub checks: Option<String>,
+    /// JSON or TOML file listing `commands`; below `checks`, above `[checks].commands`.
+    pub checks_from_file: Option<PathBuf>,
     pub simulate: bool,
     pub log: Option<PathBuf>,
+    pub log_format: LogFormat,
     pub resume: bool,
     pub run_id: Option<String>,
     pub state_db: Option<PathBuf>,
@@ -41,7 +76,27 @@ pub struct RunCommand {
     pub trust_plan_checks: bool,
     pub interactive: bool,
     pub attempt_timeout_secs: Option<u64>,
+    pub reviewer_timeout_secs: Option<u64>,
+    pub max_attempts: Option<i64>,
+    pub check_timeout_secs: Option<u64>,
+    pub agent_spec_review: bool,
+    /// Approve the spec without the ambiguity gate (`spec_approved` source `skipped`).
+    pub no_spec_review: bool,
+    /// With no `--checks` or `[checks].commands`, ask a `checks-proposer` agent instead of failing.
+    pub propose_checks: bool,
+    /// Neither read nor update `.thence/checks.json`.
+    pub no_checks_file: bool,
+    /// Ignore `.thence/checks.json` and ask the `checks-proposer` agent again.
+    pub reconfigure_checks: bool,
+    /// Copy the worktree of a terminally failed attempt to `failed/<task>/<attempt>`.
+    pub worktree_keep_on_fail: bool,
+    /// Stop once the spec is approved and print the task graph instead of running tasks.
+    pub dry_run: bool,
+    /// Register only these translated tasks and their transitive dependencies.
+    pub only_task: Vec<String>,
     pub debug_dump_spl: Option<PathBuf>,
+    /// Keep run artifacts here instead of `<repo>/.thence/runs`; overrides `[run].artifacts_dir`.
+    pub artifacts_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,31 +108,190 @@ pub struct RunConfig {
     pub checks: Vec<String>,
     #[serde(default)]
     pub checks_from_cli: bool,
+    /// Checks were loaded from `.thence/checks.json` saved by an earlier run.
+    #[serde(default, alias = "checks_from_file")]
+    pub checks_from_saved_file: bool,
+    /// Absolute path of the `--checks-from-file` list the checks were loaded from.
+    #[serde(default)]
+    pub checks_source_file: Option<PathBuf>,
+    #[serde(default)]
+    pub no_checks_file: bool,
     #[serde(default)]
     pub simulate: bool,
     pub allow_partial_completion: bool,
     pub trust_plan_checks: bool,
     pub interactive: bool,
+    /// Ask a `spec-reviewer` agent to gate the spec instead of only the keyword heuristic.
+    #[serde(default)]
+    pub agent_spec_review: bool,
+    /// Skip the spec gate; the plan must still translate and validate.
+    #[serde(default)]
+    pub no_spec_review: bool,
+    #[serde(default)]
+    pub propose_checks: bool,
     #[serde(default = "default_max_attempts")]
     pub max_attempts: i64,
+    /// Translated plans with more tasks than this are rejected.
+    #[serde(default)]
+    pub max_tasks: Option<usize>,
+    /// Claims allowed across all tasks before the run pauses on `budget-q-attempts-<n>`; answering
+    /// that question with a larger number raises it.
+    #[serde(default)]
+    pub max_total_attempts: Option<i64>,
     #[serde(default = "default_check_timeout_secs")]
     pub check_timeout_secs: u64,
+    /// Per-command timeouts from `[checks].commands` tables, keyed by command text.
+    #[serde(default)]
+    pub check_timeout_overrides: BTreeMap<String, u64>,
+    /// Per-command working directories from `[checks].commands` tables, relative to the worktree.
+    #[serde(default)]
+    pub check_workdirs: BTreeMap<String, PathBuf>,
+    /// Commands whose `[checks].commands` table sets a non-default `stage`.
+    #[serde(default)]
+    pub check_stages: BTreeMap<String, CheckStage>,
+    #[serde(default = "default_check_concurrency")]
+    pub check_concurrency: usize,
+    /// Re-runs of a failing check command before the gate treats it as failed.
+    #[serde(default)]
+    pub check_retries: u32,
+    #[serde(default = "default_check_output_tail_bytes")]
+    pub check_output_tail_bytes: usize,
+    #[serde(default)]
+    pub task_checks_mode: crate::config::TaskChecksMode,
     #[serde(default = "default_attempt_timeout_secs")]
     pub attempt_timeout_secs: u64,
+    #[serde(default = "default_lease_stale_secs")]
+    pub lease_stale_secs: u64,
+    #[serde(default = "default_lease_tick_secs")]
+    pub lease_tick_secs: u64,
+    /// Wall-clock budget per task from its first claim; a task's own `deadline_secs` wins.
+    #[serde(default)]
+    pub task_deadline_secs: Option<u64>,
+    /// Base delay before re-claiming a task whose previous attempt had findings; zero disables.
+    #[serde(default)]
+    pub attempt_backoff_secs: u64,
+    /// Sleep between supervisor passes while nothing is claimable but attempts are in flight.
+    #[serde(default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    #[serde(default)]
+    pub spec_drift: crate::config::DriftAction,
+    #[serde(default)]
+    pub config_drift: crate::config::DriftAction,
+    #[serde(default)]
+    pub reviewer_timeout_secs: Option<u64>,
     #[serde(default)]
     pub reviewer_prompt_override: Option<String>,
     #[serde(default)]
+    pub implementer_prompt_override: Option<String>,
+    #[serde(default)]
     pub agent_command: Option<String>,
+    #[serde(default = "default_agent_spawn_retries")]
+    pub agent_spawn_retries: u32,
+    #[serde(default)]
+    pub agent_env: BTreeMap<String, String>,
     #[serde(default)]
     pub worktree_provision_files: Vec<crate::config::ProvisionedFile>,
+    #[serde(default)]
+    pub worktree_cleanup: crate::config::WorktreeCleanup,
+    #[serde(default)]
+    pub worktree_keep_on_fail: bool,
+    /// `--only-task` ids; the plan is pruned to them and their dependencies on every load.
+    #[serde(default)]
+    pub only_tasks: Vec<String>,
+    #[serde(default)]
+    pub vcs: crate::config::VcsConfig,
+    /// Absolute directory holding run artifacts; `None` keeps them under `<repo>/.thence/runs`.
+    #[serde(default)]
+    pub artifacts_dir: Option<PathBuf>,
+    /// Absolute path of the `[policy].extra_rules_file` SPL snippet.
+    #[serde(default)]
+    pub policy_extra_rules_file: Option<PathBuf>,
+    /// Repository root resolved when the run started; configs recorded before it was saved have
+    /// none and use the plan file's directory.
+    #[serde(default)]
+    pub repo_root: Option<PathBuf>,
 }
 
 impl RunConfig {
+    /// Directory that holds this run's artifact dir.
+    pub fn runs_root(&self, repo_root: &Path) -> PathBuf {
+        self.artifacts_dir
+            .clone()
+            .unwrap_or_else(|| default_runs_root(repo_root))
+    }
+
+    /// The run's repository: the recorded `repo_root`, else `plan_path`'s directory, which is
+    /// where runs started before the root was recorded kept their `.thence`.
+    pub fn repo_root(&self, plan_path: &Path) -> Result<PathBuf> {
+        if let Some(root) = &self.repo_root {
+            return Ok(root.clone());
+        }
+        let p = plan_path
+            .canonicalize()
+            .with_context(|| format!("resolve plan path {}", plan_path.display()))?;
+        p.parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow!("cannot derive repo root from {}", p.display()))
+    }
+
+    /// `checks_approved` source for the checks resolved at run start.
+    fn checks_source(&self) -> &'static str {
+        if self.checks_from_cli {
+            "cli"
+        } else if self.checks_source_file.is_some() {
+            "checks_from_file"
+        } else if self.checks_from_saved_file {
+            "checks_file"
+        } else {
+            "config"
+        }
+    }
+
     pub fn effective_reviewer_instruction(&self) -> &str {
         self.reviewer_prompt_override
             .as_deref()
             .unwrap_or(DEFAULT_REVIEWER_INSTRUCTION)
     }
+
+    pub fn check_specs(&self, commands: &[String]) -> Vec<CheckSpec> {
+        commands
+            .iter()
+            .map(|command| CheckSpec {
+                command: command.clone(),
+                timeout_secs: self.check_timeout_overrides.get(command).copied(),
+                workdir: self.check_workdirs.get(command).cloned(),
+                stage: self.check_stages.get(command).copied().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Configured `[agent].env` followed by the `THENCE_*` variables thence sets for this call.
+    pub fn agent_env(&self, reserved: Vec<(String, String)>) -> Vec<(String, String)> {
+        self.agent_env
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .chain(reserved)
+            .collect()
+    }
+
+    /// The `[policy].extra_rules_file` snippet, read afresh on every call so edits (say, a new
+    /// signal fact) apply to a run in progress.
+    pub fn extra_policy_rules(&self) -> Result<Option<String>> {
+        let Some(path) = self.policy_extra_rules_file.as_ref() else {
+            return Ok(None);
+        };
+        let src = std::fs::read_to_string(path)
+            .with_context(|| format!("read extra policy rules {}", path.display()))?;
+        crate::policy::spindle_bridge::validate_extra_rules(&src)
+            .with_context(|| format!("invalid extra policy rules {}", path.display()))?;
+        Ok(Some(src))
+    }
+
+    /// Reviewer attempts fall back to the implementer's `attempt_timeout_secs` when unset.
+    pub fn effective_reviewer_timeout_secs(&self) -> u64 {
+        self.reviewer_timeout_secs
+            .unwrap_or(self.attempt_timeout_secs)
+    }
 }
 
 fn default_state_db() -> PathBuf {
@@ -102,10 +316,51 @@ fn default_check_timeout_secs() -> u64 {
     10 * 60
 }
 
+fn default_agent_spawn_retries() -> u32 {
+    2
+}
+
+fn default_check_concurrency() -> usize {
+    1
+}
+
+fn default_check_output_tail_bytes() -> usize {
+    crate::checks::runner::DEFAULT_OUTPUT_TAIL_BYTES
+}
+
 fn default_attempt_timeout_secs() -> u64 {
     45 * 60
 }
 
+fn default_lease_stale_secs() -> u64 {
+    lease::LEASE_STALE_AFTER_SECS
+}
+
+fn default_lease_tick_secs() -> u64 {
+    lease::LEASE_TICK_SECS
+}
+
+fn default_poll_interval_ms() -> u64 {
+    250
+}
+
+pub(crate) fn capsule_path(run_dir: &Path, task_id: &str, attempt: i64, role: &str) -> PathBuf {
+    run_dir
+        .join("capsules")
+        .join(task_id)
+        .join(format!("attempt{attempt}"))
+        .join(format!("{role}.json"))
+}
+
+/// Where an attempt's agent logs are kept once `[worktree] cleanup = "on_close"` removes its
+/// worktree.
+pub(crate) fn attempt_logs_dir(run_dir: &Path, task_id: &str, attempt: i64) -> PathBuf {
+    run_dir
+        .join("logs")
+        .join(task_id)
+        .join(format!("attempt{attempt}"))
+}
+
 fn translated_plan_path(run_dir: &Path) -> PathBuf {
     run_dir.join("translated_plan.json")
 }
@@ -124,10 +379,43 @@ fn write_frozen_spec(run_dir: &Path, markdown: &str) -> Result<PathBuf> {
 fn read_spec_markdown(run_dir: &Path, plan_path: &Path) -> Result<String> {
     let frozen = frozen_spec_path(run_dir);
     if frozen.exists() {
-        return fs::read_to_string(&frozen)
-            .with_context(|| format!("read frozen spec {}", frozen.display()));
+        return read_plan_text(&frozen, "frozen spec");
+    }
+    read_plan_text(plan_path, "plan file")
+}
+
+/// Read a plan as text, naming the first invalid byte when the file is not UTF-8.
+fn read_plan_text(path: &Path, what: &str) -> Result<String> {
+    let bytes = fs::read(path).with_context(|| format!("read {what} {}", path.display()))?;
+    String::from_utf8(bytes).map_err(|err| {
+        anyhow!(
+            "{what} {} is not valid UTF-8 (invalid byte at offset {}); plans must be UTF-8 markdown or YAML",
+            path.display(),
+            err.utf8_error().valid_up_to()
+        )
+    })
+}
+
+/// Translate the spec into a plan: YAML plans are parsed directly, anything else goes
+/// through the plan-translator agent. The agent result is `None` for YAML plans.
+fn translate_spec(
+    cfg: &RunConfig,
+    repo_root: &Path,
+    plan_file: &Path,
+    markdown: &str,
+    run_dir: &Path,
+) -> Result<(
+    translator::TranslatedPlan,
+    Option<crate::workers::provider::AgentResult>,
+)> {
+    if yaml::is_yaml_plan(plan_file) {
+        let translated = yaml::parse_yaml_plan(markdown)
+            .with_context(|| format!("parse YAML plan {}", plan_file.display()))?;
+        return Ok((translated, None));
     }
-    fs::read_to_string(plan_path).with_context(|| format!("read plan file {}", plan_path.display()))
+    let (translated, res) =
+        translate_spec_with_agent(cfg, repo_root, plan_file, markdown, run_dir)?;
+    Ok((translated, Some(res)))
 }
 
 fn translate_spec_with_agent(
@@ -140,7 +428,12 @@ fn translate_spec_with_agent(
     translator::TranslatedPlan,
     crate::workers::provider::AgentResult,
 )> {
-    let provider = provider_for(&cfg.agent, cfg.simulate, cfg.agent_command.as_deref())?;
+    let provider = provider_for(
+        &cfg.agent,
+        cfg.simulate,
+        cfg.agent_command.as_deref(),
+        cfg.agent_spawn_retries,
+    )?;
     let prompt = packet::build_plan_translator_prompt(
         repo_root,
         plan_file,
@@ -149,7 +442,7 @@ fn translate_spec_with_agent(
         read_optional_file(&repo_root.join("AGENTS.md")),
         read_optional_file(&repo_root.join("CLAUDE.md")),
     );
-    let worktree = run_dir.join("plan-translation").join("attempt1");
+    let worktree = plan_translation_dir(run_dir);
     fs::create_dir_all(&worktree)?;
     let res = provider.run(AgentRequest {
         role: "plan-translator".to_string(),
@@ -157,7 +450,7 @@ fn translate_spec_with_agent(
         attempt: 1,
         worktree_path: worktree,
         prompt,
-        env: Vec::new(),
+        env: cfg.agent_env(Vec::new()),
         timeout: Duration::from_secs(20 * 60),
     })?;
     if res.exit_code != 0 {
@@ -176,12 +469,114 @@ fn translate_spec_with_agent(
     Ok((translated, res))
 }
 
+fn plan_translation_dir(run_dir: &Path) -> PathBuf {
+    run_dir.join("plan-translation").join("attempt1")
+}
+
+/// Record `translation_failed`, then open the `spec-q-translate` question and pause, quoting
+/// the tail of the translator's captured output so the failure can be diagnosed without
+/// digging through the run dir.
+fn open_translation_question(
+    store: &EventStore,
+    run_id: &str,
+    run_dir: &Path,
+    err: &anyhow::Error,
+    ndjson_log: Option<&EventLog>,
+) -> Result<()> {
+    let qid = "spec-q-translate";
+    let (stdout_path, stderr_path) =
+        agent_log_paths(&plan_translation_dir(run_dir), "plan-translator", 1);
+    let stdout_tail = read_log_tail(&stdout_path, TRANSLATOR_LOG_TAIL_BYTES);
+    let stderr_tail = read_log_tail(&stderr_path, TRANSLATOR_LOG_TAIL_BYTES);
+
+    append_event(
+        store,
+        run_id,
+        &NewEvent::simple(
+            "translation_failed",
+            json!({
+                "error": format!("{err:#}"),
+                "translator_stdout_tail": stdout_tail,
+                "translator_stderr_tail": stderr_tail
+            }),
+        ),
+        ndjson_log,
+    )?;
+
+    let mut question = format!("Plan translation failed: {err}");
+    for (stream, tail) in [("stderr", &stderr_tail), ("stdout", &stdout_tail)] {
+        if let Some(tail) = tail {
+            question.push_str(&format!("\ntranslator {stream} (tail):\n{tail}"));
+        }
+    }
+    append_event(
+        store,
+        run_id,
+        &NewEvent::simple(
+            "spec_question_opened",
+            json!({
+                "question_id": qid,
+                "question": question,
+                "translator_stdout_tail": stdout_tail,
+                "translator_stderr_tail": stderr_tail
+            }),
+        ),
+        ndjson_log,
+    )?;
+    diag::info(&question);
+    pause_for_question(store, run_id, qid, ndjson_log)
+}
+
+/// Last `max_bytes` of a log file, or `None` when it is missing or blank.
+fn read_log_tail(path: &Path, max_bytes: usize) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+    let start = bytes.len().saturating_sub(max_bytes);
+    let tail = String::from_utf8_lossy(&bytes[start..]).trim().to_string();
+    (!tail.is_empty()).then_some(tail)
+}
+
+/// Run the spec gate: the keyword heuristic, or a `spec-reviewer` agent when enabled.
+fn review_spec_for_run(
+    cfg: &RunConfig,
+    run_dir: &Path,
+    markdown: &str,
+    translated: &translator::TranslatedPlan,
+) -> Result<review_loop::SpecReviewOutcome> {
+    if !cfg.agent_spec_review {
+        return Ok(review_loop::review_spec(markdown, translated));
+    }
+    let Ok(provider) = provider_for(
+        &cfg.agent,
+        cfg.simulate,
+        cfg.agent_command.as_deref(),
+        cfg.agent_spawn_retries,
+    ) else {
+        return Ok(review_loop::review_spec(markdown, translated));
+    };
+    let worktree = run_dir.join("spec-review").join("attempt1");
+    fs::create_dir_all(&worktree)?;
+    Ok(review_loop::review_spec_with_agent(
+        provider.as_ref(),
+        AgentRequest {
+            role: "spec-reviewer".to_string(),
+            task_id: "__spec__".to_string(),
+            attempt: 1,
+            worktree_path: worktree,
+            prompt: packet::build_spec_reviewer_prompt(markdown, translated),
+            env: cfg.agent_env(Vec::new()),
+            timeout: Duration::from_secs(cfg.attempt_timeout_secs),
+        },
+        markdown,
+        translated,
+    ))
+}
+
 fn register_translated_tasks(
     store: &EventStore,
     run_id: &str,
     cfg: &RunConfig,
     translated: &translator::TranslatedPlan,
-    ndjson_log: Option<&Path>,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<()> {
     for t in &translated.tasks {
         append_event(
@@ -197,8 +592,11 @@ fn register_translated_tasks(
                     "task_id": t.id,
                     "objective": t.objective,
                     "acceptance": t.acceptance,
+                    "acceptance_criteria": t.acceptance_criteria,
                     "dependencies": t.dependencies,
-                    "checks": if cfg.trust_plan_checks { t.checks.clone() } else { default_checks() }
+                    "checks": if cfg.trust_plan_checks { t.checks.clone() } else { Vec::new() },
+                    "priority": t.priority,
+                    "deadline_secs": t.deadline_secs
                 }),
                 dedupe_key: Some(format!("task_registered:{}", t.id)),
             },
@@ -208,31 +606,157 @@ fn register_translated_tasks(
     Ok(())
 }
 
+/// Prune `translated` to the `--only-task` ids and everything they transitively depend on.
+/// An empty `only` keeps every task.
+fn retain_only_tasks(
+    mut translated: translator::TranslatedPlan,
+    only: &[String],
+) -> Result<translator::TranslatedPlan> {
+    if only.is_empty() {
+        return Ok(translated);
+    }
+    let dependencies = translated
+        .tasks
+        .iter()
+        .map(|t| (t.id.clone(), t.dependencies.clone()))
+        .collect::<BTreeMap<_, _>>();
+    if let Some(unknown) = only.iter().find(|id| !dependencies.contains_key(*id)) {
+        bail!(
+            "--only-task {unknown} is not a task of the translated plan; its tasks are: {}",
+            dependencies.keys().cloned().collect::<Vec<_>>().join(", ")
+        );
+    }
+    let mut keep = BTreeSet::new();
+    let mut pending = only.to_vec();
+    while let Some(id) = pending.pop() {
+        if keep.insert(id.clone())
+            && let Some(deps) = dependencies.get(&id)
+        {
+            pending.extend(deps.iter().cloned());
+        }
+    }
+    translated.tasks.retain(|t| keep.contains(&t.id));
+    Ok(translated)
+}
+
 pub fn execute_run(cmd: RunCommand) -> Result<()> {
+    start_run(cmd, None)
+}
+
+/// `thence run --plan-stdin`: save the spec read from `spec` into the new run's dir and run it
+/// against `repo_root` as if that file had been passed as the plan.
+pub fn execute_run_from_reader(
+    mut cmd: RunCommand,
+    repo_root: &Path,
+    mut spec: impl io::Read,
+) -> Result<()> {
+    let mut markdown = String::new();
+    spec.read_to_string(&mut markdown)
+        .context("read spec from stdin")?;
+    if markdown.trim().is_empty() {
+        bail!("spec read from stdin is empty");
+    }
+    let repo_root = repo_root
+        .canonicalize()
+        .with_context(|| format!("resolve repo root {}", repo_root.display()))?;
+    let repo_cfg = crate::config::load_repo_config(&repo_root)?;
+    let runs_root = resolve_artifacts_dir(&cmd, repo_cfg.as_ref(), &repo_root)?
+        .unwrap_or_else(|| default_runs_root(&repo_root));
+    let run_id = cmd
+        .run_id
+        .get_or_insert_with(|| Uuid::new_v4().to_string())
+        .clone();
+    let run_dir = run_artifact_dir(&runs_root, &run_id);
+    fs::create_dir_all(&run_dir)
+        .with_context(|| format!("create run dir {}", run_dir.display()))?;
+    cmd.plan_file = run_dir.join(STDIN_SPEC_FILE);
+    fs::write(&cmd.plan_file, markdown)
+        .with_context(|| format!("write spec from stdin to {}", cmd.plan_file.display()))?;
+    start_run(cmd, Some(repo_root))
+}
+
+/// `--artifacts-dir`, else `[run].artifacts_dir` relative to the repo root.
+fn resolve_artifacts_dir(
+    cmd: &RunCommand,
+    repo_cfg: Option<&crate::config::RepoConfig>,
+    repo_root: &Path,
+) -> Result<Option<PathBuf>> {
+    Ok(match cmd.artifacts_dir.as_deref() {
+        Some(dir) => Some(
+            std::path::absolute(dir)
+                .with_context(|| format!("resolve artifacts dir {}", dir.display()))?,
+        ),
+        None => repo_cfg
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.artifacts_dir.as_ref())
+            .map(|dir| repo_root.join(dir)),
+    })
+}
+
+/// Start a new run (or resume one with `--resume`); `repo_root_override` replaces the plan
+/// file's directory as the repository.
+fn start_run(cmd: RunCommand, repo_root_override: Option<PathBuf>) -> Result<()> {
     let db = cmd.state_db.clone().unwrap_or_else(default_state_db);
     let store = EventStore::open(&db)?;
+    let log = cmd.log.clone().map(|path| EventLog {
+        path,
+        format: cmd.log_format,
+    });
 
     if cmd.resume {
         let run_id = resolve_resume_run_id(&store, cmd.run_id.as_deref())?;
-        return continue_run(&store, &run_id, cmd.log.clone());
+        let run_lock = lock_run(&store, &run_id)?;
+        return continue_run(&store, &run_id, log, run_lock);
     }
 
-    let markdown = std::fs::read_to_string(&cmd.plan_file)
-        .with_context(|| format!("read plan file {}", cmd.plan_file.display()))?;
+    let markdown = read_plan_text(&cmd.plan_file, "plan file")?;
     let cli_checks = parse_checks(cmd.checks.as_deref());
-    let repo_root = repo_root_for_plan(&cmd.plan_file)?;
+    let repo_root = match repo_root_override {
+        Some(root) => root,
+        None => repo_root_for_plan(&cmd.plan_file)?,
+    };
     let repo_cfg = crate::config::load_repo_config(&repo_root)?;
 
-    if cmd.agent != "codex" {
-        bail!("only `codex` supported in this version");
-    }
+    ensure_supported_provider(&cmd.agent)?;
+
+    let artifacts_dir = resolve_artifacts_dir(&cmd, repo_cfg.as_ref(), &repo_root)?;
+    let runs_root = artifacts_dir
+        .clone()
+        .unwrap_or_else(|| default_runs_root(&repo_root));
 
     let run_id = cmd.run_id.unwrap_or_else(|| Uuid::new_v4().to_string());
-    let run_dir = run_artifact_dir(&repo_root, &run_id);
+    let run_dir = run_artifact_dir(&runs_root, &run_id);
     std::fs::create_dir_all(&run_dir)?;
     let spl_path = run_dir.join("plan.spl");
     let translated_path = translated_plan_path(&run_dir);
 
+    let config_checks: Vec<String> = repo_cfg
+        .as_ref()
+        .and_then(|cfg| cfg.checks.as_ref())
+        .map(|checks| checks.commands.iter().map(|c| c.command.clone()).collect())
+        .unwrap_or_default();
+    let file_checks = match cmd.checks_from_file.as_deref() {
+        Some(path) if cli_checks.is_empty() => {
+            let path = std::path::absolute(path)
+                .with_context(|| format!("resolve checks file {}", path.display()))?;
+            let commands = checks_config::load_checks_list(&path)?;
+            ensure_checks_configured(&commands)
+                .with_context(|| format!("invalid checks file {}", path.display()))?;
+            Some((path, commands))
+        }
+        _ => None,
+    };
+    let saved_checks = if cli_checks.is_empty()
+        && file_checks.is_none()
+        && config_checks.is_empty()
+        && !cmd.no_checks_file
+        && !cmd.reconfigure_checks
+    {
+        checks_config::load_checks_file(&repo_root)?
+    } else {
+        None
+    };
+
     let plan_sha256 = sha256_hex(&markdown);
     let cfg = RunConfig {
         agent: cmd.agent,
@@ -240,39 +764,187 @@ pub fn execute_run(cmd: RunCommand) -> Result<()> {
         reviewers: cmd.reviewers.max(1),
         checks: if !cli_checks.is_empty() {
             cli_checks.clone()
+        } else if let Some((_, commands)) = file_checks.as_ref() {
+            commands.clone()
+        } else if let Some(saved) = saved_checks.as_ref() {
+            saved.commands.clone()
         } else {
-            repo_cfg
-                .as_ref()
-                .and_then(|cfg| cfg.checks.as_ref())
-                .map(|checks| checks.commands.clone())
-                .unwrap_or_default()
+            config_checks
         },
         checks_from_cli: !cli_checks.is_empty(),
+        checks_from_saved_file: saved_checks.is_some(),
+        checks_source_file: file_checks.map(|(path, _)| path),
+        no_checks_file: cmd.no_checks_file,
         simulate: cmd.simulate,
         allow_partial_completion: cmd.allow_partial_completion,
         trust_plan_checks: cmd.trust_plan_checks,
         interactive: cmd.interactive,
-        max_attempts: 3,
-        check_timeout_secs: 10 * 60,
+        agent_spec_review: cmd.agent_spec_review,
+        no_spec_review: cmd.no_spec_review,
+        propose_checks: cmd.propose_checks || cmd.reconfigure_checks,
+        max_attempts: cmd
+            .max_attempts
+            .or_else(|| {
+                repo_cfg
+                    .as_ref()
+                    .and_then(|cfg| cfg.run.as_ref())
+                    .and_then(|run| run.max_attempts)
+            })
+            .unwrap_or_else(default_max_attempts),
+        max_tasks: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.max_tasks),
+        max_total_attempts: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.max_total_attempts),
+        check_timeout_secs: cmd
+            .check_timeout_secs
+            .unwrap_or_else(default_check_timeout_secs),
+        check_timeout_overrides: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.checks.as_ref())
+            .map(|checks| {
+                checks
+                    .commands
+                    .iter()
+                    .filter_map(|c| c.timeout_secs.map(|secs| (c.command.clone(), secs)))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        check_workdirs: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.checks.as_ref())
+            .map(|checks| {
+                checks
+                    .commands
+                    .iter()
+                    .filter_map(|c| c.workdir.clone().map(|dir| (c.command.clone(), dir)))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        check_stages: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.checks.as_ref())
+            .map(|checks| {
+                checks
+                    .commands
+                    .iter()
+                    .filter(|c| c.stage != CheckStage::default())
+                    .map(|c| (c.command.clone(), c.stage))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        check_concurrency: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.checks.as_ref())
+            .and_then(|checks| checks.concurrency)
+            .unwrap_or_else(default_check_concurrency),
+        check_retries: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.checks.as_ref())
+            .and_then(|checks| checks.retries)
+            .unwrap_or(0),
+        check_output_tail_bytes: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.checks.as_ref())
+            .and_then(|checks| checks.output_tail_bytes)
+            .unwrap_or_else(default_check_output_tail_bytes),
+        task_checks_mode: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.checks.as_ref())
+            .map(|checks| checks.task_checks)
+            .unwrap_or_default(),
         attempt_timeout_secs: cmd
             .attempt_timeout_secs
             .unwrap_or_else(default_attempt_timeout_secs),
+        reviewer_timeout_secs: cmd.reviewer_timeout_secs,
+        lease_stale_secs: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.lease_stale_secs)
+            .unwrap_or_else(default_lease_stale_secs),
+        lease_tick_secs: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.lease_tick_secs)
+            .unwrap_or_else(default_lease_tick_secs),
+        task_deadline_secs: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.task_deadline_secs),
+        attempt_backoff_secs: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.attempt_backoff_secs)
+            .unwrap_or(0),
+        poll_interval_ms: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.poll_interval_ms)
+            .unwrap_or_else(default_poll_interval_ms),
+        spec_drift: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.spec_drift)
+            .unwrap_or_default(),
+        config_drift: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.config_drift)
+            .unwrap_or_default(),
         reviewer_prompt_override: repo_cfg
             .as_ref()
             .and_then(|cfg| cfg.prompts.as_ref())
             .and_then(|prompts| prompts.reviewer.clone()),
+        implementer_prompt_override: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.prompts.as_ref())
+            .and_then(|prompts| prompts.implementer.clone()),
         agent_command: repo_cfg
             .as_ref()
             .and_then(|cfg| cfg.agent.as_ref())
             .and_then(|agent| agent.command.clone()),
+        agent_spawn_retries: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.agent.as_ref())
+            .and_then(|agent| agent.spawn_retries)
+            .unwrap_or_else(default_agent_spawn_retries),
+        agent_env: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.agent.as_ref())
+            .map(|agent| agent.env.clone())
+            .unwrap_or_default(),
         worktree_provision_files: repo_cfg
             .as_ref()
             .and_then(|cfg| cfg.worktree.as_ref())
             .and_then(|worktree| worktree.provision.as_ref())
             .map(|provision| provision.files.clone())
             .unwrap_or_default(),
+        worktree_cleanup: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.worktree.as_ref())
+            .map(|worktree| worktree.cleanup)
+            .unwrap_or_default(),
+        worktree_keep_on_fail: cmd.worktree_keep_on_fail,
+        only_tasks: cmd.only_task.clone(),
+        vcs: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.vcs.clone())
+            .unwrap_or_default(),
+        artifacts_dir,
+        policy_extra_rules_file: repo_cfg
+            .as_ref()
+            .and_then(|cfg| cfg.policy.as_ref())
+            .and_then(|policy| policy.extra_rules_file.as_ref())
+            .map(|file| repo_root.join(file)),
+        repo_root: Some(repo_root.clone()),
     };
-    ensure_checks_configured(&cfg.checks)?;
+    cfg.extra_policy_rules()?;
+    if !(cfg.propose_checks && cfg.checks.is_empty()) {
+        ensure_checks_configured(&cfg.checks)?;
+    }
 
     store.create_run(&RunRow {
         id: run_id.clone(),
@@ -284,6 +956,7 @@ pub fn execute_run(cmd: RunCommand) -> Result<()> {
         config_json: serde_json::to_value(&cfg)?,
     })?;
 
+    let config_structure = config_structure(repo_cfg.as_ref());
     append_event(
         &store,
         &run_id,
@@ -293,35 +966,22 @@ pub fn execute_run(cmd: RunCommand) -> Result<()> {
                 "plan_file": cmd.plan_file,
                 "agent": cfg.agent,
                 "workers": cfg.workers,
-                "reviewers": cfg.reviewers
+                "reviewers": cfg.reviewers,
+                "config_sha256": sha256_hex(&config_structure.to_string()),
+                "config_structure": config_structure
             }),
         ),
-        cmd.log.as_deref(),
+        log.as_ref(),
     )?;
 
-    let (translated, translation_res) = match translate_spec_with_agent(
-        &cfg,
-        &repo_root,
-        &cmd.plan_file,
-        &markdown,
-        &run_dir,
-    ) {
-        Ok(result) => result,
-        Err(e) => {
-            let qid = "spec-q-translate";
-            append_event(
-                &store,
-                &run_id,
-                &NewEvent::simple(
-                    "spec_question_opened",
-                    json!({"question_id": qid, "question": format!("Plan translation failed: {e}")}),
-                ),
-                cmd.log.as_deref(),
-            )?;
-            pause_for_question(&store, &run_id, qid, cmd.log.as_deref())?;
-            bail!("run paused due to translation failure")
-        }
-    };
+    let (translated, translation_res) =
+        match translate_spec(&cfg, &repo_root, &cmd.plan_file, &markdown, &run_dir) {
+            Ok(result) => result,
+            Err(e) => {
+                open_translation_question(&store, &run_id, &run_dir, &e, log.as_ref())?;
+                bail!("run paused due to translation failure")
+            }
+        };
     std::fs::write(&spl_path, &translated.spl)
         .with_context(|| format!("write translated SPL {}", spl_path.display()))?;
     translator::save_translated_plan(&translated_path, &translated)?;
@@ -340,16 +1000,16 @@ pub fn execute_run(cmd: RunCommand) -> Result<()> {
                 "translated_plan_path": translated_path,
                 "frozen_spec_path": frozen_spec,
                 "task_count": translated.tasks.len(),
-                "source": "agent",
-                "translator_stdout_path": translation_res.stdout_path,
-                "translator_stderr_path": translation_res.stderr_path
+                "source": if translation_res.is_some() { "agent" } else { "yaml" },
+                "translator_stdout_path": translation_res.as_ref().map(|res| &res.stdout_path),
+                "translator_stderr_path": translation_res.as_ref().map(|res| &res.stderr_path)
             }),
         ),
-        cmd.log.as_deref(),
+        log.as_ref(),
     )?;
 
-    if let Err(e) =
-        validate::validate_spl(&translated.spl).and_then(|_| sanity::run_sanity_checks(&translated))
+    if let Err(e) = validate::validate_spl(&translated.spl)
+        .and_then(|_| sanity::run_sanity_checks(&translated, cfg.max_tasks))
     {
         let qid = "spec-q-validate";
         append_event(
@@ -359,9 +1019,9 @@ pub fn execute_run(cmd: RunCommand) -> Result<()> {
                 "spec_question_opened",
                 json!({"question_id": qid, "question": format!("Plan generation failed: {e}")}),
             ),
-            cmd.log.as_deref(),
+            log.as_ref(),
         )?;
-        pause_for_question(&store, &run_id, qid, cmd.log.as_deref())?;
+        pause_for_question(&store, &run_id, qid, log.as_ref())?;
         bail!("run paused due to invalid translated plan")
     }
 
@@ -369,54 +1029,350 @@ pub fn execute_run(cmd: RunCommand) -> Result<()> {
         &store,
         &run_id,
         &NewEvent::simple("plan_validated", json!({"ok": true})),
-        cmd.log.as_deref(),
+        log.as_ref(),
     )?;
+    let translated = retain_only_tasks(translated, &cfg.only_tasks)?;
 
-    match review_loop::review_spec(&markdown, &translated) {
-        review_loop::SpecReviewOutcome::Approved => {
-            append_event(
-                &store,
-                &run_id,
-                &NewEvent::simple("spec_approved", json!({"approved": true})),
-                cmd.log.as_deref(),
-            )?;
-        }
-        review_loop::SpecReviewOutcome::Question {
-            question_id,
-            question,
-        } => {
-            append_event(
-                &store,
-                &run_id,
-                &NewEvent::simple(
-                    "spec_question_opened",
-                    json!({"question_id": question_id, "question": question}),
-                ),
-                cmd.log.as_deref(),
-            )?;
-            pause_for_question(&store, &run_id, &question_id, cmd.log.as_deref())?;
-            bail!("run paused awaiting spec clarification")
+    if cfg.no_spec_review {
+        append_event(
+            &store,
+            &run_id,
+            &NewEvent::simple(
+                "spec_approved",
+                json!({"approved": true, "source": SPEC_REVIEW_SKIPPED_SOURCE}),
+            ),
+            log.as_ref(),
+        )?;
+    } else {
+        match review_spec_for_run(&cfg, &run_dir, &markdown, &translated)? {
+            review_loop::SpecReviewOutcome::Approved => {
+                append_event(
+                    &store,
+                    &run_id,
+                    &NewEvent::simple("spec_approved", json!({"approved": true})),
+                    log.as_ref(),
+                )?;
+            }
+            review_loop::SpecReviewOutcome::Question {
+                question_id,
+                question,
+            } => {
+                append_event(
+                    &store,
+                    &run_id,
+                    &NewEvent::simple(
+                        "spec_question_opened",
+                        json!({"question_id": question_id, "question": question}),
+                    ),
+                    log.as_ref(),
+                )?;
+                pause_for_question(&store, &run_id, &question_id, log.as_ref())?;
+                bail!("run paused awaiting spec clarification")
+            }
         }
     }
 
-    resolve_checks_configuration(&store, &run_id, &cfg, cmd.log.as_deref())?;
+    if cmd.dry_run {
+        print_task_graph(&translated);
+        println!("Dry run {run_id}: plan approved; no tasks were run.");
+        println!("Execute it with: thence resume --run {run_id}");
+        return Ok(());
+    }
+
+    if cfg.propose_checks && cfg.checks.is_empty() {
+        propose_checks_for_run(
+            &store,
+            &run_id,
+            &cfg,
+            &repo_root,
+            &markdown,
+            &translated,
+            log.as_ref(),
+        )?;
+        bail!("run paused awaiting checks approval")
+    }
+    resolve_checks_configuration(&store, &run_id, &cfg, &repo_root, log.as_ref())?;
 
-    register_translated_tasks(&store, &run_id, &cfg, &translated, cmd.log.as_deref())?;
+    register_translated_tasks(&store, &run_id, &cfg, &translated, log.as_ref())?;
 
-    continue_run(&store, &run_id, cmd.log)
+    let run_lock = lock_run(&store, &run_id)?;
+    continue_run(&store, &run_id, log, run_lock)
 }
 
-pub fn list_questions(run_id: &str, state_db: Option<PathBuf>) -> Result<()> {
-    let store = EventStore::open(&state_db.unwrap_or_else(default_state_db))?;
-    let unresolved = store.unresolved_questions(run_id)?;
-    if unresolved.is_empty() {
-        println!("No open questions for run {run_id}");
-        return Ok(());
-    }
-    for (id, q) in unresolved {
-        println!("{id}: {q}");
-    }
-    Ok(())
+/// Translate and lint a plan offline with the same gates a run applies, without an agent.
+pub fn validate_plan(plan_file: &Path) -> Result<translator::TranslatedPlan> {
+    let raw = read_plan_text(plan_file, "plan file")?;
+    let translated = if yaml::is_yaml_plan(plan_file) {
+        yaml::parse_yaml_plan(&raw)
+            .with_context(|| format!("parse YAML plan {}", plan_file.display()))?
+    } else {
+        translator::translate_markdown_to_spl(&raw, &default_checks())?
+    };
+    validate::validate_spl(&translated.spl)?;
+    let max_tasks = crate::config::load_repo_config(&repo_root_for_plan(plan_file)?)?
+        .and_then(|cfg| cfg.run)
+        .and_then(|run| run.max_tasks);
+    sanity::run_sanity_checks(&translated, max_tasks)?;
+    Ok(translated)
+}
+
+pub fn print_plan_validation(plan_file: &Path) -> Result<()> {
+    let translated = validate_plan(plan_file)?;
+    println!("OK: {} task(s)", translated.tasks.len());
+    for task in &translated.tasks {
+        if task.dependencies.is_empty() {
+            println!("  - {}", task.id);
+        } else {
+            println!("  - {} (after {})", task.id, task.dependencies.join(", "));
+        }
+    }
+    Ok(())
+}
+
+fn print_task_graph(translated: &translator::TranslatedPlan) {
+    println!("{} task(s):", translated.tasks.len());
+    for task in &translated.tasks {
+        println!("  - {}: {}", task.id, task.objective);
+        if !task.dependencies.is_empty() {
+            println!("      after: {}", task.dependencies.join(", "));
+        }
+        if !task.checks.is_empty() {
+            println!("      checks: {}", task.checks.join("; "));
+        }
+    }
+}
+
+pub fn list_runs(status: Option<&str>, state_db: Option<PathBuf>) -> Result<()> {
+    let db = state_db.unwrap_or_else(default_state_db);
+    // A state DB that was never created simply has no runs yet.
+    let runs = if db.exists() {
+        EventStore::open_read_only(&db)?.list_runs(status)?
+    } else {
+        Vec::new()
+    };
+    if runs.is_empty() {
+        match status {
+            Some(status) => println!("No {status} runs found"),
+            None => println!("No runs found"),
+        }
+        return Ok(());
+    }
+    println!(
+        "{:<36}  {:<9}  {:<32}  PLAN_PATH",
+        "RUN_ID", "STATUS", "CREATED_AT"
+    );
+    for run in runs {
+        println!(
+            "{:<36}  {:<9}  {:<32}  {}",
+            run.id, run.status, run.created_at, run.plan_path
+        );
+    }
+    Ok(())
+}
+
+pub fn list_resumable_runs(state_db: Option<PathBuf>) -> Result<()> {
+    let db = state_db.unwrap_or_else(default_state_db);
+    // A state DB that was never created simply has no runs yet.
+    if !db.exists() {
+        println!("No resumable runs found");
+        return Ok(());
+    }
+    let store = EventStore::open_read_only(&db)?;
+    write_resumable_runs(&store, &mut io::stdout().lock())
+}
+
+/// One line per `running` run with the phase, open question count, and in-flight attempt, so a
+/// user facing "multiple resumable runs" can tell them apart.
+pub fn write_resumable_runs(store: &EventStore, out: &mut impl Write) -> Result<()> {
+    let run_ids = store.list_resumable_run_ids()?;
+    if run_ids.is_empty() {
+        writeln!(out, "No resumable runs found")?;
+        return Ok(());
+    }
+    writeln!(
+        out,
+        "{:<36}  {:<19}  {:<9}  CURRENT",
+        "RUN_ID", "PHASE", "QUESTIONS"
+    )?;
+    for run_id in run_ids {
+        let state = RunProjection::replay(&store.list_events(&run_id)?);
+        let current = state
+            .tasks
+            .values()
+            .find(|t| t.claimed)
+            .map(|t| format!("task={} attempt={}", t.id, t.latest_attempt))
+            .unwrap_or_else(|| "-".to_string());
+        writeln!(
+            out,
+            "{:<36}  {:<19}  {:<9}  {}",
+            run_id,
+            inspect_phase(&state),
+            state.open_questions.len(),
+            current
+        )?;
+    }
+    Ok(())
+}
+
+pub fn export_run(
+    run_id: &str,
+    format: &str,
+    from_seq: Option<i64>,
+    to_seq: Option<i64>,
+    state_db: Option<PathBuf>,
+) -> Result<()> {
+    if let (Some(from), Some(to)) = (from_seq, to_seq)
+        && from > to
+    {
+        bail!("--from-seq {from} is greater than --to-seq {to}")
+    }
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    let events = store.list_events_in_range(run_id, from_seq, to_seq)?;
+    write_events_export(&events, format, &mut io::stdout().lock())
+}
+
+/// Serialize full event rows as a pretty JSON array (`json`) or one object per line (`ndjson`).
+pub fn write_events_export(events: &[EventRow], format: &str, out: &mut impl Write) -> Result<()> {
+    match format {
+        "json" => {
+            serde_json::to_writer_pretty(&mut *out, events)?;
+            writeln!(out)?;
+        }
+        "ndjson" => {
+            for ev in events {
+                ndjson::write_full_event(out, ev)?;
+            }
+        }
+        other => bail!("unsupported export format `{other}`; expected `json` or `ndjson`"),
+    }
+    Ok(())
+}
+
+pub fn tail_run(
+    run_id: &str,
+    follow: bool,
+    interval_ms: u64,
+    state_db: Option<PathBuf>,
+) -> Result<()> {
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    tail_events(
+        &store,
+        run_id,
+        follow,
+        Duration::from_millis(interval_ms),
+        &mut io::stdout().lock(),
+    )
+}
+
+/// Print one line per event; with `follow`, keep polling for new rows until a terminal event.
+pub fn tail_events(
+    store: &EventStore,
+    run_id: &str,
+    follow: bool,
+    interval: Duration,
+    out: &mut impl Write,
+) -> Result<()> {
+    let mut last_seq = 0;
+    loop {
+        let mut terminal = false;
+        for ev in store.list_events_since(run_id, last_seq)? {
+            writeln!(
+                out,
+                "{}  {:<28}  task={}  attempt={}",
+                ev.ts,
+                ev.event_type,
+                ev.task_id.as_deref().unwrap_or("-"),
+                ev.attempt
+                    .map_or_else(|| "-".to_string(), |a| a.to_string())
+            )?;
+            terminal |= transitions::TERMINAL_EVENTS.contains(&ev.event_type.as_str());
+            last_seq = ev.seq;
+        }
+        out.flush()?;
+        if !follow || terminal {
+            return Ok(());
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Load the prompt capsule written for one role of a task attempt.
+pub fn read_capsule(
+    run_id: &str,
+    task_id: &str,
+    attempt: i64,
+    role: &str,
+    state_db: Option<PathBuf>,
+) -> Result<serde_json::Value> {
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    let run = store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    let path = capsule_path(
+        &run_artifact_dir(&runs_root_for_run(&run)?, run_id),
+        task_id,
+        attempt,
+        role,
+    );
+    if !path.exists() {
+        bail!(
+            "no {role} capsule for task {task_id} attempt {attempt} in run {run_id} (expected {})",
+            path.display()
+        );
+    }
+    let raw =
+        fs::read_to_string(&path).with_context(|| format!("read capsule {}", path.display()))?;
+    serde_json::from_str(&raw).with_context(|| format!("parse capsule {}", path.display()))
+}
+
+pub fn show_capsule(
+    run_id: &str,
+    task_id: &str,
+    attempt: i64,
+    role: &str,
+    state_db: Option<PathBuf>,
+) -> Result<()> {
+    let capsule = read_capsule(run_id, task_id, attempt, role, state_db)?;
+    println!("{}", serde_json::to_string_pretty(&capsule)?);
+    Ok(())
+}
+
+pub fn list_questions(run_id: &str, json: bool, state_db: Option<PathBuf>) -> Result<()> {
+    if json {
+        let questions = questions_json(run_id, state_db)?;
+        println!("{}", serde_json::to_string_pretty(&questions)?);
+        return Ok(());
+    }
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    let unresolved = store.unresolved_questions(run_id)?;
+    if unresolved.is_empty() {
+        println!("No open questions for run {run_id}");
+        return Ok(());
+    }
+    for (id, q) in unresolved {
+        println!("{id}: {q}");
+    }
+    Ok(())
+}
+
+/// Machine-readable form of `thence questions`: the open questions in the order they opened.
+pub fn questions_json(run_id: &str, state_db: Option<PathBuf>) -> Result<serde_json::Value> {
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    let questions = store
+        .unresolved_questions(run_id)?
+        .into_iter()
+        .map(|(question_id, question)| OpenQuestion {
+            question_id,
+            question,
+        })
+        .collect::<Vec<_>>();
+    Ok(serde_json::to_value(questions)?)
 }
 
 pub fn answer_question(
@@ -426,14 +1382,22 @@ pub fn answer_question(
     state_db: Option<PathBuf>,
 ) -> Result<()> {
     let store = EventStore::open(&state_db.unwrap_or_else(default_state_db))?;
-    let unresolved = store.unresolved_questions(run_id)?;
-    if !unresolved.iter().any(|(id, _)| id == question_id) {
-        bail!("question {question_id} is not currently open for run {run_id}")
-    }
+    ensure_question_open(&store, run_id, question_id)?;
+    // Held until the answer and `run_resumed` are recorded, so a run another process is
+    // driving is left untouched.
+    let _run_lock = lock_run(&store, run_id)?;
 
-    let _run = store
+    let run = store
         .get_run(run_id)?
         .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    let approved_checks = if question_id == CHECKS_QUESTION_ID {
+        Some(checks_from_answer(&store, run_id, text)?)
+    } else {
+        None
+    };
+    if question_id.starts_with(BUDGET_QUESTION_PREFIX) {
+        raise_attempt_budget(&store, run_id, text)?;
+    }
 
     append_event(
         &store,
@@ -455,6 +1419,21 @@ pub fn answer_question(
         None,
     )?;
 
+    if let Some((commands, source)) = approved_checks {
+        append_event(
+            &store,
+            run_id,
+            &NewEvent::simple(
+                "checks_approved",
+                json!({"commands": commands, "source": source}),
+            ),
+            None,
+        )?;
+        let cfg: RunConfig = serde_json::from_value(run.config_json.clone())?;
+        let repo_root = cfg.repo_root(Path::new(&run.plan_path))?;
+        remember_approved_checks(&cfg, &repo_root, &commands, source)?;
+    }
+
     let is_spec_review_question = is_spec_review_question_id(question_id);
     if is_spec_review_question {
         let events_after = store.list_events(run_id)?;
@@ -499,32 +1478,318 @@ pub fn answer_question(
     Ok(())
 }
 
+/// `thence answer --reject`: record a standard rejection and leave the question open, so the
+/// run stays paused until it is answered again.
+pub fn reject_question(run_id: &str, question_id: &str, state_db: Option<PathBuf>) -> Result<()> {
+    let store = EventStore::open(&state_db.unwrap_or_else(default_state_db))?;
+    let _run_lock = lock_run(&store, run_id)?;
+    ensure_question_open(&store, run_id, question_id)?;
+    append_event(
+        &store,
+        run_id,
+        &NewEvent::simple(
+            "human_input_provided",
+            json!({"question_id": question_id, "text": REJECT_NOTE, "rejected": true}),
+        ),
+        None,
+    )?;
+    println!(
+        "Recorded rejection for {question_id}; it stays open. Answer it with --text or --approve, then resume."
+    );
+    Ok(())
+}
+
+/// Apply a `budget-q-attempts-*` answer: the new `max_total_attempts`, which must leave room for
+/// at least one more claim.
+fn raise_attempt_budget(store: &EventStore, run_id: &str, text: &str) -> Result<()> {
+    let run = store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    let claimed = store.list_events_of_type(run_id, "task_claimed")?.len() as i64;
+    let cap = text
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .filter(|cap| *cap > claimed)
+        .ok_or_else(|| {
+            anyhow!(
+                "answer with a new total attempt budget greater than the {claimed} attempts already claimed"
+            )
+        })?;
+    let mut cfg: RunConfig = serde_json::from_value(run.config_json.clone())?;
+    cfg.max_total_attempts = Some(cap);
+    store.update_run_config(run_id, &serde_json::to_value(cfg)?)
+}
+
+fn ensure_question_open(store: &EventStore, run_id: &str, question_id: &str) -> Result<()> {
+    let unresolved = store.unresolved_questions(run_id)?;
+    if !unresolved.iter().any(|(id, _)| id == question_id) {
+        bail!("question {question_id} is not currently open for run {run_id}")
+    }
+    Ok(())
+}
+
 pub fn resume_run(run_id: &str, state_db: Option<PathBuf>) -> Result<()> {
     let store = EventStore::open(&state_db.unwrap_or_else(default_state_db))?;
+    let run_lock = lock_run(&store, run_id)?;
     append_event(
         &store,
         run_id,
         &NewEvent::simple("run_resumed", json!({"reason": "manual_resume"})),
         None,
     )?;
-    continue_run(&store, run_id, None)
+    continue_run(&store, run_id, None, run_lock)
 }
 
-pub fn inspect_run(run_id: &str, state_db: Option<PathBuf>) -> Result<()> {
+pub fn cancel_run(run_id: &str, state_db: Option<PathBuf>) -> Result<()> {
     let store = EventStore::open(&state_db.unwrap_or_else(default_state_db))?;
+    let _run = store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    let state = RunProjection::replay(&store.list_events(run_id)?);
+    if let Some(terminal) = state.terminal {
+        bail!("run {run_id} is already terminal ({terminal}); nothing to cancel")
+    }
+
+    append_event(
+        &store,
+        run_id,
+        &NewEvent::simple("run_cancelled", json!({"reason": "user_cancelled"})),
+        None,
+    )?;
+    store.update_run_status(run_id, "cancelled")?;
+
+    println!("Cancelled run {run_id}");
+    Ok(())
+}
+
+/// Drop a run's rows from the state DB. Artifacts under `.thence/runs` are left in place.
+pub fn delete_run(run_id: &str, force: bool, state_db: Option<PathBuf>) -> Result<()> {
+    let store = EventStore::open(&state_db.unwrap_or_else(default_state_db))?;
+    let _run = store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    let state = RunProjection::replay(&store.list_events(run_id)?);
+    if state.terminal.is_none() && !force {
+        bail!(
+            "run {run_id} is not terminal; cancel it first (thence cancel --run {run_id}) or pass --force"
+        )
+    }
+
+    let removed = store.delete_run(run_id)?;
+    println!("Deleted run {run_id} ({removed} events)");
+    Ok(())
+}
+
+/// Typed state of a run for callers embedding thence as a library: the data `thence inspect`
+/// prints, without going through the CLI.
+#[derive(Debug, Clone, Serialize)]
+pub struct RunStatusReport {
+    pub run_id: String,
+    pub status: String,
+    pub plan_path: String,
+    pub spl_path: String,
+    pub artifacts_dir: PathBuf,
+    /// `terminal`, `paused_for_question`, `spec_gate`, `checks_gate`, `implementation_loop`,
+    /// or `scheduler_idle`.
+    pub phase: String,
+    pub state: RunProjection,
+    /// Sorted by question id.
+    pub open_questions: Vec<OpenQuestion>,
+    pub current: Option<CurrentAttempt>,
+    /// Most recent review rejection per task, sorted by task id.
+    pub latest_findings: Vec<LatestFinding>,
+    /// Failed check commands of each task's latest attempt that has any, sorted by task id.
+    pub failed_checks: Vec<FailedChecks>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct OpenQuestion {
+    pub question_id: String,
+    pub question: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct CurrentAttempt {
+    pub task_id: String,
+    pub attempt: i64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct LatestFinding {
+    pub task_id: String,
+    pub attempt: i64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FailedChecks {
+    pub task_id: String,
+    pub attempt: i64,
+    pub commands: Vec<String>,
+}
+
+pub fn status_report(run_id: &str, state_db: Option<PathBuf>) -> Result<RunStatusReport> {
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
     let run = store
         .get_run(run_id)?
         .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
     let events = store.list_events(run_id)?;
-    let state = RunProjection::replay(&events);
-    let repo_root = repo_root_for_plan(Path::new(&run.plan_path))?;
-    let run_dir = run_artifact_dir(&repo_root, run_id);
-
-    println!("run_id: {}", run.id);
-    println!("status: {}", run.status);
-    println!("plan_path: {}", run.plan_path);
-    println!("spl_path: {}", run.spl_plan_path);
-    println!("artifacts_dir: {}", run_dir.display());
+    status_report_from_events(run, &events)
+}
+
+/// The status report as it stood at `to_seq`, built by replaying only events with
+/// `seq <= to_seq`.
+pub fn replay_status_report(
+    run_id: &str,
+    to_seq: i64,
+    state_db: Option<PathBuf>,
+) -> Result<RunStatusReport> {
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    let run = store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    let events = store.list_events_in_range(run_id, None, Some(to_seq))?;
+    let mut report = status_report_from_events(run, &events)?;
+    if report.state.terminal.is_none() {
+        report.status = "running".to_string();
+    }
+    Ok(report)
+}
+
+fn status_report_from_events(run: RunRow, events: &[EventRow]) -> Result<RunStatusReport> {
+    let state = RunProjection::replay(events);
+    let runs_root = runs_root_for_run(&run)?;
+
+    let mut open_questions = state
+        .open_questions
+        .iter()
+        .map(|(id, q)| OpenQuestion {
+            question_id: id.clone(),
+            question: q.clone(),
+        })
+        .collect::<Vec<_>>();
+    open_questions.sort_by(|a, b| a.question_id.cmp(&b.question_id));
+    let current = state
+        .tasks
+        .values()
+        .find(|t| t.claimed)
+        .map(|t| CurrentAttempt {
+            task_id: t.id.clone(),
+            attempt: t.latest_attempt,
+        });
+    let latest_findings = latest_findings(events)
+        .into_iter()
+        .map(|(task_id, (attempt, reason))| LatestFinding {
+            task_id,
+            attempt,
+            reason,
+        })
+        .collect();
+    let failed_checks = state
+        .tasks
+        .values()
+        .filter_map(|t| {
+            let (attempt, commands) = t.failed_checks.last_key_value()?;
+            Some(FailedChecks {
+                task_id: t.id.clone(),
+                attempt: *attempt,
+                commands: commands.clone(),
+            })
+        })
+        .collect();
+
+    Ok(RunStatusReport {
+        artifacts_dir: run_artifact_dir(&runs_root, &run.id),
+        run_id: run.id,
+        status: run.status,
+        plan_path: run.plan_path,
+        spl_path: run.spl_plan_path,
+        phase: inspect_phase(&state).to_string(),
+        state,
+        open_questions,
+        current,
+        latest_findings,
+        failed_checks,
+    })
+}
+
+pub fn inspect_run(
+    run_id: &str,
+    json: bool,
+    timeline: bool,
+    state_db: Option<PathBuf>,
+) -> Result<()> {
+    if timeline {
+        for line in run_timeline(run_id, state_db)? {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+    if json {
+        let state = inspect_run_json(run_id, state_db)?;
+        println!("{}", serde_json::to_string_pretty(&state)?);
+        return Ok(());
+    }
+    let report = status_report(run_id, state_db.clone())?;
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    let events = store.list_events(run_id)?;
+    let run_dir = &report.artifacts_dir;
+    print_status_report(&report);
+
+    let mut seen_attempts = std::collections::HashSet::<(String, i64)>::new();
+    let mut attempts = Vec::<(String, i64)>::new();
+    for ev in events.iter().rev() {
+        if let (Some(task_id), Some(attempt)) = (ev.task_id.as_ref(), ev.attempt) {
+            let key = (task_id.clone(), attempt);
+            if seen_attempts.insert(key.clone()) {
+                attempts.push(key);
+            }
+        }
+        if attempts.len() >= 8 {
+            break;
+        }
+    }
+
+    let translator_artifacts = discover_translator_artifacts(run_dir)?;
+    if !attempts.is_empty() || !translator_artifacts.is_empty() {
+        println!("attempt_artifacts:");
+        if !translator_artifacts.is_empty() {
+            println!("  - task=plan-translator attempt=1");
+            for path in translator_artifacts {
+                println!("      plan-translator: {}", path.display());
+            }
+        }
+        for (task_id, attempt) in attempts {
+            println!("  - task={} attempt={}", task_id, attempt);
+            for role in ["implementer", "reviewer"] {
+                let artifacts = discover_attempt_artifacts(run_dir, &task_id, attempt, role)?;
+                for path in artifacts {
+                    println!("      {}: {}", role, path.display());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `thence replay`: print the `inspect` fields as they stood at `to_seq`.
+pub fn replay_run(run_id: &str, to_seq: i64, state_db: Option<PathBuf>) -> Result<()> {
+    let report = replay_status_report(run_id, to_seq, state_db)?;
+    println!("as_of_seq: {to_seq}");
+    print_status_report(&report);
+    Ok(())
+}
+
+fn print_status_report(report: &RunStatusReport) {
+    let state = &report.state;
+
+    println!("run_id: {}", report.run_id);
+    println!("status: {}", report.status);
+    println!("plan_path: {}", report.plan_path);
+    println!("spl_path: {}", report.spl_path);
+    println!("artifacts_dir: {}", report.artifacts_dir.display());
     println!(
         "state: spec_approved={} checks_approved={} paused={} terminal={}",
         state.spec_approved,
@@ -532,41 +1797,95 @@ pub fn inspect_run(run_id: &str, state_db: Option<PathBuf>) -> Result<()> {
         state.paused,
         state.terminal.as_deref().unwrap_or("none")
     );
-    let phase = if state.terminal.is_some() {
-        "terminal"
-    } else if !state.open_questions.is_empty() {
-        "paused_for_question"
-    } else if !state.spec_approved {
-        "spec_gate"
-    } else if !state.checks_approved {
-        "checks_gate"
-    } else if state.tasks.values().any(|t| t.claimed) {
-        "implementation_loop"
-    } else {
-        "scheduler_idle"
-    };
-    println!("phase: {phase}");
+    println!("phase: {}", report.phase);
 
-    if let Some(task) = state.tasks.values().find(|t| t.claimed) {
-        println!("current: task={} attempt={}", task.id, task.latest_attempt);
+    if let Some(current) = &report.current {
+        println!(
+            "current: task={} attempt={}",
+            current.task_id, current.attempt
+        );
     }
 
-    if !state.open_questions.is_empty() {
+    if !report.open_questions.is_empty() {
         println!("open_questions:");
-        for (id, q) in &state.open_questions {
-            println!("  - {}: {}", id, q);
+        for q in &report.open_questions {
+            println!("  - {}: {}", q.question_id, q.question);
         }
     }
 
-    let mut latest_findings = BTreeMap::<String, (i64, String)>::new();
-    for ev in events.iter().rev() {
-        if ev.event_type != "review_found_issues" {
-            continue;
-        }
+    if !report.latest_findings.is_empty() {
+        println!("latest_findings:");
+        for finding in &report.latest_findings {
+            println!(
+                "  - task={} attempt={} reason={}",
+                finding.task_id, finding.attempt, finding.reason
+            );
+        }
+    }
+
+    if !report.failed_checks.is_empty() {
+        println!("failed_checks:");
+        for failed in &report.failed_checks {
+            println!(
+                "  - task={} attempt={} commands={}",
+                failed.task_id,
+                failed.attempt,
+                failed.commands.join("; ")
+            );
+        }
+    }
+}
+
+/// Machine-readable form of `thence inspect`.
+pub fn inspect_run_json(run_id: &str, state_db: Option<PathBuf>) -> Result<serde_json::Value> {
+    let report = status_report(run_id, state_db)?;
+    Ok(json!({
+        "run_id": report.run_id,
+        "status": report.status,
+        "plan_path": report.plan_path,
+        "spl_path": report.spl_path,
+        "translator_artifacts": discover_translator_artifacts(&report.artifacts_dir)?,
+        "artifacts_dir": report.artifacts_dir,
+        "phase": report.phase,
+        "spec_approved": report.state.spec_approved,
+        "checks_approved": report.state.checks_approved,
+        "paused": report.state.paused,
+        "terminal": report.state.terminal,
+        "open_questions": report.open_questions,
+        "current": report.current,
+        "latest_findings": report.latest_findings,
+        "failed_checks": report.failed_checks
+    }))
+}
+
+fn inspect_phase(state: &RunProjection) -> &'static str {
+    if state.terminal.is_some() {
+        "terminal"
+    } else if !state.open_questions.is_empty() {
+        "paused_for_question"
+    } else if !state.spec_approved {
+        "spec_gate"
+    } else if !state.checks_approved {
+        "checks_gate"
+    } else if state.tasks.values().any(|t| t.claimed) {
+        "implementation_loop"
+    } else {
+        "scheduler_idle"
+    }
+}
+
+/// Most recent review rejection per task, as `(attempt, reason)`.
+fn latest_findings(events: &[EventRow]) -> BTreeMap<String, (i64, String)> {
+    let mut latest = BTreeMap::<String, (i64, String)>::new();
+    for ev in events
+        .iter()
+        .rev()
+        .filter(|ev| ev.event_type == "review_found_issues")
+    {
         let Some(task_id) = ev.task_id.as_ref() else {
             continue;
         };
-        if latest_findings.contains_key(task_id) {
+        if latest.contains_key(task_id) {
             continue;
         }
         let reason = ev
@@ -575,55 +1894,47 @@ pub fn inspect_run(run_id: &str, state_db: Option<PathBuf>) -> Result<()> {
             .and_then(|v| v.as_str())
             .unwrap_or("review findings")
             .to_string();
-        latest_findings.insert(task_id.clone(), (ev.attempt.unwrap_or(0), reason));
-    }
-    if !latest_findings.is_empty() {
-        println!("latest_findings:");
-        for (task, (attempt, reason)) in latest_findings {
-            println!("  - task={} attempt={} reason={}", task, attempt, reason);
-        }
-    }
-
-    let mut seen_attempts = std::collections::HashSet::<(String, i64)>::new();
-    let mut attempts = Vec::<(String, i64)>::new();
-    for ev in events.iter().rev() {
-        if let (Some(task_id), Some(attempt)) = (ev.task_id.as_ref(), ev.attempt) {
-            let key = (task_id.clone(), attempt);
-            if seen_attempts.insert(key.clone()) {
-                attempts.push(key);
-            }
-        }
-        if attempts.len() >= 8 {
-            break;
-        }
-    }
-
-    if !attempts.is_empty() {
-        println!("attempt_artifacts:");
-        for (task_id, attempt) in attempts {
-            println!("  - task={} attempt={}", task_id, attempt);
-            for role in ["implementer", "reviewer"] {
-                let artifacts = discover_attempt_artifacts(&run_dir, &task_id, attempt, role)?;
-                for path in artifacts {
-                    println!("      {}: {}", role, path.display());
-                }
-            }
-        }
+        latest.insert(task_id.clone(), (ev.attempt.unwrap_or(0), reason));
     }
+    latest
+}
 
-    Ok(())
+/// Take the run lock for `run_id`, resolving its runs root from the stored config.
+fn lock_run(store: &EventStore, run_id: &str) -> Result<lease::RunLock> {
+    let run = store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    lease::acquire_run_lock(&runs_root_for_run(&run)?, run_id)
 }
 
-fn continue_run(store: &EventStore, run_id: &str, log: Option<PathBuf>) -> Result<()> {
+/// Drive a run to its next stop; `_run_lock` is held by the caller from before it first wrote
+/// to the run, and released when this returns.
+fn continue_run(
+    store: &EventStore,
+    run_id: &str,
+    log: Option<EventLog>,
+    _run_lock: lease::RunLock,
+) -> Result<()> {
     let run = store
         .get_run(run_id)?
         .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
     let mut cfg: RunConfig = serde_json::from_value(run.config_json.clone())?;
     let plan_path = PathBuf::from(&run.plan_path);
-    let repo_root = repo_root_for_plan(&plan_path)?;
+    let repo_root = cfg.repo_root(&plan_path)?;
+    let runs_root = cfg.runs_root(&repo_root);
 
-    append_attempt_interrupted_for_orphans(store, run_id, &repo_root, log.as_deref())?;
+    append_attempt_interrupted_for_orphans(
+        store,
+        run_id,
+        &runs_root,
+        cfg.lease_stale_secs,
+        log.as_ref(),
+    )?;
     let events = store.list_events(run_id)?;
+    diag::debug(format_args!(
+        "Resuming run {run_id} after seq {}",
+        events.last().map(|ev| ev.seq).unwrap_or(0)
+    ));
     let state = RunProjection::replay(&events);
     if state.terminal.is_some() {
         println!(
@@ -632,6 +1943,12 @@ fn continue_run(store: &EventStore, run_id: &str, log: Option<PathBuf>) -> Resul
         );
         return Ok(());
     }
+    if detect_spec_drift(store, run_id, &run, &cfg, &repo_root, log.as_ref())? {
+        bail!("run paused; plan file changed since the run started")
+    }
+    if detect_config_drift(store, run_id, &cfg, &repo_root, &events, log.as_ref())? {
+        bail!("run paused; config changed since the run started")
+    }
 
     if !state.open_questions.is_empty() {
         let mut ids = state.open_questions.keys().cloned().collect::<Vec<_>>();
@@ -640,7 +1957,7 @@ fn continue_run(store: &EventStore, run_id: &str, log: Option<PathBuf>) -> Resul
             .first()
             .map(|s| s.as_str())
             .ok_or_else(|| anyhow!("unresolved questions present but no IDs found"))?;
-        pause_for_question(store, run_id, first_question_id, log.as_deref())?;
+        pause_for_question(store, run_id, first_question_id, log.as_ref())?;
         bail!("run paused; unresolved questions remain")
     }
 
@@ -648,7 +1965,7 @@ fn continue_run(store: &EventStore, run_id: &str, log: Option<PathBuf>) -> Resul
         refresh_agent_command_before_initial_translation(
             store, run_id, &repo_root, &events, &mut cfg,
         )?;
-        rerun_spec_gate_on_resume(store, run_id, &run, &cfg, &repo_root, log.as_deref())?;
+        rerun_spec_gate_on_resume(store, run_id, &run, &cfg, &repo_root, log.as_ref())?;
         let events_after_spec = store.list_events(run_id)?;
         let state_after_spec = RunProjection::replay(&events_after_spec);
         if !state_after_spec.open_questions.is_empty() {
@@ -662,7 +1979,7 @@ fn continue_run(store: &EventStore, run_id: &str, log: Option<PathBuf>) -> Resul
                 .first()
                 .map(|s| s.as_str())
                 .ok_or_else(|| anyhow!("unresolved questions present but no IDs found"))?;
-            pause_for_question(store, run_id, first_question_id, log.as_deref())?;
+            pause_for_question(store, run_id, first_question_id, log.as_ref())?;
             bail!("run paused; unresolved questions remain")
         }
     }
@@ -671,7 +1988,14 @@ fn continue_run(store: &EventStore, run_id: &str, log: Option<PathBuf>) -> Resul
     let state = RunProjection::replay(&events);
 
     if !state.checks_approved {
-        resolve_checks_configuration_on_resume(store, run_id, &cfg, log.as_deref())?;
+        resolve_checks_configuration_on_resume(
+            store,
+            run_id,
+            &run,
+            &cfg,
+            &repo_root,
+            log.as_ref(),
+        )?;
         let events_after_gate = store.list_events(run_id)?;
         let state_after_gate = RunProjection::replay(&events_after_gate);
         if !state_after_gate.open_questions.is_empty() {
@@ -685,18 +2009,18 @@ fn continue_run(store: &EventStore, run_id: &str, log: Option<PathBuf>) -> Resul
                 .first()
                 .map(|s| s.as_str())
                 .ok_or_else(|| anyhow!("unresolved questions present but no IDs found"))?;
-            pause_for_question(store, run_id, first_question_id, log.as_deref())?;
+            pause_for_question(store, run_id, first_question_id, log.as_ref())?;
             bail!("run paused; unresolved questions remain")
         }
     }
 
     if state.spec_approved {
-        ensure_tasks_registered_on_resume(store, run_id, &run, &cfg, &repo_root, log.as_deref())?;
+        ensure_tasks_registered_on_resume(store, run_id, &run, &cfg, &repo_root, log.as_ref())?;
     }
 
     let spl_path = PathBuf::from(&run.spl_plan_path);
     if !spl_path.exists() {
-        regenerate_plan_spl_if_missing(store, run_id, &cfg, &repo_root, &run, log.as_deref())?;
+        regenerate_plan_spl_if_missing(store, run_id, &cfg, &repo_root, &run, log.as_ref())?;
     }
 
     let plan_spl = std::fs::read_to_string(&run.spl_plan_path)
@@ -706,6 +2030,7 @@ fn continue_run(store: &EventStore, run_id: &str, log: Option<PathBuf>) -> Resul
         run_id: run_id.to_string(),
         cfg,
         base_dir: repo_root,
+        runs_dir: runs_root,
         plan_spl,
         ndjson_log: log,
     };
@@ -746,11 +2071,11 @@ fn refresh_agent_command_before_initial_translation(
     Ok(())
 }
 
-fn pause_for_question(
+pub(crate) fn pause_for_question(
     store: &EventStore,
     run_id: &str,
     question_id: &str,
-    ndjson_log: Option<&Path>,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<()> {
     append_event(
         store,
@@ -776,10 +2101,9 @@ fn pause_for_question(
         ),
         ndjson_log,
     )?;
-    eprintln!("Run paused. Next commands:");
-    eprintln!("  thence questions --run {run_id}");
-    eprintln!("  thence answer --run {run_id} --question {question_id} --text \"...\"");
-    eprintln!("  thence resume --run {run_id}");
+    diag::info(format_args!(
+        "Run paused. Next commands:\n  thence questions --run {run_id}\n  thence answer --run {run_id} --question {question_id} --text \"...\"\n  thence resume --run {run_id}"
+    ));
     Ok(())
 }
 
@@ -787,19 +2111,23 @@ pub(crate) fn append_event(
     store: &EventStore,
     run_id: &str,
     ev: &NewEvent,
-    ndjson_log: Option<&Path>,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<Option<EventRow>> {
-    let history = store.list_events(run_id)?;
-    transitions::validate_transition(&history, ev)?;
+    let (mut state, since_snapshot) = project_run_with_tail_len(store, run_id)?;
+    transitions::validate_transition(&state, ev)?;
     let seq = store.append_event(run_id, ev)?;
     if let Some(seq) = seq {
         let inserted = store
-            .list_events(run_id)?
+            .list_events_in_range(run_id, Some(seq), Some(seq))?
             .into_iter()
-            .find(|e| e.seq == seq)
+            .next()
             .ok_or_else(|| anyhow!("event sequence {seq} was not readable"))?;
-        if let Some(path) = ndjson_log {
-            ndjson::mirror_event(path, &inserted)?;
+        if since_snapshot + 1 >= SNAPSHOT_EVERY_EVENTS {
+            state.apply_event(&inserted);
+            store.save_snapshot(run_id, seq, &state)?;
+        }
+        if let Some(log) = ndjson_log {
+            log.mirror(&inserted)?;
         }
         Ok(Some(inserted))
     } else {
@@ -807,11 +2135,33 @@ pub(crate) fn append_event(
     }
 }
 
+/// Project run state from the latest stored snapshot plus the events recorded after it.
+pub(crate) fn project_run(store: &EventStore, run_id: &str) -> Result<RunProjection> {
+    Ok(project_run_with_tail_len(store, run_id)?.0)
+}
+
+/// [`project_run`], also returning how many events were replayed on top of the snapshot.
+fn project_run_with_tail_len(store: &EventStore, run_id: &str) -> Result<(RunProjection, usize)> {
+    Ok(match store.load_latest_snapshot(run_id)? {
+        Some((seq, snapshot)) => {
+            let tail = store.list_events_since(run_id, seq)?;
+            let len = tail.len();
+            (RunProjection::replay_from((seq, snapshot), &tail), len)
+        }
+        None => {
+            let events = store.list_events(run_id)?;
+            let len = events.len();
+            (RunProjection::replay(&events), len)
+        }
+    })
+}
+
 fn append_attempt_interrupted_for_orphans(
     store: &EventStore,
     run_id: &str,
-    repo_root: &Path,
-    ndjson_log: Option<&Path>,
+    runs_root: &Path,
+    lease_stale_secs: u64,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<()> {
     let events = store.list_events(run_id)?;
     let mut claimed_attempts = Vec::<(String, i64)>::new();
@@ -839,15 +2189,20 @@ fn append_attempt_interrupted_for_orphans(
         if complete {
             continue;
         }
-        let (reason, lease_details) =
-            match lease::evaluate_orphan_attempt(repo_root, run_id, &task_id, attempt)? {
-                lease::OrphanLeaseDecision::Interrupt { reason, details } => (reason, details),
-                lease::OrphanLeaseDecision::LikelyActive { reason, details } => {
-                    let details_str = serde_json::to_string_pretty(&details)
-                        .unwrap_or_else(|_| details.to_string());
-                    bail!("{reason}\nlease_details: {details_str}");
-                }
-            };
+        let (reason, lease_details) = match lease::evaluate_orphan_attempt(
+            runs_root,
+            run_id,
+            &task_id,
+            attempt,
+            lease_stale_secs,
+        )? {
+            lease::OrphanLeaseDecision::Interrupt { reason, details } => (reason, details),
+            lease::OrphanLeaseDecision::LikelyActive { reason, details } => {
+                let details_str =
+                    serde_json::to_string_pretty(&details).unwrap_or_else(|_| details.to_string());
+                bail!("{reason}\nlease_details: {details_str}");
+            }
+        };
         append_event(
             store,
             run_id,
@@ -858,7 +2213,7 @@ fn append_attempt_interrupted_for_orphans(
                 actor_id: Some("supervisor-recovery".to_string()),
                 attempt: Some(attempt),
                 payload_json: json!({"reason": reason, "lease": lease_details}),
-                dedupe_key: Some(format!("attempt_interrupted:{task_id}:{attempt}")),
+                dedupe_key: Some(attempt_dedupe_key("attempt_interrupted", &task_id, attempt)),
             },
             ndjson_log,
         )?;
@@ -871,31 +2226,338 @@ fn resolve_checks_configuration(
     store: &EventStore,
     run_id: &str,
     cfg: &RunConfig,
-    ndjson_log: Option<&Path>,
+    repo_root: &Path,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<()> {
     ensure_checks_configured(&cfg.checks)?;
-    let source = if cfg.checks_from_cli { "cli" } else { "config" };
+    let source = cfg.checks_source();
     append_event(
         store,
         run_id,
         &NewEvent::simple(
             "checks_approved",
-            json!({"commands": cfg.checks, "source": source}),
+            json!({
+                "commands": cfg.checks,
+                "source": source,
+                "source_path": cfg.checks_source_file
+            }),
         ),
         ndjson_log,
     )?;
+    remember_approved_checks(cfg, repo_root, &cfg.checks, source)
+}
+
+/// Save approved checks to `.thence/checks.json` for later runs, unless they came from there
+/// or the run opted out with `--no-checks-file`.
+fn remember_approved_checks(
+    cfg: &RunConfig,
+    repo_root: &Path,
+    commands: &[String],
+    source: &str,
+) -> Result<()> {
+    if cfg.checks_from_saved_file || cfg.no_checks_file {
+        return Ok(());
+    }
+    checks_config::save_checks_file(repo_root, commands, source)?;
     Ok(())
 }
 
+/// Record `spec_drift_detected` when the live plan file no longer hashes to the spec the run
+/// was created from. Each distinct live content is reported once; with
+/// `[run].spec_drift = "pause"` it also opens `spec-q-drift`. Returns whether the run paused.
+fn detect_spec_drift(
+    store: &EventStore,
+    run_id: &str,
+    run: &RunRow,
+    cfg: &RunConfig,
+    repo_root: &Path,
+    ndjson_log: Option<&EventLog>,
+) -> Result<bool> {
+    let Ok(live) = fs::read(&run.plan_path) else {
+        return Ok(false);
+    };
+    let live_sha256 = sha256_hex(&String::from_utf8_lossy(&live));
+    if live_sha256 == run.plan_sha256 {
+        return Ok(false);
+    }
+
+    let pause = cfg.spec_drift == crate::config::DriftAction::Pause;
+    let frozen_spec = frozen_spec_path(&run_artifact_dir(&cfg.runs_root(repo_root), run_id));
+    let recorded = append_event(
+        store,
+        run_id,
+        &NewEvent {
+            dedupe_key: Some(format!("spec_drift_detected:{live_sha256}")),
+            ..NewEvent::simple(
+                "spec_drift_detected",
+                json!({
+                    "plan_path": run.plan_path,
+                    "frozen_spec_path": frozen_spec,
+                    "frozen_sha256": run.plan_sha256,
+                    "live_sha256": live_sha256,
+                    "action": if pause { "pause" } else { "warn" }
+                }),
+            )
+        },
+        ndjson_log,
+    )?;
+    if recorded.is_none() {
+        return Ok(false);
+    }
+    diag::warn(format_args!(
+        "plan file {} changed since run {run_id} started; the run continues from the frozen spec {}",
+        run.plan_path,
+        frozen_spec.display()
+    ));
+    if !pause {
+        return Ok(false);
+    }
+
+    append_event(
+        store,
+        run_id,
+        &NewEvent::simple(
+            "spec_question_opened",
+            json!({
+                "question_id": SPEC_DRIFT_QUESTION_ID,
+                "question": format!(
+                    "Plan file {} changed since the run started, but the run continues from the frozen spec {}. Answer to continue anyway, or cancel and start a new run from the edited plan.",
+                    run.plan_path,
+                    frozen_spec.display()
+                )
+            }),
+        ),
+        ndjson_log,
+    )?;
+    pause_for_question(store, run_id, SPEC_DRIFT_QUESTION_ID, ndjson_log)?;
+    Ok(true)
+}
+
+/// The `.thence/config.toml` settings a run's schedule and gates depend on, as recorded in
+/// `run_started`. Settings that only tune timing or output are left out.
+fn config_structure(repo_cfg: Option<&crate::config::RepoConfig>) -> serde_json::Value {
+    let checks = repo_cfg.and_then(|cfg| cfg.checks.as_ref());
+    json!({
+        "run.max_attempts": repo_cfg
+            .and_then(|cfg| cfg.run.as_ref())
+            .and_then(|run| run.max_attempts),
+        "checks.commands": checks
+            .map(|checks| checks.commands.iter().map(|c| c.command.clone()).collect::<Vec<_>>()),
+        "checks.task_checks": checks.map(|checks| checks.task_checks),
+        "vcs": repo_cfg.and_then(|cfg| cfg.vcs.clone()),
+    })
+}
+
+/// Record `config_drift_detected` when the structural config recorded in `run_started` differs
+/// from the current `.thence/config.toml`. The run keeps the settings it started with; with
+/// `[run].config_drift = "pause"` it also opens `config-q-drift`. Returns whether the run paused.
+fn detect_config_drift(
+    store: &EventStore,
+    run_id: &str,
+    cfg: &RunConfig,
+    repo_root: &Path,
+    events: &[EventRow],
+    ndjson_log: Option<&EventLog>,
+) -> Result<bool> {
+    // Runs started before the digest was recorded have nothing to compare against.
+    let Some(recorded) = events
+        .iter()
+        .find(|ev| ev.event_type == "run_started")
+        .and_then(|ev| ev.payload_json.get("config_structure"))
+    else {
+        return Ok(false);
+    };
+    let live = config_structure(crate::config::load_repo_config(repo_root)?.as_ref());
+    let changed = live
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, value)| recorded.get(key.as_str()) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+    if changed.is_empty() {
+        return Ok(false);
+    }
+
+    let pause = cfg.config_drift == crate::config::DriftAction::Pause;
+    let live_sha256 = sha256_hex(&live.to_string());
+    let appended = append_event(
+        store,
+        run_id,
+        &NewEvent {
+            dedupe_key: Some(format!("config_drift_detected:{live_sha256}")),
+            ..NewEvent::simple(
+                "config_drift_detected",
+                json!({
+                    "changed": changed,
+                    "recorded_sha256": sha256_hex(&recorded.to_string()),
+                    "live_sha256": live_sha256,
+                    "action": if pause { "pause" } else { "warn" }
+                }),
+            )
+        },
+        ndjson_log,
+    )?;
+    if appended.is_none() {
+        return Ok(false);
+    }
+    diag::warn(format_args!(
+        "config changed since run {run_id} started ({}); the run keeps the settings it started with",
+        changed.join(", ")
+    ));
+    if !pause {
+        return Ok(false);
+    }
+
+    append_event(
+        store,
+        run_id,
+        &NewEvent::simple(
+            "spec_question_opened",
+            json!({
+                "question_id": CONFIG_DRIFT_QUESTION_ID,
+                "question": format!(
+                    "Config changed since the run started ({}), but the run keeps the settings it started with. Answer to continue anyway, or cancel and start a new run with the new config.",
+                    changed.join(", ")
+                )
+            }),
+        ),
+        ndjson_log,
+    )?;
+    pause_for_question(store, run_id, CONFIG_DRIFT_QUESTION_ID, ndjson_log)?;
+    Ok(true)
+}
+
+#[derive(Debug, Deserialize)]
+struct ChecksProposal {
+    commands: Vec<String>,
+    #[serde(default)]
+    rationale: Option<String>,
+}
+
+/// Ask the `checks-proposer` agent for check commands, record them as `checks_proposed`, and
+/// pause on the `checks-q-propose` question until a human approves or replaces them.
+fn propose_checks_for_run(
+    store: &EventStore,
+    run_id: &str,
+    cfg: &RunConfig,
+    repo_root: &Path,
+    markdown: &str,
+    translated: &translator::TranslatedPlan,
+    ndjson_log: Option<&EventLog>,
+) -> Result<()> {
+    let provider = provider_for(
+        &cfg.agent,
+        cfg.simulate,
+        cfg.agent_command.as_deref(),
+        cfg.agent_spawn_retries,
+    )?;
+    let worktree = run_artifact_dir(&cfg.runs_root(repo_root), run_id)
+        .join("checks-proposal")
+        .join("attempt1");
+    fs::create_dir_all(&worktree)?;
+    let res = provider.run(AgentRequest {
+        role: "checks-proposer".to_string(),
+        task_id: "__checks__".to_string(),
+        attempt: 1,
+        worktree_path: worktree,
+        prompt: packet::build_checks_proposer_prompt(repo_root, markdown, translated),
+        env: cfg.agent_env(Vec::new()),
+        timeout: Duration::from_secs(cfg.attempt_timeout_secs),
+    })?;
+    let proposal = res
+        .structured_output
+        .as_ref()
+        .filter(|_| res.exit_code == 0)
+        .and_then(|output| serde_json::from_value::<ChecksProposal>(output.clone()).ok());
+    let commands = proposal
+        .as_ref()
+        .map(|p| {
+            p.commands
+                .iter()
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if commands.is_empty() {
+        bail!(
+            "checks-proposer did not propose any commands (exit_code={}); see logs: stdout={} stderr={}. {NO_CHECKS_CONFIGURED_ERROR}",
+            res.exit_code,
+            res.stdout_path.display(),
+            res.stderr_path.display()
+        );
+    }
+
+    append_event(
+        store,
+        run_id,
+        &NewEvent::simple(
+            "checks_proposed",
+            json!({
+                "commands": commands,
+                "rationale": proposal.and_then(|p| p.rationale),
+                "question_id": CHECKS_QUESTION_ID
+            }),
+        ),
+        ndjson_log,
+    )?;
+    append_event(
+        store,
+        run_id,
+        &NewEvent::simple(
+            "spec_question_opened",
+            json!({
+                "question_id": CHECKS_QUESTION_ID,
+                "question": format!(
+                    "No checks are configured; the checks-proposer suggests: {}. Answer `approve` to use them, or give replacement commands separated by `;`.",
+                    commands.join("; ")
+                )
+            }),
+        ),
+        ndjson_log,
+    )?;
+    pause_for_question(store, run_id, CHECKS_QUESTION_ID, ndjson_log)
+}
+
+/// Commands a `checks-q-propose` answer approves: the latest proposal for `approve`, otherwise
+/// the answer itself as `;`-separated commands. The second value is the `checks_approved` source.
+fn checks_from_answer(
+    store: &EventStore,
+    run_id: &str,
+    text: &str,
+) -> Result<(Vec<String>, &'static str)> {
+    if text.trim().eq_ignore_ascii_case(APPROVE_ANSWER) {
+        let proposed = store
+            .list_events_of_type(run_id, "checks_proposed")?
+            .pop()
+            .ok_or_else(|| anyhow!("run {run_id} has no proposed checks to approve"))?;
+        let commands = proposed.payload_json["commands"]
+            .as_array()
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str().map(ToString::to_string))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        return Ok((commands, "proposer"));
+    }
+    let commands = parse_checks(Some(text));
+    if commands.is_empty() {
+        bail!("answer `approve` or give check commands separated by `;`")
+    }
+    Ok((commands, "human"))
+}
+
 fn load_or_translate_plan_for_run(
     store: &EventStore,
     run_id: &str,
     run: &RunRow,
     cfg: &RunConfig,
     repo_root: &Path,
-    ndjson_log: Option<&Path>,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<(String, translator::TranslatedPlan)> {
-    let run_dir = run_artifact_dir(repo_root, run_id);
+    let run_dir = run_artifact_dir(&cfg.runs_root(repo_root), run_id);
     fs::create_dir_all(&run_dir)?;
     let plan_path = Path::new(&run.plan_path);
     let translated_path = translated_plan_path(&run_dir);
@@ -911,27 +2573,15 @@ fn load_or_translate_plan_for_run(
         )
     } else {
         // When there is no frozen translated plan yet, always translate from the live spec.
-        let markdown = fs::read_to_string(plan_path)
-            .with_context(|| format!("read plan file {}", plan_path.display()))?;
-        let (translated, translation_res) = match translate_spec_with_agent(
-            cfg, repo_root, plan_path, &markdown, &run_dir,
-        ) {
-            Ok(result) => result,
-            Err(err) => {
-                let qid = "spec-q-translate";
-                append_event(
-                    store,
-                    run_id,
-                    &NewEvent::simple(
-                        "spec_question_opened",
-                        json!({"question_id": qid, "question": format!("Plan translation failed: {err}")}),
-                    ),
-                    ndjson_log,
-                )?;
-                pause_for_question(store, run_id, qid, ndjson_log)?;
-                bail!("run paused due to translation failure")
-            }
-        };
+        let markdown = read_plan_text(plan_path, "plan file")?;
+        let (translated, translation_res) =
+            match translate_spec(cfg, repo_root, plan_path, &markdown, &run_dir) {
+                Ok(result) => result,
+                Err(err) => {
+                    open_translation_question(store, run_id, &run_dir, &err, ndjson_log)?;
+                    bail!("run paused due to translation failure")
+                }
+            };
         fs::write(&run.spl_plan_path, &translated.spl)
             .with_context(|| format!("write translated SPL {}", run.spl_plan_path))?;
         translator::save_translated_plan(&translated_path, &translated)?;
@@ -939,16 +2589,16 @@ fn load_or_translate_plan_for_run(
         append_event(
             store,
             run_id,
-            &NewEvent::simple(
-                "plan_translated",
+            &resume_plan_translated_event(
+                "resume_translated",
+                &translated.spl,
                 json!({
                     "spl_path": run.spl_plan_path,
                     "translated_plan_path": translated_path,
                     "frozen_spec_path": frozen_spec,
                     "task_count": translated.tasks.len(),
-                    "source": "resume_translated",
-                    "translator_stdout_path": translation_res.stdout_path,
-                    "translator_stderr_path": translation_res.stderr_path
+                    "translator_stdout_path": translation_res.as_ref().map(|res| &res.stdout_path),
+                    "translator_stderr_path": translation_res.as_ref().map(|res| &res.stderr_path)
                 }),
             ),
             ndjson_log,
@@ -962,13 +2612,13 @@ fn load_or_translate_plan_for_run(
         append_event(
             store,
             run_id,
-            &NewEvent::simple(
-                "plan_translated",
+            &resume_plan_translated_event(
+                "resume_regenerated_from_frozen",
+                &translated.spl,
                 json!({
                     "spl_path": run.spl_plan_path,
                     "translated_plan_path": translated_path,
-                    "task_count": translated.tasks.len(),
-                    "source": "resume_regenerated_from_frozen"
+                    "task_count": translated.tasks.len()
                 }),
             ),
             ndjson_log,
@@ -983,13 +2633,13 @@ fn load_or_translate_plan_for_run(
             append_event(
                 store,
                 run_id,
-                &NewEvent::simple(
-                    "plan_translated",
+                &resume_plan_translated_event(
+                    "resume_reconciled_from_frozen",
+                    &translated.spl,
                     json!({
                         "spl_path": run.spl_plan_path,
                         "translated_plan_path": translated_path,
-                        "task_count": translated.tasks.len(),
-                        "source": "resume_reconciled_from_frozen"
+                        "task_count": translated.tasks.len()
                     }),
                 ),
                 ndjson_log,
@@ -997,8 +2647,8 @@ fn load_or_translate_plan_for_run(
         }
     }
 
-    if let Err(err) =
-        validate::validate_spl(&translated.spl).and_then(|_| sanity::run_sanity_checks(&translated))
+    if let Err(err) = validate::validate_spl(&translated.spl)
+        .and_then(|_| sanity::run_sanity_checks(&translated, cfg.max_tasks))
     {
         let qid = "spec-q-validate";
         append_event(
@@ -1029,18 +2679,45 @@ fn load_or_translate_plan_for_run(
     Ok((markdown, translated))
 }
 
+/// `plan_translated` for a resume-time translation or SPL repair, deduplicated on
+/// `(source, spl_sha)` so repeated resumes over the same frozen plan record it once.
+fn resume_plan_translated_event(
+    source: &str,
+    spl: &str,
+    mut payload: serde_json::Value,
+) -> NewEvent {
+    payload["source"] = json!(source);
+    NewEvent {
+        dedupe_key: Some(format!("plan_translated:{source}:{}", sha256_hex(spl))),
+        ..NewEvent::simple("plan_translated", payload)
+    }
+}
+
 fn rerun_spec_gate_on_resume(
     store: &EventStore,
     run_id: &str,
     run: &RunRow,
     cfg: &RunConfig,
     repo_root: &Path,
-    ndjson_log: Option<&Path>,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<()> {
     let (markdown, translated) =
         load_or_translate_plan_for_run(store, run_id, run, cfg, repo_root, ndjson_log)?;
 
-    match review_loop::review_spec(&markdown, &translated) {
+    if cfg.no_spec_review {
+        append_event(
+            store,
+            run_id,
+            &NewEvent::simple(
+                "spec_approved",
+                json!({"approved": true, "source": SPEC_REVIEW_SKIPPED_SOURCE}),
+            ),
+            ndjson_log,
+        )?;
+        return Ok(());
+    }
+    let run_dir = run_artifact_dir(&cfg.runs_root(repo_root), run_id);
+    match review_spec_for_run(cfg, &run_dir, &markdown, &translated)? {
         review_loop::SpecReviewOutcome::Approved => {
             append_event(
                 store,
@@ -1076,25 +2753,41 @@ fn rerun_spec_gate_on_resume(
 fn resolve_checks_configuration_on_resume(
     store: &EventStore,
     run_id: &str,
+    run: &RunRow,
     cfg: &RunConfig,
-    ndjson_log: Option<&Path>,
+    repo_root: &Path,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<()> {
+    if cfg.propose_checks && cfg.checks.is_empty() {
+        let (markdown, translated) =
+            load_or_translate_plan_for_run(store, run_id, run, cfg, repo_root, ndjson_log)?;
+        propose_checks_for_run(
+            store,
+            run_id,
+            cfg,
+            repo_root,
+            &markdown,
+            &translated,
+            ndjson_log,
+        )?;
+        bail!("run paused awaiting checks approval")
+    }
     ensure_checks_configured(&cfg.checks)?;
-    let source = if cfg.checks_from_cli {
-        "cli_resume"
-    } else {
-        "config_resume"
-    };
+    let source = format!("{}_resume", cfg.checks_source());
     append_event(
         store,
         run_id,
         &NewEvent::simple(
             "checks_approved",
-            json!({"commands": cfg.checks, "source": source}),
+            json!({
+                "commands": cfg.checks,
+                "source": source,
+                "source_path": cfg.checks_source_file
+            }),
         ),
         ndjson_log,
     )?;
-    Ok(())
+    remember_approved_checks(cfg, repo_root, &cfg.checks, &source)
 }
 
 fn regenerate_plan_spl_if_missing(
@@ -1103,36 +2796,43 @@ fn regenerate_plan_spl_if_missing(
     cfg: &RunConfig,
     repo_root: &Path,
     run: &RunRow,
-    ndjson_log: Option<&Path>,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<()> {
     let _ = load_or_translate_plan_for_run(store, run_id, run, cfg, repo_root, ndjson_log)?;
     Ok(())
 }
 
+/// Register every task of the frozen translated plan that has no `task_registered` yet, so a
+/// task added to `translated_plan.json` between resumes joins the run. Tasks already registered
+/// are left alone, whatever their state.
 fn ensure_tasks_registered_on_resume(
     store: &EventStore,
     run_id: &str,
     run: &RunRow,
     cfg: &RunConfig,
     repo_root: &Path,
-    ndjson_log: Option<&Path>,
+    ndjson_log: Option<&EventLog>,
 ) -> Result<()> {
-    let events = store.list_events(run_id)?;
-    if events.iter().any(|ev| ev.event_type == "task_registered") {
-        return Ok(());
+    let registered = store
+        .list_events(run_id)?
+        .into_iter()
+        .filter(|ev| ev.event_type == "task_registered")
+        .filter_map(|ev| ev.task_id)
+        .collect::<BTreeSet<_>>();
+
+    // Also rewrites plan.spl from the frozen plan, so added tasks get their policy facts, and
+    // translates afresh for runs without translated_plan.json.
+    let (_, translated) =
+        load_or_translate_plan_for_run(store, run_id, run, cfg, repo_root, ndjson_log)
+            .context("load translated plan during resume task registration")?;
+    let mut translated = retain_only_tasks(translated, &cfg.only_tasks)?;
+    translated.tasks.retain(|t| !registered.contains(&t.id));
+    if !registered.is_empty() && !translated.tasks.is_empty() {
+        diag::info(format_args!(
+            "Registering {} task(s) added to the translated plan since the last resume",
+            translated.tasks.len()
+        ));
     }
-
-    let run_dir = run_artifact_dir(&repo_root, run_id);
-    let translated_path = translated_plan_path(&run_dir);
-    let translated = if translated_path.exists() {
-        translator::load_translated_plan(&translated_path)
-            .context("load translated plan during resume task registration")?
-    } else {
-        // Backward-compatible fallback for runs without translated_plan.json.
-        let (_, translated) =
-            load_or_translate_plan_for_run(store, run_id, run, cfg, repo_root, ndjson_log)?;
-        translated
-    };
     register_translated_tasks(store, run_id, cfg, &translated, ndjson_log)?;
     Ok(())
 }
@@ -1141,13 +2841,21 @@ fn read_optional_file(path: &Path) -> Option<String> {
     fs::read_to_string(path).ok()
 }
 
+/// The nearest directory above the (symlink-resolved) plan file that holds `.git` or an existing
+/// `.thence`, so a plan under `docs/` still uses the repo's `.thence`. Falls back to the plan's
+/// own directory.
 fn repo_root_for_plan(plan_file: &Path) -> Result<PathBuf> {
     let p = plan_file
         .canonicalize()
         .with_context(|| format!("resolve plan path {}", plan_file.display()))?;
-    p.parent()
-        .map(Path::to_path_buf)
-        .ok_or_else(|| anyhow!("cannot derive repo root from {}", p.display()))
+    let plan_dir = p
+        .parent()
+        .ok_or_else(|| anyhow!("cannot derive repo root from {}", p.display()))?;
+    let root = plan_dir
+        .ancestors()
+        .find(|dir| dir.join(".git").exists() || dir.join(".thence").is_dir())
+        .unwrap_or(plan_dir);
+    Ok(root.to_path_buf())
 }
 
 pub(crate) fn default_checks() -> Vec<String> {
@@ -1173,8 +2881,18 @@ fn ensure_checks_configured(commands: &[String]) -> Result<()> {
     Ok(())
 }
 
-pub(crate) fn run_artifact_dir(base: &Path, run_id: &str) -> PathBuf {
-    base.join(".thence").join("runs").join(run_id)
+pub(crate) fn default_runs_root(repo_root: &Path) -> PathBuf {
+    repo_root.join(".thence").join("runs")
+}
+
+pub(crate) fn run_artifact_dir(runs_root: &Path, run_id: &str) -> PathBuf {
+    runs_root.join(run_id)
+}
+
+/// Runs root recorded in a run's config, for commands that only have the run row.
+fn runs_root_for_run(run: &RunRow) -> Result<PathBuf> {
+    let cfg: RunConfig = serde_json::from_value(run.config_json.clone())?;
+    Ok(cfg.runs_root(&cfg.repo_root(Path::new(&run.plan_path))?))
 }
 
 fn resolve_resume_run_id(store: &EventStore, explicit: Option<&str>) -> Result<String> {
@@ -1186,7 +2904,7 @@ fn resolve_resume_run_id(store: &EventStore, explicit: Option<&str>) -> Result<S
         [only] => Ok(only.clone()),
         [] => bail!("no resumable runs found; provide a plan file without --resume"),
         _ => bail!(
-            "multiple resumable runs found: {}. Re-run with --run-id <id>",
+            "multiple resumable runs found: {}. Re-run with --run-id <id> (see `thence list --resumable`)",
             candidates.join(", ")
         ),
     }
@@ -1196,6 +2914,7 @@ fn is_spec_review_question_id(question_id: &str) -> bool {
     question_id.starts_with("spec-q-")
         && question_id != "spec-q-translate"
         && question_id != "spec-q-validate"
+        && question_id != SPEC_DRIFT_QUESTION_ID
 }
 
 fn discover_attempt_artifacts(
@@ -1209,10 +2928,25 @@ fn discover_attempt_artifacts(
         .join("thence")
         .join(task_id)
         .join(format!("v{attempt}"));
+    let prefix = format!("{role}_attempt{attempt}");
+    let mut out = collect_prefixed_files(root, &prefix)?;
+    out.extend(collect_prefixed_files(
+        attempt_logs_dir(run_dir, task_id, attempt),
+        &prefix,
+    )?);
+    out.sort();
+    Ok(out)
+}
+
+/// Plan-translator prompt, result, and stdout/stderr logs, which live outside any task worktree.
+fn discover_translator_artifacts(run_dir: &Path) -> Result<Vec<PathBuf>> {
+    collect_prefixed_files(plan_translation_dir(run_dir), "plan-translator_attempt1")
+}
+
+fn collect_prefixed_files(root: PathBuf, prefix: &str) -> Result<Vec<PathBuf>> {
     if !root.exists() {
         return Ok(Vec::new());
     }
-    let prefix = format!("{role}_attempt{attempt}");
     let mut out = Vec::new();
     let mut stack = vec![root];
     while let Some(dir) = stack.pop() {
@@ -1226,7 +2960,7 @@ fn discover_attempt_artifacts(
             }
             let name = entry.file_name();
             let name = name.to_string_lossy();
-            if name.starts_with(&prefix) {
+            if name.starts_with(prefix) {
                 out.push(path);
             }
         }
@@ -1,7 +1,8 @@
 use crate::events::EventRow;
 use crate::events::projector::{RunProjection, TaskProjection};
+use crate::plan::translator::TranslatedPlan;
 use serde_json::json;
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 const PLAN_TRANSLATOR_SPL_REFERENCE: &str = r#"SPL QUICK REFERENCE (thence translator subset)
@@ -38,14 +39,17 @@ pub fn build_plan_translator_prompt(
         "instruction": "Translate the specification into a self-contained SPL plan and a normalized task graph JSON. Return ONLY JSON.",
         "output_contract": {
             "required_keys": ["spl", "tasks"],
-            "tasks_item_keys": ["id", "objective", "acceptance", "dependencies", "checks"],
+            "tasks_item_keys": ["id", "objective", "acceptance", "dependencies", "checks", "priority", "deadline_secs"],
             "task_id_charset": "[A-Za-z0-9_-]+",
             "constraints": [
                 "spl must be valid spindle SPL",
                 "no import directives",
                 "every tasks[].id appears as (given (task <id>)) fact",
                 "every dependency edge appears as (given (depends-on <task> <dep>)) fact",
-                "dependencies must reference existing task ids"
+                "dependencies must reference existing task ids",
+                "acceptance is an optional string or array of strings, one per criterion",
+                "priority is an optional integer; higher is scheduled first (default 0)",
+                "deadline_secs is an optional positive integer; the task fails once this many seconds pass after its first claim"
             ]
         },
         "repo_root": repo_root,
@@ -59,29 +63,75 @@ pub fn build_plan_translator_prompt(
     payload.to_string()
 }
 
+pub fn build_spec_reviewer_prompt(markdown: &str, plan: &TranslatedPlan) -> String {
+    json!({
+        "role": "spec-reviewer",
+        "instruction": "Review the specification and its translated task graph for ambiguities an implementer could not resolve alone. Return ONLY JSON.",
+        "output_contract": {
+            "required_keys": ["approved"],
+            "optional_keys": ["question_id", "question"],
+            "constraints": [
+                "approved is true when the spec is implementable as written",
+                "when approved is false, question asks the single most important clarification"
+            ]
+        },
+        "spec_markdown": markdown,
+        "tasks": plan.tasks
+    })
+    .to_string()
+}
+
+pub fn build_checks_proposer_prompt(
+    repo_root: &Path,
+    markdown: &str,
+    plan: &TranslatedPlan,
+) -> String {
+    json!({
+        "role": "checks-proposer",
+        "instruction": "Propose the shell commands that should gate every task (build, lint, tests) for this repository. Return ONLY JSON.",
+        "output_contract": {
+            "required_keys": ["commands"],
+            "optional_keys": ["rationale"],
+            "constraints": [
+                "commands is a non-empty array of shell commands run from the repository root",
+                "prefer fast, deterministic commands the repository already uses"
+            ]
+        },
+        "repo_root": repo_root,
+        "spec_markdown": markdown,
+        "tasks": plan.tasks
+    })
+    .to_string()
+}
+
 pub fn build_implementer_prompt(
     run: &RunProjection,
     events: &[EventRow],
     task: &TaskProjection,
     attempt: i64,
     run_checks: &[String],
+    implementer_instruction: Option<&str>,
 ) -> String {
     let dep_outcomes = dependency_outcomes(run, task);
     let unresolved = unresolved_findings(events, &task.id);
     let artifact_refs = artifact_refs(events, &task.id, attempt);
 
-    json!({
+    let mut prompt = json!({
         "role": "implementer",
         "task_id": task.id,
         "attempt": attempt,
         "objective": task.objective,
         "acceptance": task.acceptance,
+        "acceptance_criteria": task.acceptance_criteria,
         "dependency_outcomes": dep_outcomes,
         "unresolved_findings": unresolved,
         "required_checks": run_checks,
         "artifact_refs": artifact_refs
-    })
-    .to_string()
+    });
+    if let Some(instruction) = implementer_instruction {
+        prompt["instruction"] = json!(instruction);
+    }
+    prompt.to_string()
 }
 
 pub fn build_reviewer_prompt(
@@ -102,6 +152,7 @@ pub fn build_reviewer_prompt(
         "attempt": attempt,
         "objective": task.objective,
         "acceptance": task.acceptance,
+        "acceptance_criteria": task.acceptance_criteria,
         "submission_refs": submission_refs,
         "prior_findings": findings,
         "required_checks": run_checks,
@@ -126,6 +177,7 @@ fn dependency_outcomes(run: &RunProjection, task: &TaskProjection) -> Vec<serde_
 
 fn unresolved_findings(events: &[EventRow], task_id: &str) -> Vec<serde_json::Value> {
     let mut by_attempt: BTreeMap<i64, Vec<String>> = BTreeMap::new();
+    let mut details: BTreeMap<i64, Vec<serde_json::Value>> = BTreeMap::new();
     let mut resolved: HashMap<i64, bool> = HashMap::new();
 
     for ev in events {
@@ -158,6 +210,16 @@ fn unresolved_findings(events: &[EventRow], task_id: &str) -> Vec<serde_json::Va
                     );
                 }
                 by_attempt.entry(attempt).or_default().extend(reasons);
+                if let Some(structured) = ev
+                    .payload_json
+                    .get("structured_findings")
+                    .and_then(|v| v.as_array())
+                {
+                    details
+                        .entry(attempt)
+                        .or_default()
+                        .extend(structured.iter().cloned());
+                }
                 resolved.insert(attempt, false);
             }
             "review_approved" => {
@@ -168,20 +230,66 @@ fn unresolved_findings(events: &[EventRow], task_id: &str) -> Vec<serde_json::Va
         }
     }
 
-    by_attempt
+    // A finding that recurs across attempts is listed once, in first-seen order, with every
+    // attempt it was raised on.
+    let mut merged: Vec<MergedFinding> = Vec::new();
+    for (attempt, reasons) in by_attempt
         .into_iter()
         .filter(|(attempt, _)| !resolved.get(attempt).copied().unwrap_or(false))
-        .map(|(attempt, reasons)| {
-            let reasons = reasons
-                .into_iter()
-                .collect::<BTreeSet<_>>()
-                .into_iter()
-                .collect::<Vec<_>>();
-            json!({"attempt": attempt, "reasons": reasons})
+    {
+        let attempt_details = details.remove(&attempt).unwrap_or_default();
+        for reason in reasons {
+            let key = normalize_finding(&reason);
+            let detail = attempt_details
+                .iter()
+                .find(|d| {
+                    d.get("message")
+                        .and_then(|m| m.as_str())
+                        .is_some_and(|m| normalize_finding(m) == key)
+                })
+                .cloned();
+            match merged.iter_mut().find(|m| m.key == key) {
+                Some(existing) => {
+                    if !existing.attempts.contains(&attempt) {
+                        existing.attempts.push(attempt);
+                    }
+                    existing.detail = existing.detail.take().or(detail);
+                }
+                None => merged.push(MergedFinding {
+                    key,
+                    finding: reason,
+                    attempts: vec![attempt],
+                    detail,
+                }),
+            }
+        }
+    }
+
+    merged
+        .into_iter()
+        .map(|m| match m.detail {
+            Some(detail) => {
+                json!({"finding": m.finding, "attempts": m.attempts, "detail": detail})
+            }
+            None => json!({"finding": m.finding, "attempts": m.attempts}),
         })
         .collect()
 }
 
+struct MergedFinding {
+    key: String,
+    finding: String,
+    attempts: Vec<i64>,
+    detail: Option<serde_json::Value>,
+}
+
+fn normalize_finding(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 fn artifact_refs(
     events: &[EventRow],
     task_id: &str,
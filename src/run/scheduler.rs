@@ -6,12 +6,35 @@ pub fn next_claimable_task(
     policy: &PolicySnapshot,
     max_attempts: i64,
 ) -> Option<String> {
-    let mut ids = run.tasks.keys().cloned().collect::<Vec<_>>();
-    ids.sort();
-    ids.into_iter().find(|id| {
-        run.tasks
-            .get(id)
-            .map(|t| policy.claimable.contains(id) && t.attempts < max_attempts)
-            .unwrap_or(false)
-    })
+    next_claimable_tasks(run, policy, max_attempts, 1)
+        .into_iter()
+        .next()
+}
+
+/// Claimable tasks, highest priority first with ties broken by id, capped at `limit` so each
+/// gets its own worker slot.
+pub fn next_claimable_tasks(
+    run: &RunProjection,
+    policy: &PolicySnapshot,
+    max_attempts: i64,
+    limit: usize,
+) -> Vec<String> {
+    let mut tasks = run
+        .tasks
+        .values()
+        .filter(|t| policy.claimable.contains(&t.id) && t.attempts < max_attempts)
+        .collect::<Vec<_>>();
+    tasks.sort_by(|a, b| b.priority.cmp(&a.priority).then_with(|| a.id.cmp(&b.id)));
+    tasks
+        .into_iter()
+        .take(limit.max(1))
+        .map(|t| t.id.clone())
+        .collect()
+}
+
+/// Actor id of the worker slot running the `index`-th attempt of a batch. Batches run at most
+/// `limit` agents at once, so the id names the concurrent slot an attempt occupies (`impl-1`
+/// .. `impl-N`) for its whole duration, independent of the attempt number.
+pub fn worker_slot_id(role_prefix: &str, index: usize, limit: usize) -> String {
+    format!("{role_prefix}-{}", index % limit.max(1) + 1)
 }
@@ -0,0 +1,139 @@
+use super::default_state_db;
+use crate::events::EventRow;
+use crate::events::store::EventStore;
+use anyhow::{Result, anyhow};
+use chrono::DateTime;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Longest string payload value shown in a timeline line before it is cut short.
+const MAX_VALUE_CHARS: usize = 80;
+
+/// Payload fields worth a glance for each event type, in the order they are printed.
+fn summary_fields(event_type: &str) -> &'static [&'static str] {
+    match event_type {
+        "run_started" => &["plan_file", "agent", "workers", "reviewers"],
+        "plan_translated" => &["source", "task_count"],
+        "translation_failed" => &["error"],
+        "plan_validated" => &["ok"],
+        "spec_question_opened" | "human_input_requested" => &["question_id", "question"],
+        "spec_question_resolved" => &["question_id"],
+        "human_input_provided" => &["question_id", "rejected"],
+        "spec_approved" => &["source"],
+        "spec_drift_detected" | "config_drift_detected" => &["action"],
+        "checks_proposed" | "checks_approved" => &["commands"],
+        "run_paused" | "run_resumed" | "run_cancelled" => &["reason"],
+        "work_submitted" => &["exit_code", "output_valid", "duration_ms"],
+        "review_found_issues" => &["reason", "source"],
+        "review_approved" => &["finding_count"],
+        "checks_reported" => &["passed", "stage", "duration_ms"],
+        "merge_succeeded" => &["integration_branch"],
+        "merge_conflict" => &["reason"],
+        "task_failed_terminal" | "attempt_interrupted" => &["reason"],
+        "task_skipped" => &["dependency"],
+        "run_completed" => &["task_count"],
+        "run_failed" => &["reason"],
+        _ => &[],
+    }
+}
+
+fn summary_value(value: &Value) -> String {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map_or_else(|| item.to_string(), str::to_string)
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() > MAX_VALUE_CHARS {
+        let cut = text.chars().take(MAX_VALUE_CHARS).collect::<String>();
+        format!("{cut}...")
+    } else {
+        text
+    }
+}
+
+/// One line per event: seconds since the run's first event, seq, type, task and attempt, then
+/// the event's key payload fields.
+fn timeline_line(event: &EventRow, start: Option<DateTime<chrono::FixedOffset>>) -> String {
+    let offset = match (start, DateTime::parse_from_rfc3339(&event.ts).ok()) {
+        (Some(start), Some(ts)) => {
+            format!("+{:.3}s", (ts - start).num_milliseconds() as f64 / 1000.0)
+        }
+        _ => "+?".to_string(),
+    };
+    let mut line = format!("{offset:>10} #{} {}", event.seq, event.event_type);
+    if let Some(task_id) = &event.task_id {
+        line.push_str(&format!(" task={task_id}"));
+    }
+    if let Some(attempt) = event.attempt {
+        line.push_str(&format!(" attempt={attempt}"));
+    }
+    for field in summary_fields(&event.event_type) {
+        if let Some(value) = event.payload_json.get(*field).filter(|v| !v.is_null()) {
+            line.push_str(&format!(" {field}={}", summary_value(value)));
+        }
+    }
+    line
+}
+
+/// The run's events in order, one summary line each, as `thence inspect --timeline` prints them.
+pub fn run_timeline(run_id: &str, state_db: Option<PathBuf>) -> Result<Vec<String>> {
+    let store = EventStore::open_read_only(&state_db.unwrap_or_else(default_state_db))?;
+    store
+        .get_run(run_id)?
+        .ok_or_else(|| anyhow!("run not found: {run_id}"))?;
+    let events = store.list_events(run_id)?;
+    let start = events
+        .first()
+        .and_then(|ev| DateTime::parse_from_rfc3339(&ev.ts).ok());
+    Ok(events.iter().map(|ev| timeline_line(ev, start)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(seq: i64, ts: &str, event_type: &str, payload: Value) -> EventRow {
+        EventRow {
+            seq,
+            run_id: "run".to_string(),
+            ts: ts.to_string(),
+            event_type: event_type.to_string(),
+            task_id: Some("task_a".to_string()),
+            actor_role: None,
+            actor_id: None,
+            attempt: Some(2),
+            payload_json: payload,
+            dedupe_key: None,
+        }
+    }
+
+    #[test]
+    fn timeline_line_shows_offset_task_and_key_fields() {
+        let start = DateTime::parse_from_rfc3339("2026-01-01T00:00:00+00:00").ok();
+        let ev = event(
+            7,
+            "2026-01-01T00:00:01.500+00:00",
+            "checks_reported",
+            json!({"passed": false, "stage": "post_review", "results": [{"ok": false}]}),
+        );
+        assert_eq!(
+            timeline_line(&ev, start),
+            "   +1.500s #7 checks_reported task=task_a attempt=2 passed=false stage=post_review"
+        );
+
+        let long = "x ".repeat(100);
+        let ev = event(8, "bad", "task_failed_terminal", json!({ "reason": long }));
+        let line = timeline_line(&ev, start);
+        assert!(line.starts_with("        +? #8 task_failed_terminal"));
+        assert!(line.ends_with("..."));
+    }
+}
@@ -1,24 +1,18 @@
+use crate::events::NewEvent;
 use crate::events::projector::RunProjection;
-use crate::events::{EventRow, NewEvent};
 use anyhow::{Result, bail};
 
-const TERMINAL_EVENTS: [&str; 3] = ["run_completed", "run_failed", "run_cancelled"];
+pub(super) const TERMINAL_EVENTS: [&str; 3] = ["run_completed", "run_failed", "run_cancelled"];
 
-pub fn validate_transition(history: &[EventRow], next: &NewEvent) -> Result<()> {
-    let state = RunProjection::replay(history);
-
-    if state.terminal.is_some() && !TERMINAL_EVENTS.contains(&next.event_type.as_str()) {
+/// Check `next` against the run state projected from every event recorded so far.
+pub fn validate_transition(state: &RunProjection, next: &NewEvent) -> Result<()> {
+    if state.terminal.is_some() {
+        if TERMINAL_EVENTS.contains(&next.event_type.as_str()) {
+            bail!("invalid transition: run terminal event already exists")
+        }
         bail!("invalid transition: run already terminal")
     }
 
-    if TERMINAL_EVENTS.contains(&next.event_type.as_str())
-        && history
-            .iter()
-            .any(|ev| TERMINAL_EVENTS.contains(&ev.event_type.as_str()))
-    {
-        bail!("invalid transition: run terminal event already exists")
-    }
-
     if (next.event_type == "task_claimed" || next.event_type == "merge_succeeded")
         && (state.paused || !state.open_questions.is_empty())
     {
@@ -41,11 +35,25 @@ pub fn validate_transition(history: &[EventRow], next: &NewEvent) -> Result<()>
         {
             bail!("invalid transition: cannot claim before spec approval/unpaused run")
         }
-        if task.closed || task.terminal_failed {
+        if task.closed || task.terminal_failed || task.skipped {
             bail!("invalid transition: task already terminal")
         }
     }
 
+    if next.event_type == "task_skipped" {
+        let task_id = next
+            .task_id
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("task_skipped missing task_id"))?;
+        let task = state
+            .tasks
+            .get(task_id)
+            .ok_or_else(|| anyhow::anyhow!("task_skipped references unknown task '{task_id}'"))?;
+        if task.closed || task.terminal_failed || task.skipped || task.claimed {
+            bail!("invalid transition: only an unclaimed open task can be skipped")
+        }
+    }
+
     if next.event_type == "review_approved" && next.actor_role.as_deref() == Some("implementer") {
         bail!("invalid transition: implementer cannot approve review")
     }
@@ -62,16 +70,27 @@ pub fn validate_transition(history: &[EventRow], next: &NewEvent) -> Result<()>
         let attempt = next
             .attempt
             .ok_or_else(|| anyhow::anyhow!("task_closed missing attempt"))?;
-        let merged = history.iter().any(|ev| {
-            ev.event_type == "merge_succeeded"
-                && ev.task_id.as_deref() == Some(task_id)
-                && ev.attempt == Some(attempt)
-        });
+        let merged = state
+            .tasks
+            .get(task_id)
+            .is_some_and(|task| task.merged_attempts.contains(&attempt));
         if !merged {
             bail!("invalid transition: task_closed requires merge_succeeded for same attempt")
         }
     }
 
+    if next.event_type == "translation_failed" {
+        let has_error = next
+            .payload_json
+            .get("error")
+            .and_then(|v| v.as_str())
+            .map(|e| !e.trim().is_empty())
+            .unwrap_or(false);
+        if !has_error {
+            bail!("invalid transition: translation_failed requires an error detail")
+        }
+    }
+
     if next.event_type == "checks_approved" {
         let has_commands = next
             .payload_json
@@ -90,12 +109,61 @@ pub fn validate_transition(history: &[EventRow], next: &NewEvent) -> Result<()>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::events::EventRow;
     use serde_json::json;
 
     #[test]
     fn checks_approved_requires_non_empty_commands() {
         let next = NewEvent::simple("checks_approved", json!({"commands": []}));
-        let err = validate_transition(&[], &next).unwrap_err();
+        let err = validate_transition(&RunProjection::default(), &next).unwrap_err();
         assert!(format!("{err}").contains("requires non-empty commands"));
     }
+
+    #[test]
+    fn translation_failed_requires_error_detail() {
+        let next = NewEvent::simple("translation_failed", json!({"error": " "}));
+        let err = validate_transition(&RunProjection::default(), &next).unwrap_err();
+        assert!(format!("{err}").contains("requires an error detail"));
+    }
+
+    #[test]
+    fn task_skipped_requires_an_open_task() {
+        let registered = EventRow {
+            seq: 1,
+            run_id: "r".to_string(),
+            ts: "2026-01-01T00:00:00Z".to_string(),
+            event_type: "task_registered".to_string(),
+            task_id: Some("b".to_string()),
+            actor_role: None,
+            actor_id: None,
+            attempt: None,
+            payload_json: json!({"objective": "y"}),
+            dedupe_key: None,
+        };
+        let skip = NewEvent {
+            task_id: Some("b".to_string()),
+            ..NewEvent::simple("task_skipped", json!({"reason": "dependency 'a' failed"}))
+        };
+        validate_transition(
+            &RunProjection::replay(std::slice::from_ref(&registered)),
+            &skip,
+        )
+        .unwrap();
+
+        let skipped = EventRow {
+            seq: 2,
+            event_type: "task_skipped".to_string(),
+            ..registered.clone()
+        };
+        let err =
+            validate_transition(&RunProjection::replay(&[registered, skipped]), &skip).unwrap_err();
+        assert!(format!("{err}").contains("only an unclaimed open task"));
+
+        let unknown = NewEvent {
+            task_id: Some("zz".to_string()),
+            ..skip
+        };
+        let err = validate_transition(&RunProjection::default(), &unknown).unwrap_err();
+        assert!(format!("{err}").contains("unknown task 'zz'"));
+    }
 }
@@ -1,3 +1,232 @@
-pub fn attempt_merge(task_objective: &str, attempt: i64) -> bool {
+use anyhow::{Context, Result, bail};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::{Mutex, PoisonError};
+
+/// Agent bookkeeping files (prompts, logs, results): the `<role>_attempt<N>_*` files providers
+/// write at the worktree root for the roles that run in a task worktree. Never committed or
+/// diffed; anchored so user files with similar names still are.
+const AGENT_ARTIFACT_EXCLUDES: &[&str] = &[
+    ":(exclude,top,glob)implementer_attempt[0-9]*_*",
+    ":(exclude,top,glob)reviewer_attempt[0-9]*_*",
+];
+
+/// A task attempt whose checks passed, waiting for its turn to merge.
+#[derive(Debug, Clone)]
+pub struct MergeRequest {
+    pub task_id: String,
+    pub attempt: i64,
+    pub worktree: PathBuf,
+    pub objective: String,
+}
+
+/// Serializes merges onto the integration branch. Requests may be queued from any thread;
+/// `drain` merges them one at a time in arrival order under a single lock, so attempts that
+/// become merge-ready together never merge concurrently.
+#[derive(Debug, Default)]
+pub struct MergeQueue {
+    pending: Mutex<VecDeque<MergeRequest>>,
+    merging: Mutex<()>,
+}
+
+impl MergeQueue {
+    pub fn enqueue(&self, req: MergeRequest) {
+        self.pending
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push_back(req);
+    }
+
+    /// Hand each queued request to `merge_one` in order, stopping at the first error.
+    pub fn drain(&self, mut merge_one: impl FnMut(MergeRequest) -> Result<()>) -> Result<()> {
+        let _merging = self.merging.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            let next = self
+                .pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .pop_front();
+            let Some(req) = next else {
+                return Ok(());
+            };
+            merge_one(req)?;
+        }
+    }
+}
+
+pub fn simulate_merge(task_objective: &str, attempt: i64) -> bool {
     !(task_objective.contains("[conflict]") && attempt == 1)
 }
+
+/// Commit the task worktree and merge it onto `integration_branch`, which is checked out in
+/// its own worktree at `integration_dir` so the user's checkout is never touched. Returns
+/// `Ok(false)` when git reports a merge conflict. A `repo_root` that is not a git work tree
+/// has nothing to integrate and always merges cleanly.
+pub fn attempt_merge(
+    repo_root: &Path,
+    integration_dir: &Path,
+    integration_branch: &str,
+    worktree: &Path,
+    commit_message: &str,
+) -> Result<bool> {
+    if !super::worktree::is_git_work_tree(repo_root) {
+        return Ok(true);
+    }
+
+    commit_worktree(worktree, commit_message)?;
+    let head = git_stdout(worktree, &["rev-parse", "HEAD"])
+        .with_context(|| format!("resolve HEAD of worktree `{}`", worktree.display()))?;
+    ensure_integration_worktree(repo_root, integration_dir, integration_branch)?;
+
+    let out = git(
+        integration_dir,
+        &["merge", "--no-ff", "--no-edit", "-m", commit_message, &head],
+    )?;
+    if out.status.success() {
+        return Ok(true);
+    }
+    let conflicted = git_stdout(integration_dir, &["diff", "--name-only", "--diff-filter=U"])
+        .map(|files| !files.is_empty())
+        .unwrap_or(false);
+    let _ = git(integration_dir, &["merge", "--abort"]);
+    if conflicted {
+        return Ok(false);
+    }
+    bail!(
+        "git merge of `{}` into {} failed: {}",
+        worktree.display(),
+        integration_branch,
+        String::from_utf8_lossy(&out.stderr).trim()
+    )
+}
+
+/// The attempt's uncommitted changes against `HEAD` as a patch, new files included. Returns
+/// `Ok(None)` when `worktree` is a plain directory rather than a git worktree.
+pub fn worktree_diff(worktree: &Path) -> Result<Option<String>> {
+    if !worktree.join(".git").exists() {
+        return Ok(None);
+    }
+    // Mark new files intent-to-add so they show up in the diff; the merge stages them anyway.
+    let mut add_args = vec!["add", "-A", "-N", "--", "."];
+    add_args.extend_from_slice(AGENT_ARTIFACT_EXCLUDES);
+    run_git(worktree, &add_args)
+        .with_context(|| format!("mark new files in `{}`", worktree.display()))?;
+    let mut diff_args = vec!["diff", "HEAD", "--", "."];
+    diff_args.extend_from_slice(AGENT_ARTIFACT_EXCLUDES);
+    let out = git(worktree, &diff_args)?;
+    if !out.status.success() {
+        bail!(
+            "git diff in `{}` failed: {}",
+            worktree.display(),
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(Some(String::from_utf8_lossy(&out.stdout).into_owned()))
+}
+
+fn commit_worktree(worktree: &Path, message: &str) -> Result<()> {
+    let mut args = vec!["add", "-A", "--", "."];
+    args.extend_from_slice(AGENT_ARTIFACT_EXCLUDES);
+    run_git(worktree, &args)
+        .with_context(|| format!("stage changes in `{}`", worktree.display()))?;
+    let staged = git(worktree, &["diff", "--cached", "--quiet"])?;
+    if staged.status.success() {
+        return Ok(());
+    }
+    run_git(worktree, &["commit", "--no-verify", "-m", message])
+        .with_context(|| format!("commit changes in `{}`", worktree.display()))
+}
+
+fn ensure_integration_worktree(repo_root: &Path, dir: &Path, branch: &str) -> Result<()> {
+    if dir.join(".git").exists() {
+        return Ok(());
+    }
+    let branch_ref = format!("refs/heads/{branch}");
+    if !git(
+        repo_root,
+        &["rev-parse", "--verify", "--quiet", &branch_ref],
+    )?
+    .status
+    .success()
+    {
+        run_git(repo_root, &["branch", branch, "HEAD"])
+            .with_context(|| format!("create integration branch {branch}"))?;
+    }
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create integration parent `{}`", parent.display()))?;
+    }
+    let dir_arg = dir.display().to_string();
+    run_git(repo_root, &["worktree", "add", &dir_arg, branch])
+        .with_context(|| format!("check out integration branch {branch}"))
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args([
+            "-c",
+            "user.name=thence",
+            "-c",
+            "user.email=thence@localhost",
+        ])
+        .args(args)
+        .output()
+        .with_context(|| format!("spawn git {}", args.join(" ")))
+}
+
+fn run_git(dir: &Path, args: &[&str]) -> Result<()> {
+    git_stdout(dir, args).map(|_| ())
+}
+
+fn git_stdout(dir: &Path, args: &[&str]) -> Result<String> {
+    let out = git(dir, args)?;
+    if !out.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn merge_queue_merges_one_request_at_a_time_in_order() {
+        let queue = MergeQueue::default();
+        let in_merge = AtomicBool::new(false);
+        let merged = Mutex::new(Vec::new());
+        std::thread::scope(|scope| {
+            for n in 0..4 {
+                let (queue, in_merge, merged) = (&queue, &in_merge, &merged);
+                scope.spawn(move || {
+                    queue.enqueue(MergeRequest {
+                        task_id: format!("task{n}"),
+                        attempt: 1,
+                        worktree: PathBuf::from(format!("wt{n}")),
+                        objective: String::new(),
+                    });
+                    queue
+                        .drain(|req| {
+                            assert!(!in_merge.swap(true, Ordering::SeqCst), "merges overlapped");
+                            std::thread::sleep(std::time::Duration::from_millis(5));
+                            merged.lock().unwrap().push(req.task_id);
+                            in_merge.store(false, Ordering::SeqCst);
+                            Ok(())
+                        })
+                        .unwrap();
+                });
+            }
+        });
+        let mut merged = merged.into_inner().unwrap();
+        merged.sort();
+        assert_eq!(merged, vec!["task0", "task1", "task2", "task3"]);
+    }
+}
@@ -1,26 +1,154 @@
 use crate::config::{ProvisionMode, ProvisionedFile};
 use anyhow::{Context, Result, bail};
 use std::path::{Component, Path, PathBuf};
+use std::process::Command;
 
+/// Create the attempt worktree under `run_dir`; `base` is the repository the worktree is
+/// checked out from. Once `integration_branch` exists the attempt starts from its tip, so it
+/// sees the work already merged; before that it starts from `HEAD`.
 pub fn prepare_worktree(
     base: &Path,
-    run_id: &str,
+    run_dir: &Path,
     task_id: &str,
     attempt: i64,
     worker_id: &str,
+    integration_branch: &str,
     provision_files: &[ProvisionedFile],
 ) -> Result<PathBuf> {
-    let dir = base
-        .join(".thence")
-        .join("runs")
-        .join(run_id)
+    let dir = run_dir
         .join("worktrees")
         .join(format!("thence/{task_id}/v{attempt}/{worker_id}"));
-    std::fs::create_dir_all(&dir)?;
+    if is_git_work_tree(base) {
+        add_git_worktree(base, &dir, &start_point(base, integration_branch))?;
+    } else {
+        std::fs::create_dir_all(&dir)?;
+    }
     materialize_provisioned_files(&dir, provision_files)?;
     Ok(dir)
 }
 
+/// Remove an attempt worktree, unregistering it from git when it was created as a git worktree.
+pub fn remove_worktree(base: &Path, dir: &Path) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    if dir.join(".git").is_file() {
+        let out = Command::new("git")
+            .arg("-C")
+            .arg(base)
+            .args(["worktree", "remove", "--force"])
+            .arg(dir)
+            .output()
+            .with_context(|| format!("spawn git worktree remove for `{}`", dir.display()))?;
+        if out.status.success() {
+            return Ok(());
+        }
+    }
+    std::fs::remove_dir_all(dir).with_context(|| format!("remove worktree `{}`", dir.display()))
+}
+
+/// Copy an attempt worktree to `dest` so it survives cleanup and later attempts. The git
+/// link file is left behind; the copy is a plain snapshot of the files.
+pub fn preserve_worktree(dir: &Path, dest: &Path) -> Result<()> {
+    std::fs::create_dir_all(dest)
+        .with_context(|| format!("create preserved worktree `{}`", dest.display()))?;
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("read worktree `{}`", dir.display()))?
+    {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let from = entry.path();
+        let to = dest.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            preserve_worktree(&from, &to)?;
+        } else if file_type.is_symlink() {
+            create_symlink(&std::fs::read_link(&from)?, &to)
+                .with_context(|| format!("copy symlink `{}`", from.display()))?;
+        } else {
+            std::fs::copy(&from, &to).with_context(|| format!("copy `{}`", from.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy the agent logs and prompt/result files (`<role>_attempt<N>_*`) at the root of an
+/// attempt worktree to `dest`, so they outlive the worktree.
+pub fn preserve_attempt_logs(dir: &Path, dest: &Path) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("read worktree `{}`", dir.display()))?
+    {
+        let entry = entry?;
+        let name = entry.file_name();
+        if !entry.file_type()?.is_file() || !name.to_string_lossy().contains("_attempt") {
+            continue;
+        }
+        std::fs::create_dir_all(dest)
+            .with_context(|| format!("create attempt log dir `{}`", dest.display()))?;
+        std::fs::copy(entry.path(), dest.join(&name))
+            .with_context(|| format!("copy `{}`", entry.path().display()))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn is_git_work_tree(base: &Path) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(base)
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .output()
+        .map(|out| out.status.success() && String::from_utf8_lossy(&out.stdout).trim() == "true")
+        .unwrap_or(false)
+}
+
+fn start_point(base: &Path, integration_branch: &str) -> String {
+    let branch_ref = format!("refs/heads/{integration_branch}");
+    let exists = Command::new("git")
+        .arg("-C")
+        .arg(base)
+        .args(["rev-parse", "--verify", "--quiet", &branch_ref])
+        .output()
+        .map(|out| out.status.success())
+        .unwrap_or(false);
+    if exists {
+        branch_ref
+    } else {
+        "HEAD".to_string()
+    }
+}
+
+fn add_git_worktree(base: &Path, dir: &Path, start_point: &str) -> Result<()> {
+    // A resumed attempt may find its worktree already checked out.
+    if dir.join(".git").exists() {
+        return Ok(());
+    }
+    if let Some(parent) = dir.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("create worktree parent `{}`", parent.display()))?;
+    }
+    let out = Command::new("git")
+        .arg("-C")
+        .arg(base)
+        .args(["worktree", "add", "--detach"])
+        .arg(dir)
+        .arg(start_point)
+        .output()
+        .with_context(|| format!("spawn git worktree add for `{}`", dir.display()))?;
+    if !out.status.success() {
+        bail!(
+            "git worktree add `{}` failed: {}",
+            dir.display(),
+            String::from_utf8_lossy(&out.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
 fn materialize_provisioned_files(worktree_dir: &Path, files: &[ProvisionedFile]) -> Result<()> {
     for (idx, file) in files.iter().enumerate() {
         if !file.from.exists() {
@@ -48,7 +176,7 @@ fn materialize_provisioned_files(worktree_dir: &Path, files: &[ProvisionedFile])
             );
         }
 
-        let dest_rel = sanitize_relative_path(&file.to).with_context(|| {
+        let dest_rel = sanitize_destination_path(&file.to).with_context(|| {
             format!("invalid destination path for provision rule index {}", idx)
         })?;
         let dest = worktree_dir.join(dest_rel);
@@ -84,19 +212,27 @@ fn materialize_provisioned_files(worktree_dir: &Path, files: &[ProvisionedFile])
     Ok(())
 }
 
-fn sanitize_relative_path(path: &Path) -> Result<PathBuf> {
+/// Normalize a path that must stay inside a worktree: absolute paths and `..` are rejected and
+/// `.` components dropped, so the result may be empty. `what` names the path in errors.
+pub(crate) fn sanitize_relative_path(path: &Path, what: &str) -> Result<PathBuf> {
     if path.is_absolute() {
-        bail!("destination path must be relative");
+        bail!("{what} must be relative");
     }
     let mut clean = PathBuf::new();
     for component in path.components() {
         match component {
             Component::Normal(part) => clean.push(part),
             Component::CurDir => {}
-            Component::ParentDir => bail!("destination path must not contain `..`"),
-            Component::RootDir | Component::Prefix(_) => bail!("destination path must be relative"),
+            Component::ParentDir => bail!("{what} must not contain `..`"),
+            Component::RootDir | Component::Prefix(_) => bail!("{what} must be relative"),
         }
     }
+    Ok(clean)
+}
+
+/// A provisioned file's destination: a non-empty path relative to the worktree.
+pub(crate) fn sanitize_destination_path(path: &Path) -> Result<PathBuf> {
+    let clean = sanitize_relative_path(path, "destination path")?;
     if clean.as_os_str().is_empty() {
         bail!("destination path is empty");
     }
@@ -141,6 +277,84 @@ mod tests {
         }
     }
 
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn git_repo_base_gets_detached_worktree() {
+        let tmp = tempdir().unwrap();
+        let repo = tmp.path();
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.email", "thence@example.com"]);
+        git(repo, &["config", "user.name", "thence"]);
+        std::fs::write(repo.join("README.md"), "hello\n").unwrap();
+        git(repo, &["add", "README.md"]);
+        git(repo, &["commit", "-q", "-m", "init"]);
+
+        let runs = tempdir().unwrap();
+        let dir = prepare_worktree(
+            repo,
+            &runs.path().join("run-1"),
+            "task-a",
+            1,
+            "impl-1",
+            "thence/run-1",
+            &[],
+        )
+        .unwrap();
+
+        assert!(dir.starts_with(runs.path()));
+        assert_eq!(
+            std::fs::read_to_string(dir.join("README.md")).unwrap(),
+            "hello\n"
+        );
+        assert!(dir.join(".git").is_file());
+    }
+
+    #[test]
+    fn preserved_worktree_copies_files_but_not_git_link() {
+        let tmp = tempdir().unwrap();
+        let dir = tmp.path().join("wt");
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join(".git"), "gitdir: elsewhere").unwrap();
+        std::fs::write(dir.join("src").join("lib.rs"), "broken").unwrap();
+
+        let dest = tmp.path().join("failed").join("task-a").join("1");
+        preserve_worktree(&dir, &dest).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dest.join("src").join("lib.rs")).unwrap(),
+            "broken"
+        );
+        assert!(!dest.join(".git").exists());
+    }
+
+    #[test]
+    fn non_git_base_falls_back_to_plain_directory() {
+        let tmp = tempdir().unwrap();
+
+        let runs = tmp.path().join(".thence").join("runs");
+        let dir = prepare_worktree(
+            tmp.path(),
+            &runs.join("run-1"),
+            "task-a",
+            1,
+            "impl-1",
+            "thence/run-1",
+            &[],
+        )
+        .unwrap();
+
+        assert!(dir.is_dir());
+        assert!(!dir.join(".git").exists());
+    }
+
     #[test]
     fn creates_symlink_destination() {
         let tmp = tempdir().unwrap();
@@ -1,22 +1,31 @@
-use crate::workers::provider::{AgentProvider, AgentRequest, AgentResult};
+use crate::logging::diag;
+use crate::workers::provider::{
+    AgentProvider, AgentRequest, AgentResult, INTERRUPTED_EXIT_CODE, TIMEOUT_EXIT_CODE,
+    agent_log_paths,
+};
 use anyhow::{Context, Result, bail};
 use serde_json::json;
 use std::fs;
-use std::io::Read;
-use std::process::{Command, Stdio};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
 use std::thread;
 use std::time::{Duration, Instant};
 
 const CODEX_SETUP_ERROR: &str = "Non-simulated runs require a runnable codex command. Install codex or set `[agent].command` in `.thence/config.toml`.";
 
+/// First backoff before re-spawning an agent whose process failed to start; doubles per retry.
+const SPAWN_RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
 #[derive(Debug)]
 pub struct CodexProvider {
     simulate: bool,
     command: Option<String>,
+    spawn_retries: u32,
 }
 
 impl CodexProvider {
-    pub fn new(simulate: bool, command: Option<&str>) -> Result<Self> {
+    pub fn new(simulate: bool, command: Option<&str>, spawn_retries: u32) -> Result<Self> {
         let resolved = if simulate {
             None
         } else {
@@ -25,6 +34,7 @@ impl CodexProvider {
         Ok(Self {
             simulate,
             command: resolved,
+            spawn_retries,
         })
     }
 }
@@ -38,18 +48,13 @@ impl AgentProvider for CodexProvider {
             .command
             .as_deref()
             .ok_or_else(|| anyhow::anyhow!(CODEX_SETUP_ERROR))?;
-        run_subprocess_agent(cmd, "codex", req)
+        run_subprocess_agent(cmd, "codex", self.spawn_retries, req)
     }
 }
 
-fn run_stub_agent(provider_name: &str, req: AgentRequest) -> Result<AgentResult> {
+pub(crate) fn run_stub_agent(provider_name: &str, req: AgentRequest) -> Result<AgentResult> {
     fs::create_dir_all(&req.worktree_path)?;
-    let stdout_path = req
-        .worktree_path
-        .join(format!("{}_attempt{}_stdout.log", req.role, req.attempt));
-    let stderr_path = req
-        .worktree_path
-        .join(format!("{}_attempt{}_stderr.log", req.role, req.attempt));
+    let (stdout_path, stderr_path) = agent_log_paths(&req.worktree_path, &req.role, req.attempt);
     let metadata_path = req
         .worktree_path
         .join(format!("{}_attempt{}_meta.log", req.role, req.attempt));
@@ -61,6 +66,7 @@ fn run_stub_agent(provider_name: &str, req: AgentRequest) -> Result<AgentResult>
         ),
     )?;
     fs::write(&stderr_path, "")?;
+
     fs::write(
         &metadata_path,
         format!(
@@ -89,6 +95,16 @@ fn run_stub_agent(provider_name: &str, req: AgentRequest) -> Result<AgentResult>
         }))
     } else if req.role == "plan-translator" {
         stub_plan_translation(&req.prompt).ok()
+    } else if req.role == "spec-reviewer" {
+        if req.prompt.contains("???") || req.prompt.contains("[QUESTION]") {
+            Some(json!({
+                "approved": false,
+                "question_id": "spec-q-1",
+                "question": "Spec contains ambiguity marker (??? or [QUESTION]). Please clarify expected behavior."
+            }))
+        } else {
+            Some(json!({"approved": true}))
+        }
     } else {
         Some(json!({"submitted": true}))
     };
@@ -101,20 +117,23 @@ fn run_stub_agent(provider_name: &str, req: AgentRequest) -> Result<AgentResult>
         } else {
             0
         },
+        timed_out: false,
         stdout_path,
         stderr_path,
         structured_output: structured,
+        agent_model: None,
+        agent_version: None,
     })
 }
 
-fn run_subprocess_agent(cmd: &str, provider_name: &str, req: AgentRequest) -> Result<AgentResult> {
+pub(crate) fn run_subprocess_agent(
+    cmd: &str,
+    provider_name: &str,
+    spawn_retries: u32,
+    req: AgentRequest,
+) -> Result<AgentResult> {
     fs::create_dir_all(&req.worktree_path)?;
-    let stdout_path = req
-        .worktree_path
-        .join(format!("{}_attempt{}_stdout.log", req.role, req.attempt));
-    let stderr_path = req
-        .worktree_path
-        .join(format!("{}_attempt{}_stderr.log", req.role, req.attempt));
+    let (stdout_path, stderr_path) = agent_log_paths(&req.worktree_path, &req.role, req.attempt);
     let prompt_path = req
         .worktree_path
         .join(format!("{}_attempt{}_prompt.json", req.role, req.attempt));
@@ -128,8 +147,6 @@ fn run_subprocess_agent(cmd: &str, provider_name: &str, req: AgentRequest) -> Re
     fs::write(&prompt_path, &req.prompt)
         .with_context(|| format!("write prompt file for {} attempt {}", req.role, req.attempt))?;
 
-    let stdout_file = fs::File::create(&stdout_path)?;
-    let stderr_file = fs::File::create(&stderr_path)?;
     let mut command = Command::new("sh");
     command
         .arg("-lc")
@@ -142,31 +159,58 @@ fn run_subprocess_agent(cmd: &str, provider_name: &str, req: AgentRequest) -> Re
         .env("THENCE_WORKTREE", &req.worktree_path)
         .env("THENCE_PROMPT_FILE", &prompt_path)
         .env("THENCE_RESULT_FILE", &result_path)
-        .env("THENCE_TIMEOUT_SECS", req.timeout.as_secs().to_string())
-        .stdout(Stdio::from(stdout_file))
-        .stderr(Stdio::from(stderr_file));
+        .env("THENCE_TIMEOUT_SECS", req.timeout.as_secs().to_string());
     for (k, v) in &req.env {
         command.env(k, v);
     }
 
-    let mut child = command
-        .spawn()
-        .with_context(|| format!("spawn subprocess provider command for {}", req.role))?;
+    let attach = diag::attached();
+    let mut child = spawn_with_retry(&mut command, spawn_retries, SPAWN_RETRY_BASE_DELAY, || {
+        if attach {
+            Ok((Stdio::piped(), Stdio::piped()))
+        } else {
+            command_output_files(&stdout_path, &stderr_path)
+        }
+    })
+    .with_context(|| format!("spawn subprocess provider command for {}", req.role))?;
+    let mut tees = Vec::new();
+    if attach {
+        let prefix = format!("[{} {}#{}]", req.role, req.task_id, req.attempt);
+        if let Some(stdout) = child.stdout.take() {
+            tees.push(tee_to_stderr(stdout, &stdout_path, prefix.clone())?);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tees.push(tee_to_stderr(stderr, &stderr_path, prefix)?);
+        }
+    }
 
     let started = Instant::now();
     let mut timed_out = false;
+    let mut interrupted = false;
     let exit_code = loop {
         if let Some(status) = child.try_wait()? {
             break status.code().unwrap_or(1);
         }
+        if crate::run::stop_requested() {
+            interrupted = true;
+            let _ = child.kill();
+            let _ = child.wait()?;
+            break INTERRUPTED_EXIT_CODE;
+        }
         if started.elapsed() >= req.timeout {
             timed_out = true;
             let _ = child.kill();
             let status = child.wait()?;
-            break status.code().unwrap_or(124);
+            break status.code().unwrap_or(TIMEOUT_EXIT_CODE);
         }
         thread::sleep(Duration::from_millis(100));
     };
+    // A killed agent may leave descendants holding the pipes open; don't wait on them.
+    if !timed_out && !interrupted {
+        for tee in tees {
+            let _ = tee.join();
+        }
+    }
 
     let structured_output = if result_path.exists() {
         let raw = fs::read_to_string(&result_path)
@@ -177,28 +221,100 @@ fn run_subprocess_agent(cmd: &str, provider_name: &str, req: AgentRequest) -> Re
         fs::File::open(&stdout_path)?.read_to_string(&mut stdout_raw)?;
         serde_json::from_str(&stdout_raw).ok()
     };
+    let agent_model = reported_string(structured_output.as_ref(), "agent_model");
+    let agent_version = reported_string(structured_output.as_ref(), "agent_version");
 
     fs::write(
         &metadata_path,
         format!(
-            "provider={}\nmode=subprocess\ncommand={}\ntimeout_secs={}\ntimed_out={}\nprompt_file={}\nresult_file={}\n",
+            "provider={}\nmode=subprocess\ncommand={}\ntimeout_secs={}\ntimed_out={}\ninterrupted={}\nprompt_file={}\nresult_file={}\n",
             provider_name,
             cmd,
             req.timeout.as_secs(),
             timed_out,
+            interrupted,
             prompt_path.display(),
             result_path.display(),
         ),
     )?;
 
     Ok(AgentResult {
-        exit_code: if timed_out { 124 } else { exit_code },
+        exit_code: if timed_out {
+            TIMEOUT_EXIT_CODE
+        } else {
+            exit_code
+        },
+        timed_out,
         stdout_path,
         stderr_path,
         structured_output,
+        agent_model,
+        agent_version,
     })
 }
 
+fn reported_string(output: Option<&serde_json::Value>, key: &str) -> Option<String> {
+    output?
+        .get(key)?
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(ToString::to_string)
+}
+
+/// Spawn `command`, retrying only failures to start the process (e.g. `EAGAIN` from fork) with
+/// exponential backoff. Once the child is running its exit status is never retried here.
+/// `stdio` is called per attempt so each try gets fresh output handles.
+fn spawn_with_retry(
+    command: &mut Command,
+    retries: u32,
+    base_delay: Duration,
+    mut stdio: impl FnMut() -> Result<(Stdio, Stdio)>,
+) -> Result<Child> {
+    let mut attempt = 0;
+    loop {
+        let (stdout, stderr) = stdio()?;
+        match command.stdout(stdout).stderr(stderr).spawn() {
+            Ok(child) => return Ok(child),
+            Err(_) if attempt < retries => {
+                thread::sleep(base_delay * 2u32.pow(attempt));
+                attempt += 1;
+            }
+            Err(err) => {
+                return Err(err)
+                    .with_context(|| format!("spawn failed after {} attempts", attempt + 1));
+            }
+        }
+    }
+}
+
+/// Copy an agent output pipe into its log file on a reader thread, echoing each line to stderr
+/// under `prefix`. The thread ends when the agent closes the pipe.
+fn tee_to_stderr(
+    pipe: impl Read + Send + 'static,
+    log_path: &Path,
+    prefix: String,
+) -> Result<thread::JoinHandle<()>> {
+    let mut log = fs::File::create(log_path)
+        .with_context(|| format!("create agent log {}", log_path.display()))?;
+    Ok(thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut line = Vec::new();
+        while matches!(reader.read_until(b'\n', &mut line), Ok(n) if n > 0) {
+            let _ = log.write_all(&line);
+            diag::agent_output(&prefix, &String::from_utf8_lossy(&line));
+            line.clear();
+        }
+    }))
+}
+
+fn command_output_files(stdout_path: &Path, stderr_path: &Path) -> Result<(Stdio, Stdio)> {
+    Ok((
+        Stdio::from(fs::File::create(stdout_path)?),
+        Stdio::from(fs::File::create(stderr_path)?),
+    ))
+}
+
 fn resolve_agent_cmd(command: Option<&str>) -> Result<String> {
     let cmd = command.unwrap_or("codex").trim().to_string();
     if cmd.is_empty() {
@@ -212,7 +328,7 @@ fn resolve_agent_cmd(command: Option<&str>) -> Result<String> {
     Ok(cmd)
 }
 
-fn is_runnable(executable: &str) -> bool {
+pub(crate) fn is_runnable(executable: &str) -> bool {
     let quoted = shell_quote(executable);
     match Command::new("sh")
         .arg("-lc")
@@ -257,17 +373,95 @@ fn stub_plan_translation(prompt: &str) -> Result<serde_json::Value> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::workers::provider::AgentOutcome;
 
     #[test]
     fn simulate_mode_allows_stub_without_command() {
-        let provider = CodexProvider::new(true, None);
+        let provider = CodexProvider::new(true, None, 0);
         assert!(provider.is_ok());
     }
 
     #[test]
     fn non_simulated_mode_requires_runnable_command() {
-        let err = CodexProvider::new(false, Some("this-command-does-not-exist-xyz"));
+        let err = CodexProvider::new(false, Some("this-command-does-not-exist-xyz"), 0);
         assert!(err.is_err());
         assert!(format!("{}", err.unwrap_err()).contains("Install codex or set `[agent].command`"));
     }
+
+    #[test]
+    fn timeout_is_reported_apart_from_a_genuine_exit_124() {
+        let tmp = tempfile::tempdir().unwrap();
+        let run = |cmd: &str, timeout: Duration| {
+            run_subprocess_agent(
+                cmd,
+                "codex",
+                0,
+                AgentRequest {
+                    role: "implementer".to_string(),
+                    task_id: "t1".to_string(),
+                    attempt: 1,
+                    worktree_path: tmp.path().to_path_buf(),
+                    prompt: "{}".to_string(),
+                    env: Vec::new(),
+                    timeout,
+                },
+            )
+            .unwrap()
+        };
+
+        let slow = run("sleep 5", Duration::from_millis(300));
+        assert!(slow.timed_out);
+        assert_eq!(slow.exit_code, TIMEOUT_EXIT_CODE);
+        assert_eq!(slow.outcome(), AgentOutcome::TimedOut);
+
+        let exits_124 = run("exit 124", Duration::from_secs(30));
+        assert!(!exits_124.timed_out);
+        assert_eq!(exits_124.outcome(), AgentOutcome::Exited(124));
+    }
+
+    #[test]
+    fn spawn_failure_is_retried_until_the_program_appears() {
+        let tmp = tempfile::tempdir().unwrap();
+        let program = tmp.path().join("late-agent.sh");
+        let out = tmp.path().join("out.log");
+        let err = tmp.path().join("err.log");
+
+        let writer = {
+            let program = program.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(50));
+                let staged = program.with_extension("tmp");
+                fs::write(&staged, "#!/bin/sh\necho spawned\n").unwrap();
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    fs::set_permissions(&staged, fs::Permissions::from_mode(0o755)).unwrap();
+                }
+                fs::rename(&staged, &program).unwrap();
+            })
+        };
+
+        let mut command = Command::new(&program);
+        let status = spawn_with_retry(&mut command, 3, Duration::from_millis(200), || {
+            command_output_files(&out, &err)
+        })
+        .unwrap()
+        .wait()
+        .unwrap();
+        writer.join().unwrap();
+
+        assert!(status.success());
+        assert_eq!(fs::read_to_string(&out).unwrap(), "spawned\n");
+    }
+
+    #[test]
+    fn spawn_failure_surfaces_after_retries_are_exhausted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut command = Command::new(tmp.path().join("never-created"));
+        let err = spawn_with_retry(&mut command, 1, Duration::from_millis(1), || {
+            command_output_files(&tmp.path().join("out.log"), &tmp.path().join("err.log"))
+        })
+        .unwrap_err();
+        assert!(format!("{err}").contains("spawn failed after 2 attempts"));
+    }
 }
@@ -1,3 +1,4 @@
 pub mod codex;
+pub mod opencode;
 pub mod provider;
 pub mod reviewer;
@@ -0,0 +1,79 @@
+use crate::workers::codex::{is_runnable, run_stub_agent, run_subprocess_agent};
+use crate::workers::provider::{AgentProvider, AgentRequest, AgentResult};
+use anyhow::{Result, bail};
+
+const OPENCODE_SETUP_ERROR: &str = "Non-simulated opencode runs require a runnable opencode command. Install opencode or set `[agent].command` in `.thence/config.toml`.";
+
+/// opencode takes its message as an argument to `opencode run`, so the default command inlines
+/// the prompt file; replies are read from `THENCE_RESULT_FILE` or, failing that, stdout.
+const OPENCODE_DEFAULT_COMMAND: &str = "opencode run \"$(cat \"$THENCE_PROMPT_FILE\")\"";
+
+#[derive(Debug)]
+pub struct OpencodeProvider {
+    simulate: bool,
+    command: Option<String>,
+    spawn_retries: u32,
+}
+
+impl OpencodeProvider {
+    pub fn new(simulate: bool, command: Option<&str>, spawn_retries: u32) -> Result<Self> {
+        let resolved = if simulate {
+            None
+        } else {
+            Some(resolve_opencode_cmd(command)?)
+        };
+        Ok(Self {
+            simulate,
+            command: resolved,
+            spawn_retries,
+        })
+    }
+}
+
+impl AgentProvider for OpencodeProvider {
+    fn run(&self, req: AgentRequest) -> Result<AgentResult> {
+        if self.simulate {
+            return run_stub_agent("opencode", req);
+        }
+        let cmd = self
+            .command
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!(OPENCODE_SETUP_ERROR))?;
+        run_subprocess_agent(cmd, "opencode", self.spawn_retries, req)
+    }
+}
+
+fn resolve_opencode_cmd(command: Option<&str>) -> Result<String> {
+    let cmd = command
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .unwrap_or(OPENCODE_DEFAULT_COMMAND)
+        .to_string();
+    let executable = cmd.split_whitespace().next().unwrap_or("");
+    if executable.is_empty() || !is_runnable(executable) {
+        bail!(OPENCODE_SETUP_ERROR);
+    }
+    Ok(cmd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simulate_mode_allows_stub_without_command() {
+        assert!(OpencodeProvider::new(true, None, 0).is_ok());
+    }
+
+    #[test]
+    fn configured_command_replaces_default_invocation() {
+        assert_eq!(
+            resolve_opencode_cmd(Some(" sh -c true ")).unwrap(),
+            "sh -c true"
+        );
+        let err = resolve_opencode_cmd(Some("definitely-not-a-real-opencode-binary"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("runnable opencode command"));
+    }
+}
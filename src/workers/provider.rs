@@ -1,6 +1,6 @@
 use anyhow::{Result, bail};
 use serde_json::Value;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 #[derive(Debug, Clone)]
@@ -17,12 +17,63 @@ pub struct AgentRequest {
 #[derive(Debug, Clone)]
 pub struct AgentResult {
     pub exit_code: i32,
+    /// The agent was killed for exceeding its request timeout; `exit_code` is then
+    /// `TIMEOUT_EXIT_CODE`, which an agent may also return on its own.
+    pub timed_out: bool,
     pub stdout_path: PathBuf,
     pub stderr_path: PathBuf,
     pub structured_output: Option<Value>,
+    /// Optional `agent_model` the agent reported in its result JSON.
+    pub agent_model: Option<String>,
+    /// Optional `agent_version` the agent reported in its result JSON.
+    pub agent_version: Option<String>,
 }
 
-pub trait AgentProvider {
+/// How an agent call ended, telling a timeout apart from an agent that exited 124 itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentOutcome {
+    Exited(i32),
+    TimedOut,
+}
+
+impl AgentResult {
+    pub fn outcome(&self) -> AgentOutcome {
+        if self.timed_out {
+            AgentOutcome::TimedOut
+        } else {
+            AgentOutcome::Exited(self.exit_code)
+        }
+    }
+}
+
+/// Where providers capture an agent's stdout and stderr for a given role and attempt.
+pub fn agent_log_paths(worktree: &Path, role: &str, attempt: i64) -> (PathBuf, PathBuf) {
+    (
+        worktree.join(format!("{role}_attempt{attempt}_stdout.log")),
+        worktree.join(format!("{role}_attempt{attempt}_stderr.log")),
+    )
+}
+
+/// Exit code reported for an agent killed after exceeding its request timeout.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Exit code reported for an agent killed because the supervisor was interrupted (SIGINT).
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Providers with a real adapter behind `provider_for`.
+pub const SUPPORTED_PROVIDERS: &[&str] = &["codex", "opencode"];
+
+pub fn ensure_supported_provider(name: &str) -> Result<()> {
+    if !SUPPORTED_PROVIDERS.contains(&name) {
+        bail!(
+            "unsupported agent provider `{name}`; expected one of: {}",
+            SUPPORTED_PROVIDERS.join(", ")
+        );
+    }
+    Ok(())
+}
+
+pub trait AgentProvider: Send + Sync {
     fn run(&self, req: AgentRequest) -> Result<AgentResult>;
 }
 
@@ -30,11 +81,19 @@ pub fn provider_for(
     name: &str,
     simulate: bool,
     command: Option<&str>,
+    spawn_retries: u32,
 ) -> Result<Box<dyn AgentProvider>> {
-    if name != "codex" {
-        bail!("only `codex` supported in this version");
-    }
-    Ok(Box::new(crate::workers::codex::CodexProvider::new(
-        simulate, command,
-    )?))
+    ensure_supported_provider(name)?;
+    Ok(match name {
+        "opencode" => Box::new(crate::workers::opencode::OpencodeProvider::new(
+            simulate,
+            command,
+            spawn_retries,
+        )?),
+        _ => Box::new(crate::workers::codex::CodexProvider::new(
+            simulate,
+            command,
+            spawn_retries,
+        )?),
+    })
 }
@@ -1,6 +1,67 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Finding {
     pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u64>,
+    /// `Some(false)` marks an advisory finding that does not need another attempt; findings
+    /// are blocking unless the reviewer says otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocking: Option<bool>,
+}
+
+/// A finding as a reviewer may report it: a bare string (the original contract) or a table.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum ReportedFinding {
+    Text(String),
+    Structured(Finding),
+}
+
+impl Finding {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            severity: None,
+            file: None,
+            line: None,
+            blocking: None,
+        }
+    }
+
+    /// Trim every field, dropping empty optional ones; `None` when the message is blank.
+    pub fn normalized(reported: ReportedFinding) -> Option<Self> {
+        let finding = match reported {
+            ReportedFinding::Text(message) => Finding::new(message),
+            ReportedFinding::Structured(finding) => finding,
+        };
+        let trim = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+        let message = finding.message.trim().to_string();
+        if message.is_empty() {
+            return None;
+        }
+        Some(Self {
+            message,
+            severity: trim(finding.severity),
+            file: trim(finding.file),
+            line: finding.line,
+            blocking: finding.blocking,
+        })
+    }
+
+    pub fn is_structured(&self) -> bool {
+        self.severity.is_some()
+            || self.file.is_some()
+            || self.line.is_some()
+            || self.blocking.is_some()
+    }
+
+    pub fn is_advisory(&self) -> bool {
+        self.blocking == Some(false)
+    }
 }
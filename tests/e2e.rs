@@ -1,8 +1,16 @@
 use std::fs;
 use tempfile::tempdir;
-use thence::events::NewEvent;
+use thence::events::projector::RunProjection;
 use thence::events::store::{EventStore, RunRow};
-use thence::run::{RunCommand, answer_question, execute_run, list_questions, resume_run};
+use thence::events::{EventRow, NewEvent, attempt_dedupe_key};
+use thence::logging::ndjson::LogFormat;
+use thence::run::{
+    APPROVE_ANSWER, RunCommand, answer_question, cancel_run, delete_run, diff_run_outcomes, doctor,
+    execute_run, execute_run_from_reader, inspect_run_json, list_questions, list_runs,
+    questions_json, read_capsule, reject_question, replay_status_report, resume_run,
+    run_doctor_checks, run_timeline, status_report, tail_events, validate_plan,
+    write_events_export, write_resumable_runs,
+};
 
 fn test_run_id(prefix: &str) -> String {
     format!("{}-{}", prefix, uuid::Uuid::new_v4())
@@ -32,8 +40,10 @@ fn end_to_end_happy_path_completes() {
         workers: 2,
         reviewers: 1,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -41,7 +51,19 @@ fn end_to_end_happy_path_completes() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
@@ -59,25 +81,27 @@ fn end_to_end_happy_path_completes() {
 }
 
 #[test]
-fn prose_spec_translates_and_completes() {
+fn timeline_reports_happy_path_phases() {
     let tmp = tempdir().unwrap();
-    let plan_path = tmp.path().join("spec.md");
+    let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
     fs::write(
         &plan_path,
-        "# Feature Spec\nImplement a tiny auth flow with validation.\nInclude implementation and review quality checks.",
+        "- [ ] task-a: implement feature\n- [ ] task-b: verify behavior | deps=task-a",
     )
     .unwrap();
 
-    let run_id = test_run_id("prose");
+    let run_id = test_run_id("timeline");
     execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
         workers: 2,
         reviewers: 1,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -85,40 +109,62 @@ fn prose_spec_translates_and_completes() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-    assert!(events.iter().any(|e| e.event_type == "plan_translated"));
-    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let timeline = run_timeline(&run_id, Some(db_path)).unwrap();
+    assert_eq!(timeline.len(), events.len());
+    let position = |event_type: &str| {
+        timeline
+            .iter()
+            .position(|line| line.split_whitespace().nth(2) == Some(event_type))
+            .unwrap_or_else(|| panic!("{event_type} missing from timeline: {timeline:#?}"))
+    };
+    assert_eq!(position("run_started"), 0);
+    assert!(position("run_started") < position("plan_translated"));
+    assert!(position("plan_translated") < position("run_completed"));
+    assert!(timeline[0].trim_start().starts_with("+0.000s #"));
 }
 
 #[test]
-fn config_only_checks_resolves_without_question_pause() {
+fn higher_priority_task_is_claimed_first() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
-    write_repo_config(
-        tmp.path(),
-        r#"
-version = 2
-[checks]
-commands = ["true"]
-"#,
-    );
+    fs::write(
+        &plan_path,
+        "- [ ] aaa: routine cleanup\n- [ ] zzz: critical path | prio=5",
+    )
+    .unwrap();
 
-    let run_id = test_run_id("config-checks");
+    let run_id = test_run_id("priority");
     execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
         workers: 1,
         reviewers: 1,
-        checks: None,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -126,7 +172,19 @@ commands = ["true"]
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
@@ -134,40 +192,41 @@ commands = ["true"]
         .unwrap()
         .list_events(&run_id)
         .unwrap();
-    assert!(events.iter().any(|e| e.event_type == "checks_approved"));
-    assert!(
-        events
-            .iter()
-            .all(|e| e.event_type != "checks_question_opened")
-    );
-    assert!(events.iter().all(|e| e.event_type != "checks_proposed"));
-    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+    let claimed = events
+        .iter()
+        .filter(|e| e.event_type == "task_claimed")
+        .filter_map(|e| e.task_id.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(claimed, vec!["zzz", "aaa"]);
+    let registered = events
+        .iter()
+        .find(|e| e.event_type == "task_registered" && e.task_id.as_deref() == Some("zzz"))
+        .unwrap();
+    assert_eq!(registered.payload_json["priority"], 5);
 }
 
 #[test]
-fn cli_checks_override_config_checks() {
+fn independent_tasks_are_claimed_concurrently() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
-    write_repo_config(
-        tmp.path(),
-        r#"
-version = 2
-[checks]
-commands = ["false"]
-"#,
-    );
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: implement other feature",
+    )
+    .unwrap();
 
-    let run_id = test_run_id("cli-over-config");
+    let run_id = test_run_id("concurrent");
     execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
-        workers: 1,
-        reviewers: 1,
+        workers: 2,
+        reviewers: 2,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -175,7 +234,19 @@ commands = ["false"]
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
@@ -183,113 +254,184 @@ commands = ["false"]
         .unwrap()
         .list_events(&run_id)
         .unwrap();
-    let checks_event = events
+    let claimed = events
         .iter()
-        .find(|e| e.event_type == "checks_approved")
-        .expect("missing checks_approved");
-    let commands = checks_event
-        .payload_json
-        .get("commands")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-    assert_eq!(commands, vec![serde_json::json!("true")]);
+        .filter(|e| e.event_type == "task_claimed")
+        .collect::<Vec<_>>();
+    assert_eq!(claimed.len(), 2);
+    assert_ne!(claimed[0].actor_id, claimed[1].actor_id);
+    let last_claim_seq = claimed.iter().map(|e| e.seq).max().unwrap();
+    let first_close_seq = events
+        .iter()
+        .filter(|e| e.event_type == "task_closed")
+        .map(|e| e.seq)
+        .min()
+        .expect("missing task_closed");
+    assert!(last_claim_seq < first_close_seq);
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
 }
 
 #[test]
-fn non_codex_agent_is_rejected() {
+fn replay_to_seq_reconstructs_earlier_task_state() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
     fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
 
-    let err = execute_run(RunCommand {
+    let run_id = test_run_id("replay");
+    execute_run(RunCommand {
         plan_file: plan_path,
-        agent: "claude".to_string(),
+        agent: "codex".to_string(),
         workers: 1,
         reviewers: 1,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
-        run_id: Some(test_run_id("bad-agent")),
-        state_db: Some(tmp.path().join("state.db")),
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
         allow_partial_completion: false,
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
-    .unwrap_err();
-    assert!(format!("{err}").contains("only `codex` supported in this version"));
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let claimed_seq = store.list_events_of_type(&run_id, "task_claimed").unwrap()[0].seq;
+    let closed_seq = store.list_events_of_type(&run_id, "task_closed").unwrap()[0].seq;
+
+    let at_claim = replay_status_report(&run_id, claimed_seq, Some(db_path.clone())).unwrap();
+    let task = &at_claim.state.tasks["task_a"];
+    assert!(task.claimed);
+    assert!(!task.closed);
+    assert_eq!(at_claim.status, "running");
+    assert_eq!(at_claim.phase, "implementation_loop");
+
+    let after_close = replay_status_report(&run_id, closed_seq + 1, Some(db_path.clone())).unwrap();
+    let task = &after_close.state.tasks["task_a"];
+    assert!(task.closed);
+    assert!(!task.claimed);
 }
 
 #[test]
-fn reviewer_prompt_override_is_written_to_reviewer_capsule() {
+fn per_task_integration_branch_follows_configured_template() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: implement other feature",
+    )
+    .unwrap();
     write_repo_config(
         tmp.path(),
         r#"
 version = 2
 [checks]
 commands = ["true"]
-[prompts]
-reviewer = "Return strict JSON with approved/findings only."
+[vcs]
+integration_strategy = "per_task"
+integration_branch = "work/{run_id}/{task_id}"
 "#,
     );
 
-    let run_id = test_run_id("reviewer-prompt");
+    let run_id = test_run_id("per-task-branch");
     execute_run(RunCommand {
-        plan_file: plan_path.clone(),
+        plan_file: plan_path,
         agent: "codex".to_string(),
-        workers: 1,
+        workers: 2,
         reviewers: 1,
         checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
-        state_db: Some(db_path),
+        state_db: Some(db_path.clone()),
         allow_partial_completion: false,
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
-    let events = EventStore::open(&plan_path.parent().unwrap().join("state.db"))
+    let merges = EventStore::open(&db_path)
         .unwrap()
-        .list_events(&run_id)
+        .list_events_of_type(&run_id, "merge_succeeded")
         .unwrap();
-    let capsule_path = events
+    let branches = merges
         .iter()
-        .find(|e| e.event_type == "review_requested")
-        .and_then(|e| e.payload_json.get("capsule_path"))
-        .and_then(|v| v.as_str())
-        .expect("missing reviewer capsule path");
-    let raw = fs::read_to_string(capsule_path).unwrap();
-    assert!(raw.contains("Return strict JSON with approved/findings only."));
+        .map(|e| {
+            (
+                e.task_id.clone().unwrap(),
+                e.payload_json["integration_branch"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        branches,
+        vec![
+            ("task_a".to_string(), format!("work/{run_id}/task_a")),
+            ("task_b".to_string(), format!("work/{run_id}/task_b")),
+        ]
+    );
 }
 
 #[test]
-fn ambiguity_pauses_and_can_resume() {
+fn concurrent_attempts_get_distinct_stable_worker_slot_ids() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature [needs-fix]\n- [ ] task-b: implement other feature",
+    )
+    .unwrap();
 
-    let run_id = test_run_id("paused");
-    let err = execute_run(RunCommand {
+    let run_id = test_run_id("worker-slots");
+    execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
         workers: 2,
-        reviewers: 1,
+        reviewers: 2,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -297,73 +439,81 @@ fn ambiguity_pauses_and_can_resume() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
-    .unwrap_err();
-    assert!(format!("{err}").contains("paused"));
-
-    list_questions(&run_id, Some(db_path.clone())).unwrap();
-    answer_question(&run_id, "spec-q-1", "Clarified", Some(db_path.clone())).unwrap();
-    resume_run(&run_id, Some(db_path.clone())).unwrap();
-
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-    assert!(events.iter().any(|e| e.event_type == "run_paused"));
-    assert!(events.iter().any(|e| e.event_type == "run_resumed"));
-    assert!(events.iter().any(|e| e.event_type == "run_completed"));
-}
-
-#[test]
-fn dedupe_key_prevents_duplicate_event() {
-    let tmp = tempdir().unwrap();
-    let db_path = tmp.path().join("state.db");
-    let store = EventStore::open(&db_path).unwrap();
+    .unwrap();
 
-    let run_id = test_run_id("dedupe");
-    store
-        .create_run(&RunRow {
-            id: run_id.clone(),
-            plan_path: "plan.md".to_string(),
-            plan_sha256: "abc".to_string(),
-            spl_plan_path: "plan.spl".to_string(),
-            created_at: chrono::Utc::now().to_rfc3339(),
-            status: "running".to_string(),
-            config_json: serde_json::json!({}),
-        })
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
         .unwrap();
-
-    let ev = NewEvent {
-        event_type: "task_registered".to_string(),
-        task_id: Some("t1".to_string()),
-        actor_role: None,
-        actor_id: None,
-        attempt: None,
-        payload_json: serde_json::json!({"task_id": "t1"}),
-        dedupe_key: Some("task_registered:t1".to_string()),
+    let actor_ids = |role: &str, task_id: &str, attempt: i64| {
+        let mut ids = events
+            .iter()
+            .filter(|e| {
+                e.actor_role.as_deref() == Some(role)
+                    && e.task_id.as_deref() == Some(task_id)
+                    && e.attempt == Some(attempt)
+            })
+            .map(|e| e.actor_id.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert!(
+            !ids.is_empty(),
+            "no {role} events for {task_id} attempt {attempt}"
+        );
+        ids.dedup();
+        assert_eq!(
+            ids.len(),
+            1,
+            "{role} id changed within {task_id} attempt {attempt}"
+        );
+        ids.remove(0)
     };
 
-    let first = store.append_event(&run_id, &ev).unwrap();
-    let second = store.append_event(&run_id, &ev).unwrap();
-    assert!(first.is_some());
-    assert!(second.is_none());
+    // The first batch runs both tasks side by side, each in its own slot.
+    assert_eq!(actor_ids("implementer", "task_a", 1), "impl-1");
+    assert_eq!(actor_ids("implementer", "task_b", 1), "impl-2");
+    assert_eq!(actor_ids("reviewer", "task_a", 1), "rev-1");
+    assert_eq!(actor_ids("reviewer", "task_b", 1), "rev-2");
+    // The retry runs alone, so it takes the first slot again whatever its attempt number.
+    assert_eq!(actor_ids("implementer", "task_a", 2), "impl-1");
+    assert_eq!(actor_ids("reviewer", "task_a", 2), "rev-1");
 }
 
 #[test]
-fn review_question_uses_returned_question_id() {
+fn merge_ready_tasks_merge_one_at_a_time() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: ").unwrap();
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: implement other feature",
+    )
+    .unwrap();
 
-    let run_id = test_run_id("question-id");
-    let err = execute_run(RunCommand {
+    let run_id = test_run_id("merge-queue");
+    execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
         workers: 2,
         reviewers: 1,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -371,30 +521,79 @@ fn review_question_uses_returned_question_id() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
-    .unwrap_err();
-    assert!(format!("{err}").contains("paused"));
+    .unwrap();
 
-    answer_question(&run_id, "spec-q-2", "filled objective", Some(db_path)).unwrap();
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let position = |event_type: &str, task_id: &str| {
+        events
+            .iter()
+            .position(|e| e.event_type == event_type && e.task_id.as_deref() == Some(task_id))
+            .unwrap_or_else(|| panic!("missing {event_type} for {task_id}"))
+    };
+
+    // Both tasks were merge-ready before either merged.
+    let last_checks =
+        position("checks_reported", "task_a").max(position("checks_reported", "task_b"));
+    let first_merge =
+        position("merge_succeeded", "task_a").min(position("merge_succeeded", "task_b"));
+    assert!(last_checks < first_merge);
+
+    // Each merge is closed out before the next one starts.
+    let queue_events = events
+        .iter()
+        .filter(|e| matches!(e.event_type.as_str(), "merge_succeeded" | "task_closed"))
+        .map(|e| (e.event_type.as_str(), e.task_id.as_deref().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        queue_events,
+        vec![
+            ("merge_succeeded", "task_a"),
+            ("task_closed", "task_a"),
+            ("merge_succeeded", "task_b"),
+            ("task_closed", "task_b"),
+        ]
+    );
 }
 
 #[test]
-fn implementer_nonzero_exit_blocks_review_and_close() {
+fn yaml_plan_runs_without_translator() {
     let tmp = tempdir().unwrap();
-    let plan_path = tmp.path().join("plan.md");
+    let plan_path = tmp.path().join("plan.yaml");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: break build [impl-fail]").unwrap();
+    fs::write(
+        &plan_path,
+        "tasks:\n  - id: task-a\n    objective: first\n  - id: task-b\n    objective: second\n    dependencies: [task-a]\n",
+    )
+    .unwrap();
 
-    let run_id = test_run_id("impl-fail");
+    let run_id = test_run_id("yaml");
     execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
-        workers: 2,
+        workers: 1,
         reviewers: 1,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -402,42 +601,62 @@ fn implementer_nonzero_exit_blocks_review_and_close() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-    assert!(events.iter().any(|e| e.event_type == "run_failed"));
-    assert!(
-        events
-            .iter()
-            .any(|e| e.event_type == "task_failed_terminal")
-    );
-    assert!(events.iter().all(|e| e.event_type != "review_requested"));
-    assert!(events.iter().all(|e| e.event_type != "task_closed"));
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let translated = events
+        .iter()
+        .find(|e| e.event_type == "plan_translated")
+        .expect("missing plan_translated");
+    assert_eq!(translated.payload_json["source"], serde_json::json!("yaml"));
+    let closed = events
+        .iter()
+        .filter(|e| e.event_type == "task_closed")
+        .filter_map(|e| e.task_id.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(closed, vec!["task-a".to_string(), "task-b".to_string()]);
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
 }
 
 #[test]
-fn reviewer_missing_output_fails_closed() {
+fn prose_spec_translates_and_completes() {
     let tmp = tempdir().unwrap();
-    let plan_path = tmp.path().join("plan.md");
+    let plan_path = tmp.path().join("spec.md");
     let db_path = tmp.path().join("state.db");
     fs::write(
         &plan_path,
-        "- [ ] task-a: reviewer output absent [missing-review-output]",
+        "# Feature Spec\nImplement a tiny auth flow with validation.\nInclude implementation and review quality checks.",
     )
     .unwrap();
 
-    let run_id = test_run_id("review-missing");
+    let run_id = test_run_id("prose");
     execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
         workers: 2,
         reviewers: 1,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -445,101 +664,54 @@ fn reviewer_missing_output_fails_closed() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
     let store = EventStore::open(&db_path).unwrap();
     let events = store.list_events(&run_id).unwrap();
-    assert!(events.iter().any(|e| e.event_type == "review_requested"));
-    assert!(events.iter().any(|e| e.event_type == "review_found_issues"));
-    let invalid_reviewer = events
-        .iter()
-        .find(|e| e.event_type == "review_found_issues")
-        .expect("missing review_found_issues");
-    assert!(
-        invalid_reviewer
-            .payload_json
-            .get("reason")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .contains("invalid reviewer output")
-    );
-    assert!(events.iter().all(|e| e.event_type != "review_approved"));
-    assert!(events.iter().all(|e| e.event_type != "task_closed"));
+    assert!(events.iter().any(|e| e.event_type == "plan_translated"));
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
 }
 
 #[test]
-fn reviewer_findings_persist_and_reach_next_implementer_attempt() {
+fn config_only_checks_resolves_without_question_pause() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    let agent_path = tmp.path().join("agent.sh");
-    fs::write(
-        &plan_path,
-        "- [ ] task-a: implement feature with rework loop",
-    )
-    .unwrap();
-    fs::write(
-        &agent_path,
-        r#"#!/usr/bin/env bash
-set -euo pipefail
-case "${THENCE_ROLE:-}" in
-  plan-translator)
-    cat > "${THENCE_RESULT_FILE}" <<'JSON'
-{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature with rework loop","acceptance":"Complete objective: implement feature with rework loop","dependencies":[],"checks":["true"]}]}
-JSON
-    ;;
-  implementer)
-    if [ "${THENCE_ATTEMPT:-1}" = "1" ]; then
-      echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
-    else
-      if grep -q "must-handle-edge-case" "${THENCE_CAPSULE_FILE}"; then
-        echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
-      else
-        echo '{"submitted":false}' > "${THENCE_RESULT_FILE}"
-      fi
-    fi
-    ;;
-  reviewer)
-    if [ "${THENCE_ATTEMPT:-1}" = "1" ]; then
-      cat > "${THENCE_RESULT_FILE}" <<'JSON'
-{"approved":false,"findings":["must-handle-edge-case","add-regression-test"]}
-JSON
-    else
-      echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}"
-    fi
-    ;;
-  checks-proposer) echo '{"commands":["true"],"rationale":"ok"}' > "${THENCE_RESULT_FILE}" ;;
-  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
-esac
-"#,
-    )
-    .unwrap();
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&agent_path).unwrap().permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(&agent_path, perms).unwrap();
-    }
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
     write_repo_config(
         tmp.path(),
-        &format!(
-            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
-            agent_path.display()
-        ),
+        r#"
+version = 2
+[checks]
+commands = ["true"]
+"#,
     );
 
-    let run_id = test_run_id("findings-forward");
+    let run_id = test_run_id("config-checks");
     execute_run(RunCommand {
-        plan_file: plan_path.clone(),
+        plan_file: plan_path,
         agent: "codex".to_string(),
         workers: 1,
         reviewers: 1,
-        checks: Some("true".to_string()),
-        simulate: false,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -547,72 +719,62 @@ esac
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-
-    let findings_event = events
-        .iter()
-        .find(|e| e.event_type == "review_found_issues" && e.attempt == Some(1))
-        .expect("missing review_found_issues for attempt 1");
-    let findings = findings_event
-        .payload_json
-        .get("findings")
-        .and_then(|v| v.as_array())
-        .cloned()
-        .unwrap_or_default();
-    assert!(
-        findings
-            .iter()
-            .any(|v| v.as_str() == Some("must-handle-edge-case"))
-    );
-
-    assert!(
-        events
-            .iter()
-            .any(|e| e.event_type == "task_claimed" && e.attempt == Some(2))
-    );
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert!(events.iter().any(|e| e.event_type == "checks_approved"));
     assert!(
         events
             .iter()
-            .any(|e| e.event_type == "review_approved" && e.attempt == Some(2))
+            .all(|e| e.event_type != "checks_question_opened")
     );
-    assert!(events.iter().any(|e| e.event_type == "task_closed"));
+    assert!(events.iter().all(|e| e.event_type != "checks_proposed"));
     assert!(events.iter().any(|e| e.event_type == "run_completed"));
-
-    let capsule = plan_path
-        .parent()
-        .unwrap()
-        .join(".thence")
-        .join("runs")
-        .join(&run_id)
-        .join("capsules")
-        .join("task-a")
-        .join("attempt2")
-        .join("implementer.json");
-    let capsule_raw = fs::read_to_string(capsule).unwrap();
-    assert!(capsule_raw.contains("must-handle-edge-case"));
 }
 
 #[test]
-fn duplicate_sanitized_task_ids_pause_translation() {
+fn cli_checks_override_config_checks() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: one\n- [ ] task_a: two").unwrap();
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        r#"
+version = 2
+[checks]
+commands = ["false"]
+"#,
+    );
 
-    let run_id = test_run_id("dup-id");
-    let err = execute_run(RunCommand {
+    let run_id = test_run_id("cli-over-config");
+    execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
-        workers: 2,
+        workers: 1,
         reviewers: 1,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -620,41 +782,65 @@ fn duplicate_sanitized_task_ids_pause_translation() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
-    .unwrap_err();
-    assert!(format!("{err}").contains("translation failure"));
+    .unwrap();
 
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-    assert!(
-        events
-            .iter()
-            .any(|e| e.event_type == "spec_question_opened")
-    );
-    assert!(events.iter().any(|e| {
-        e.event_type == "human_input_requested"
-            && e.payload_json.get("question_id").and_then(|v| v.as_str())
-                == Some("spec-q-translate")
-    }));
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let checks_event = events
+        .iter()
+        .find(|e| e.event_type == "checks_approved")
+        .expect("missing checks_approved");
+    let commands = checks_event
+        .payload_json
+        .get("commands")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    assert_eq!(commands, vec![serde_json::json!("true")]);
 }
 
 #[test]
-fn resume_with_open_question_uses_real_question_id() {
+fn failing_check_output_reaches_review_findings() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: ").unwrap();
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        r#"
+version = 2
+[checks]
+commands = ["echo 'error[E0308]: mismatched types' >&2 && false"]
+"#,
+    );
 
-    let run_id = test_run_id("resume-qid");
-    let _ = execute_run(RunCommand {
+    let run_id = test_run_id("check-output");
+    execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
-        workers: 2,
+        workers: 1,
         reviewers: 1,
-        checks: Some("true".to_string()),
+        checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -662,183 +848,398 @@ fn resume_with_open_question_uses_real_question_id() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
-    });
-
-    let err = resume_run(&run_id, Some(db_path.clone())).unwrap_err();
-    assert!(format!("{err}").contains("paused"));
+        artifacts_dir: None,
+    })
+    .unwrap();
 
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-    let latest_human_input_requested = events
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let reported = events
         .iter()
-        .rev()
-        .find(|e| e.event_type == "human_input_requested")
-        .expect("expected human_input_requested");
+        .find(|e| e.event_type == "checks_reported")
+        .expect("missing checks_reported");
     assert_eq!(
-        latest_human_input_requested
-            .payload_json
-            .get("question_id")
-            .and_then(|v| v.as_str()),
-        Some("spec-q-2")
+        reported.payload_json["results"][0]["stderr_tail"],
+        serde_json::json!("error[E0308]: mismatched types\n")
     );
+    let issues = events
+        .iter()
+        .find(|e| {
+            e.event_type == "review_found_issues"
+                && e.payload_json.get("source").and_then(|v| v.as_str()) == Some("checks_gate")
+        })
+        .expect("missing checks_gate findings");
+    let finding = issues.payload_json["findings"][0].as_str().unwrap();
+    assert!(finding.contains("error[E0308]: mismatched types"));
 }
 
 #[test]
-fn missing_checks_fails_fast() {
+fn failing_pre_review_check_skips_the_reviewer_for_that_attempt() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
+    let marker = tmp.path().join("built-once");
     fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            r#"
+version = 2
+[checks]
+commands = [{{ command = "test -f {marker} || {{ touch {marker}; echo 'build failed' >&2; false; }}", stage = "pre_review" }}, "true"]
+"#,
+            marker = marker.display()
+        ),
+    );
 
-    let err = execute_run(RunCommand {
+    let run_id = test_run_id("pre-review");
+    execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
-        workers: 2,
+        workers: 1,
         reviewers: 1,
         checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
-        run_id: Some(test_run_id("checks-gate")),
-        state_db: Some(db_path),
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
         allow_partial_completion: false,
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
-    .unwrap_err();
-    assert!(format!("{err}").contains("No checks configured"));
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let review_attempts = events
+        .iter()
+        .filter(|e| e.event_type == "review_requested")
+        .map(|e| e.attempt)
+        .collect::<Vec<_>>();
+    assert_eq!(review_attempts, vec![Some(2)]);
+    let issues = events
+        .iter()
+        .find(|e| e.event_type == "review_found_issues" && e.attempt == Some(1))
+        .expect("missing attempt 1 findings");
+    assert_eq!(issues.payload_json["source"], "pre_review_checks");
+    assert!(
+        issues.payload_json["findings"][0]
+            .as_str()
+            .unwrap()
+            .contains("build failed")
+    );
+    let stages = events
+        .iter()
+        .filter(|e| e.event_type == "checks_reported")
+        .map(|e| {
+            (
+                e.attempt,
+                e.payload_json["stage"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        stages,
+        vec![
+            (Some(1), "pre_review".to_string()),
+            (Some(2), "pre_review".to_string()),
+            (Some(2), "post_review".to_string()),
+        ]
+    );
+    assert!(events.iter().any(|e| e.event_type == "task_closed"));
 }
 
 #[test]
-fn translation_pause_resume_regenerates_spl_and_completes() {
+fn unsupported_agent_is_rejected() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
-    let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: one\n- [ ] task_a: two").unwrap();
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
 
-    let run_id = test_run_id("translate-resume");
     let err = execute_run(RunCommand {
-        plan_file: plan_path.clone(),
-        agent: "codex".to_string(),
-        workers: 2,
+        plan_file: plan_path,
+        agent: "claude".to_string(),
+        workers: 1,
         reviewers: 1,
         checks: Some("true".to_string()),
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
-        run_id: Some(run_id.clone()),
-        state_db: Some(db_path.clone()),
+        run_id: Some(test_run_id("bad-agent")),
+        state_db: Some(tmp.path().join("state.db")),
         allow_partial_completion: false,
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap_err();
-    assert!(format!("{err}").contains("translation failure"));
+    assert!(format!("{err}").contains("unsupported agent provider `claude`"));
+}
 
-    // Fix plan after pause and resume same run.
-    fs::write(
-        &plan_path,
-        "- [ ] task-a: one\n- [ ] task-b: two | deps=task-a",
-    )
-    .unwrap();
-    answer_question(
-        &run_id,
-        "spec-q-translate",
-        "fixed plan",
-        Some(db_path.clone()),
-    )
+#[test]
+fn opencode_provider_completes_simulated_run() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run_id = test_run_id("opencode");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "opencode".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
     .unwrap();
-    resume_run(&run_id, Some(db_path.clone())).unwrap();
 
     let store = EventStore::open(&db_path).unwrap();
-    let run = store.get_run(&run_id).unwrap().expect("run row");
-    let events = store.list_events(&run_id).unwrap();
-    assert!(std::path::Path::new(&run.spl_plan_path).exists());
-    assert!(events.iter().any(|e| e.event_type == "plan_translated"));
-    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "run_completed")
+            .unwrap()
+            .len(),
+        1
+    );
+    let submitted = store
+        .list_events_of_type(&run_id, "work_submitted")
+        .unwrap();
+    let stdout_path = submitted[0].payload_json["stdout_path"].as_str().unwrap();
+    assert!(
+        fs::read_to_string(stdout_path)
+            .unwrap()
+            .contains("provider=opencode")
+    );
 }
 
 #[test]
-fn resume_retranslates_when_translated_plan_missing() {
+fn reviewer_prompt_override_is_written_to_reviewer_capsule() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: clarify behavior ???").unwrap();
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        r#"
+version = 2
+[checks]
+commands = ["true"]
+[prompts]
+reviewer = "Return strict JSON with approved/findings only."
+"#,
+    );
 
-    let run_id = test_run_id("resume-missing-translated");
-    let err = execute_run(RunCommand {
+    let run_id = test_run_id("reviewer-prompt");
+    execute_run(RunCommand {
         plan_file: plan_path.clone(),
         agent: "codex".to_string(),
-        workers: 2,
+        workers: 1,
         reviewers: 1,
-        checks: Some("true".to_string()),
+        checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
-        state_db: Some(db_path.clone()),
+        state_db: Some(db_path),
         allow_partial_completion: false,
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
-    .unwrap_err();
-    assert!(format!("{err}").contains("paused"));
-    answer_question(&run_id, "spec-q-1", "clarified", Some(db_path.clone())).unwrap();
+    .unwrap();
 
-    let translated_path = plan_path
-        .parent()
+    let events = EventStore::open(&plan_path.parent().unwrap().join("state.db"))
         .unwrap()
-        .join(".thence")
-        .join("runs")
-        .join(&run_id)
-        .join("translated_plan.json");
-    if translated_path.exists() {
-        fs::remove_file(translated_path).unwrap();
-    }
+        .list_events(&run_id)
+        .unwrap();
+    let capsule_path = events
+        .iter()
+        .find(|e| e.event_type == "review_requested")
+        .and_then(|e| e.payload_json.get("capsule_path"))
+        .and_then(|v| v.as_str())
+        .expect("missing reviewer capsule path");
+    let raw = fs::read_to_string(capsule_path).unwrap();
+    assert!(raw.contains("Return strict JSON with approved/findings only."));
+}
 
-    resume_run(&run_id, Some(db_path.clone())).unwrap();
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-    assert!(events.iter().any(|e| e.event_type == "plan_translated"));
-    assert!(events.iter().any(|e| e.event_type == "task_registered"));
-    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+#[test]
+fn acceptance_criteria_list_reaches_the_reviewer_capsule() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.yaml");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        r#"
+tasks:
+  - id: parser
+    objective: Build the parser
+    acceptance:
+      - Parses nested lists
+      - Reports the line of a syntax error
+"#,
+    )
+    .unwrap();
+
+    let run_id = test_run_id("acceptance-list");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let capsule = read_capsule(&run_id, "parser", 1, "reviewer", Some(db_path)).unwrap();
+    let payload = &capsule["payload"];
+    assert_eq!(
+        payload["acceptance_criteria"],
+        serde_json::json!(["Parses nested lists", "Reports the line of a syntax error"])
+    );
+    assert_eq!(
+        payload["acceptance"],
+        serde_json::json!("Parses nested lists; Reports the line of a syntax error")
+    );
 }
 
 #[test]
-fn resume_refreshes_agent_command_before_initial_translation() {
+fn implementer_prompt_override_is_written_to_implementer_capsule() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
-    let agent_path = tmp.path().join("agent.sh");
     fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
     write_repo_config(
         tmp.path(),
         r#"
 version = 2
-[agent]
-provider = "codex"
-command = "missing-codex-command"
 [checks]
 commands = ["true"]
+[prompts]
+implementer = "Prefer small, focused diffs and keep public APIs stable."
 "#,
     );
 
-    let run_id = test_run_id("refresh-agent-command");
-    let err = execute_run(RunCommand {
-        plan_file: plan_path.clone(),
+    let run_id = test_run_id("implementer-prompt");
+    execute_run(RunCommand {
+        plan_file: plan_path,
         agent: "codex".to_string(),
         workers: 1,
         reviewers: 1,
         checks: None,
-        simulate: false,
+        checks_from_file: None,
+        simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -846,11 +1247,5658 @@ commands = ["true"]
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let capsule_path = events
+        .iter()
+        .find(|e| e.event_type == "work_submitted")
+        .and_then(|e| e.payload_json.get("capsule_path"))
+        .and_then(|v| v.as_str())
+        .expect("missing implementer capsule path");
+    let raw = fs::read_to_string(capsule_path).unwrap();
+    assert!(raw.contains("Prefer small, focused diffs and keep public APIs stable."));
+}
+
+#[test]
+fn non_utf8_plan_fails_with_byte_offset() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, b"- [ ] task-a: caf\xe9 menu\n").unwrap();
+
+    let err = execute_run(RunCommand {
+        plan_file: plan_path.clone(),
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(test_run_id("non-utf8")),
+        state_db: Some(db_path),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap_err();
-    assert!(format!("{err}").contains("paused"));
+    let msg = format!("{err}");
+    assert!(
+        msg.contains("is not valid UTF-8 (invalid byte at offset 17)"),
+        "{msg}"
+    );
+    assert!(msg.contains("plans must be UTF-8 markdown or YAML"));
+
+    let err = validate_plan(&plan_path).unwrap_err();
+    assert!(format!("{err}").contains("not valid UTF-8"));
+}
+
+#[test]
+fn max_attempts_one_fails_terminal_after_first_rejection() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature [needs-fix]").unwrap();
+
+    let run_id = test_run_id("max-attempts");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: Some(1),
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let claims = events
+        .iter()
+        .filter(|e| e.event_type == "task_claimed")
+        .count();
+    assert_eq!(claims, 1);
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "task_failed_terminal")
+    );
+    assert!(events.iter().any(|e| e.event_type == "run_failed"));
+}
+
+#[test]
+fn worktree_keep_on_fail_preserves_failed_attempt_worktree() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature [impl-fail]").unwrap();
+
+    let run_id = test_run_id("keep-on-fail");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: Some(1),
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: true,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let failed = EventStore::open(&db_path)
+        .unwrap()
+        .list_events_of_type(&run_id, "task_failed_terminal")
+        .unwrap();
+    assert_eq!(failed.len(), 1);
+    let preserved = std::path::PathBuf::from(
+        failed[0].payload_json["failed_worktree"]
+            .as_str()
+            .expect("failed_worktree recorded"),
+    );
+    assert!(
+        preserved.ends_with(
+            std::path::Path::new(&run_id)
+                .join("failed")
+                .join("task_a")
+                .join("1")
+        )
+    );
+    assert!(preserved.is_dir());
+    assert!(fs::read_dir(&preserved).unwrap().any(|entry| {
+        entry
+            .unwrap()
+            .file_name()
+            .to_string_lossy()
+            .contains("attempt1")
+    }));
+}
+
+#[test]
+fn attempt_backoff_delays_reclaim_after_review_findings() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature [needs-fix]").unwrap();
+    write_repo_config(
+        tmp.path(),
+        r#"
+version = 2
+[checks]
+commands = ["true"]
+[run]
+attempt_backoff_secs = 1
+"#,
+    );
+
+    let run_id = test_run_id("attempt-backoff");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let ts_of = |event_type: &str, attempt: i64| {
+        let event = events
+            .iter()
+            .find(|e| e.event_type == event_type && e.attempt == Some(attempt))
+            .unwrap_or_else(|| panic!("missing {event_type} for attempt {attempt}"));
+        chrono::DateTime::parse_from_rfc3339(&event.ts).unwrap()
+    };
+    let waited = ts_of("task_claimed", 2) - ts_of("review_found_issues", 1);
+    assert!(
+        waited >= chrono::Duration::seconds(1),
+        "attempt 2 was claimed {waited} after attempt 1's findings"
+    );
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+}
+
+#[test]
+fn check_timeout_flag_times_out_slow_checks() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run_id = test_run_id("check-timeout");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("sleep 3".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: Some(1),
+        check_timeout_secs: Some(1),
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let issues = events
+        .iter()
+        .find(|e| {
+            e.event_type == "review_found_issues"
+                && e.payload_json.get("source").and_then(|v| v.as_str()) == Some("checks_gate")
+        })
+        .expect("missing checks_gate findings");
+    let finding = issues.payload_json["findings"][0].as_str().unwrap();
+    assert!(finding.starts_with("check timed out: sleep 3"));
+}
+
+#[test]
+fn task_specific_checks_extend_run_checks_for_that_task_only() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature | checks=echo task-a-extra\n- [ ] task-b: implement other feature",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("task-checks");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: true,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let reported_commands = |task_id: &str| {
+        events
+            .iter()
+            .find(|e| e.event_type == "checks_reported" && e.task_id.as_deref() == Some(task_id))
+            .expect("missing checks_reported")
+            .payload_json["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["command"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        reported_commands("task_a"),
+        vec!["true".to_string(), "echo task-a-extra".to_string()]
+    );
+    assert_eq!(reported_commands("task_b"), vec!["true".to_string()]);
+    let checks_source = |task_id: &str| {
+        events
+            .iter()
+            .find(|e| e.event_type == "checks_reported" && e.task_id.as_deref() == Some(task_id))
+            .map(|e| e.payload_json["checks_source"].clone())
+    };
+    assert_eq!(checks_source("task_a"), Some(serde_json::json!("merged")));
+    assert_eq!(checks_source("task_b"), Some(serde_json::json!("run")));
+}
+
+#[test]
+fn task_override_replace_records_task_checks_source() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature | checks=echo task-a-only",
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        "version = 2\n[checks]\ncommands = [\"true\"]\ntask_override = \"replace\"\n",
+    );
+
+    let run_id = test_run_id("task-override");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: true,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let reported = events
+        .iter()
+        .find(|e| e.event_type == "checks_reported")
+        .expect("missing checks_reported");
+    assert_eq!(reported.payload_json["checks_source"], "task");
+    assert_eq!(
+        reported.payload_json["results"][0]["command"],
+        "echo task-a-only"
+    );
+    assert_eq!(
+        reported.payload_json["results"].as_array().unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn partial_completion_summary_counts_closed_and_failed_tasks() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: broken feature [impl-fail]",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("summary");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: true,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: Some(1),
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let completed = events
+        .iter()
+        .find(|e| e.event_type == "run_completed")
+        .expect("missing run_completed");
+    let summary = &completed.payload_json["summary"];
+    assert_eq!(completed.payload_json["task_count"], serde_json::json!(2));
+    assert_eq!(summary["closed"], serde_json::json!(1));
+    assert_eq!(summary["failed"], serde_json::json!(1));
+    assert_eq!(summary["pending"], serde_json::json!(0));
+    assert_eq!(
+        summary["tasks"],
+        serde_json::json!([
+            {"task_id": "task_a", "attempts": 1, "latest_attempt": 1, "outcome": "closed"},
+            {"task_id": "task_b", "attempts": 1, "latest_attempt": 1, "outcome": "failed"}
+        ])
+    );
+}
+
+#[test]
+fn diff_reports_task_that_failed_only_in_the_rerun() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: flaky feature [needs-fix]",
+    )
+    .unwrap();
+
+    let run = |run_id: &str, max_attempts: Option<i64>| {
+        execute_run(RunCommand {
+            plan_file: plan_path.clone(),
+            agent: "codex".to_string(),
+            workers: 1,
+            reviewers: 1,
+            checks: Some("true".to_string()),
+            checks_from_file: None,
+            simulate: true,
+            log: None,
+            log_format: LogFormat::Compact,
+            resume: false,
+            run_id: Some(run_id.to_string()),
+            state_db: Some(db_path.clone()),
+            allow_partial_completion: true,
+            trust_plan_checks: false,
+            interactive: false,
+            attempt_timeout_secs: None,
+            reviewer_timeout_secs: None,
+            max_attempts,
+            check_timeout_secs: None,
+            agent_spec_review: false,
+            no_spec_review: false,
+            propose_checks: false,
+            no_checks_file: false,
+            reconfigure_checks: false,
+            worktree_keep_on_fail: false,
+            dry_run: false,
+            only_task: Vec::new(),
+            debug_dump_spl: None,
+            artifacts_dir: None,
+        })
+        .unwrap();
+    };
+    let complete = test_run_id("diff-complete");
+    let partial = test_run_id("diff-partial");
+    run(&complete, None);
+    run(&partial, Some(1));
+
+    let diff = diff_run_outcomes(&complete, &partial, Some(db_path)).unwrap();
+    assert_eq!(diff.status_a, "completed");
+    assert_eq!(diff.unchanged, vec!["task_a".to_string()]);
+    assert_eq!(diff.changed.len(), 1, "{diff:?}");
+    let change = &diff.changed[0];
+    assert_eq!(change.task_id, "task_b");
+    assert_eq!(
+        (change.a.outcome.as_str(), change.a.attempts),
+        ("closed", 2)
+    );
+    assert_eq!(
+        (change.b.outcome.as_str(), change.b.attempts),
+        ("failed", 1)
+    );
+    assert!(diff.only_in_a.is_empty() && diff.only_in_b.is_empty());
+}
+
+#[test]
+fn dependents_of_a_terminally_failed_task_are_skipped() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: broken feature [impl-fail]\n- [ ] task-b: builds on a | deps=task-a",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("skipped");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: true,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: Some(1),
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let skipped = events
+        .iter()
+        .find(|e| e.event_type == "task_skipped")
+        .expect("missing task_skipped");
+    assert_eq!(skipped.task_id.as_deref(), Some("task_b"));
+    assert_eq!(
+        skipped.payload_json["reason"],
+        serde_json::json!("dependency 'task_a' failed")
+    );
+    assert!(
+        !events
+            .iter()
+            .any(|e| e.event_type == "task_claimed" && e.task_id.as_deref() == Some("task_b"))
+    );
+    assert!(!events.iter().any(|e| e.event_type == "run_completed"));
+    let failed = events
+        .iter()
+        .find(|e| e.event_type == "run_failed")
+        .expect("missing run_failed");
+    assert_eq!(
+        failed.payload_json["reason"],
+        serde_json::json!("no task closed")
+    );
+    let summary = &failed.payload_json["summary"];
+    assert_eq!(summary["closed"], serde_json::json!(0));
+    assert_eq!(summary["failed"], serde_json::json!(1));
+    assert_eq!(summary["skipped"], serde_json::json!(1));
+}
+
+#[test]
+fn ambiguity_pauses_and_can_resume() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run_id = test_run_id("paused");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    list_questions(&run_id, false, Some(db_path.clone())).unwrap();
+    answer_question(&run_id, "spec-q-1", "Clarified", Some(db_path.clone())).unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    assert!(events.iter().any(|e| e.event_type == "run_paused"));
+    assert!(events.iter().any(|e| e.event_type == "run_resumed"));
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+}
+
+#[test]
+fn questions_json_lists_open_questions() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run_id = test_run_id("questions-json");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    let questions = questions_json(&run_id, Some(db_path)).unwrap();
+    assert_eq!(questions.as_array().unwrap().len(), 1);
+    assert_eq!(questions[0]["question_id"], "spec-q-1");
+    assert!(questions[0]["question"].as_str().unwrap().contains("???"));
+}
+
+#[test]
+fn no_spec_review_runs_an_ambiguous_spec_without_pausing() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run_id = test_run_id("no-spec-review");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: true,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert!(
+        !events
+            .iter()
+            .any(|e| e.event_type == "spec_question_opened" || e.event_type == "run_paused")
+    );
+    let approved = events
+        .iter()
+        .find(|e| e.event_type == "spec_approved")
+        .expect("missing spec_approved");
+    assert_eq!(approved.payload_json["source"], "skipped");
+    assert!(events.iter().any(|e| e.event_type == "plan_validated"));
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+}
+
+#[test]
+fn approve_answer_approves_the_spec_after_a_rejection_kept_it_open() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run_id = test_run_id("approve-answer");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    reject_question(&run_id, "spec-q-1", Some(db_path.clone())).unwrap();
+    let store = EventStore::open(&db_path).unwrap();
+    let open = store.unresolved_questions(&run_id).unwrap();
+    assert!(open.iter().any(|(id, _)| id == "spec-q-1"));
+    assert!(
+        !store
+            .list_events(&run_id)
+            .unwrap()
+            .iter()
+            .any(|e| e.event_type == "spec_approved")
+    );
+
+    answer_question(&run_id, "spec-q-1", APPROVE_ANSWER, Some(db_path.clone())).unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+
+    let events = store.list_events(&run_id).unwrap();
+    let approved = events
+        .iter()
+        .find(|e| e.event_type == "spec_approved")
+        .expect("missing spec_approved");
+    assert_eq!(approved.payload_json["source"], "human_clarification");
+    let answers = events
+        .iter()
+        .filter(|e| e.event_type == "human_input_provided")
+        .map(|e| e.payload_json["rejected"].as_bool().unwrap_or(false))
+        .collect::<Vec<_>>();
+    assert_eq!(answers, vec![true, false]);
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+}
+
+#[test]
+fn reject_refuses_a_question_while_the_run_is_locked() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run_id = test_run_id("reject-locked");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    // Another driver of this run: the test process itself, which is alive.
+    let lock_path = tmp
+        .path()
+        .join(".thence/runs")
+        .join(&run_id)
+        .join("run.lock");
+    let hostname = fs::read_to_string("/proc/sys/kernel/hostname")
+        .map(|h| h.trim().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    fs::write(
+        &lock_path,
+        serde_json::json!({
+            "run_id": run_id,
+            "owner_pid": std::process::id(),
+            "hostname": hostname,
+            "acquired_at": chrono::Utc::now().to_rfc3339()
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let err = reject_question(&run_id, "spec-q-1", Some(db_path.clone())).unwrap_err();
+    assert!(
+        format!("{err:#}").contains("run already in progress"),
+        "{err:#}"
+    );
+    assert!(
+        EventStore::open(&db_path)
+            .unwrap()
+            .list_events_of_type(&run_id, "human_input_provided")
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn doctor_reports_missing_agent_command_without_starting_a_run() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    write_repo_config(
+        tmp.path(),
+        "version = 2\n[agent]\ncommand = \"thence-missing-agent-binary --flag\"\n",
+    );
+
+    let checks = run_doctor_checks(tmp.path(), None, Some(db_path.clone()));
+    let status = checks
+        .iter()
+        .map(|check| (check.name, check.ok))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        status,
+        vec![("config", true), ("agent", false), ("state_db", true)]
+    );
+    let agent = checks.iter().find(|check| check.name == "agent").unwrap();
+    assert!(
+        agent.detail.contains("runnable codex command"),
+        "{}",
+        agent.detail
+    );
+
+    let err = doctor(tmp.path(), None, Some(db_path.clone())).unwrap_err();
+    assert!(format!("{err}").contains("1 preflight check(s) failed"));
+    assert!(!db_path.exists());
+
+    EventStore::open(&db_path).unwrap();
+    let checks = run_doctor_checks(tmp.path(), None, Some(db_path.clone()));
+    let state_db = checks
+        .iter()
+        .find(|check| check.name == "state_db")
+        .unwrap();
+    assert!(state_db.ok, "{}", state_db.detail);
+    assert!(
+        EventStore::open_read_only(&db_path)
+            .unwrap()
+            .list_runs(None)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn quiet_flag_suppresses_pause_guidance_but_still_fails() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run = |quiet: bool| {
+        let mut cmd = std::process::Command::new(env!("CARGO_BIN_EXE_thence"));
+        if quiet {
+            cmd.arg("--quiet");
+        }
+        cmd.arg("run")
+            .arg(&plan_path)
+            .args(["--simulate", "--checks", "true", "--run-id"])
+            .arg(test_run_id("quiet"))
+            .arg("--state-db")
+            .arg(tmp.path().join("state.db"))
+            .output()
+            .unwrap()
+    };
+
+    let loud = run(false);
+    assert!(!loud.status.success());
+    let loud_stderr = String::from_utf8_lossy(&loud.stderr);
+    assert!(
+        loud_stderr.contains("Run paused. Next commands:"),
+        "{loud_stderr}"
+    );
+
+    let quiet = run(true);
+    assert!(!quiet.status.success());
+    let quiet_stderr = String::from_utf8_lossy(&quiet.stderr);
+    assert!(
+        !quiet_stderr.contains("Run paused. Next commands:"),
+        "{quiet_stderr}"
+    );
+    assert!(quiet_stderr.contains("run paused"), "{quiet_stderr}");
+}
+
+#[cfg(unix)]
+#[test]
+fn attach_echoes_agent_output_to_stderr_and_still_writes_logs() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  implementer)
+    echo "attach-marker-stdout"
+    echo "attach-marker-stderr" >&2
+    echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    ;;
+  reviewer) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("attach");
+    let out = std::process::Command::new(env!("CARGO_BIN_EXE_thence"))
+        .arg("run")
+        .arg(&plan_path)
+        .arg("--attach")
+        .arg("--run-id")
+        .arg(&run_id)
+        .arg("--state-db")
+        .arg(&db_path)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(out.status.success(), "{stderr}");
+    assert!(
+        stderr.contains("[implementer task-a#1] attach-marker-stdout"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("[implementer task-a#1] attach-marker-stderr"),
+        "{stderr}"
+    );
+
+    let submitted = EventStore::open(&db_path)
+        .unwrap()
+        .list_events_of_type(&run_id, "work_submitted")
+        .unwrap();
+    let stdout_log = submitted[0].payload_json["stdout_path"].as_str().unwrap();
+    assert!(
+        fs::read_to_string(stdout_log)
+            .unwrap()
+            .contains("attach-marker-stdout")
+    );
+}
+
+#[test]
+fn inspect_json_reports_paused_for_question_phase() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run_id = test_run_id("inspect-json");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+
+    let state = inspect_run_json(&run_id, Some(db_path)).unwrap();
+    assert_eq!(state["phase"], "paused_for_question");
+    assert_eq!(state["paused"], true);
+    assert_eq!(state["spec_approved"], false);
+    assert!(state["terminal"].is_null());
+    assert_eq!(state["open_questions"][0]["question_id"], "spec-q-1");
+}
+
+#[test]
+fn status_report_matches_inspect_for_paused_run() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run_id = test_run_id("status-report");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+
+    let report = status_report(&run_id, Some(db_path.clone())).unwrap();
+    let inspected = inspect_run_json(&run_id, Some(db_path)).unwrap();
+    assert_eq!(report.phase, "paused_for_question");
+    assert_eq!(inspected["phase"], report.phase.as_str());
+    assert_eq!(report.status, "running");
+    assert!(report.state.paused);
+    assert!(report.current.is_none());
+    assert_eq!(report.open_questions.len(), 1);
+    assert_eq!(report.open_questions[0].question_id, "spec-q-1");
+    assert!(report.latest_findings.is_empty());
+}
+
+#[test]
+fn inspect_lists_plan_translator_logs() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "# Sessions\n\nStore sessions in sqlite.\n\n- [ ] task-a: add a sessions table",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("inspect-translator");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let state = inspect_run_json(&run_id, Some(db_path)).unwrap();
+    let artifacts = state["translator_artifacts"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v.as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    for log in [
+        "plan-translator_attempt1_stdout.log",
+        "plan-translator_attempt1_stderr.log",
+    ] {
+        let path = artifacts
+            .iter()
+            .find(|p| p.ends_with(log))
+            .unwrap_or_else(|| panic!("missing {log} in {artifacts:?}"));
+        assert!(std::path::Path::new(path).exists());
+    }
+}
+
+#[test]
+fn dry_run_stops_after_spec_approval_and_can_be_resumed() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: follow up | deps=task-a",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("dry-run");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: true,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let types = store
+        .list_events(&run_id)
+        .unwrap()
+        .into_iter()
+        .map(|e| e.event_type)
+        .collect::<Vec<_>>();
+    assert!(types.iter().any(|t| t == "plan_translated"));
+    assert!(types.iter().any(|t| t == "plan_validated"));
+    assert!(types.iter().any(|t| t == "spec_approved"));
+    for absent in [
+        "task_registered",
+        "task_claimed",
+        "run_completed",
+        "run_failed",
+    ] {
+        assert!(!types.iter().any(|t| t == absent), "unexpected {absent}");
+    }
+
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "task_closed")
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "run_completed")
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn resume_refuses_run_locked_by_a_live_process() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run_id = test_run_id("run-lock");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: true,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    // Another driver of this run: the test process itself, which is alive.
+    let lock_path = tmp
+        .path()
+        .join(".thence/runs")
+        .join(&run_id)
+        .join("run.lock");
+    let hostname = fs::read_to_string("/proc/sys/kernel/hostname")
+        .map(|h| h.trim().to_string())
+        .unwrap_or_else(|_| "unknown".to_string());
+    fs::write(
+        &lock_path,
+        serde_json::json!({
+            "run_id": run_id,
+            "owner_pid": std::process::id(),
+            "hostname": hostname,
+            "acquired_at": chrono::Utc::now().to_rfc3339()
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let err = resume_run(&run_id, Some(db_path.clone())).unwrap_err();
+    assert!(
+        format!("{err:#}").contains("run already in progress"),
+        "{err:#}"
+    );
+    let store = EventStore::open(&db_path).unwrap();
+    assert!(
+        store
+            .list_events_of_type(&run_id, "task_claimed")
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        store
+            .list_events_of_type(&run_id, "run_resumed")
+            .unwrap()
+            .is_empty()
+    );
+
+    fs::remove_file(&lock_path).unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "run_completed")
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(!lock_path.exists());
+}
+
+#[test]
+fn max_attempts_changed_between_run_and_resume_is_flagged_as_config_drift() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    let config = |max_attempts: i64| {
+        format!(
+            "version = 2\n[checks]\ncommands = [\"true\"]\n[run]\nmax_attempts = {max_attempts}\nconfig_drift = \"pause\"\n"
+        )
+    };
+    write_repo_config(tmp.path(), &config(3));
+
+    let run_id = test_run_id("config-drift");
+    execute_run(RunCommand {
+        plan_file: plan_path.clone(),
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: true,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let started = store.list_events_of_type(&run_id, "run_started").unwrap();
+    assert!(started[0].payload_json["config_sha256"].is_string());
+
+    write_repo_config(tmp.path(), &config(1));
+    let err = resume_run(&run_id, Some(db_path.clone())).unwrap_err();
+    assert!(format!("{err:#}").contains("config changed"), "{err:#}");
+
+    let drift = store
+        .list_events_of_type(&run_id, "config_drift_detected")
+        .unwrap();
+    assert_eq!(drift.len(), 1);
+    assert_eq!(
+        drift[0].payload_json["changed"],
+        serde_json::json!(["run.max_attempts"])
+    );
+    assert_eq!(drift[0].payload_json["action"], "pause");
+    assert_ne!(
+        drift[0].payload_json["recorded_sha256"],
+        drift[0].payload_json["live_sha256"]
+    );
+    let questions = store.unresolved_questions(&run_id).unwrap();
+    assert!(questions.iter().any(|(id, _)| id == "config-q-drift"));
+
+    answer_question(
+        &run_id,
+        "config-q-drift",
+        "keep the original settings",
+        Some(db_path.clone()),
+    )
+    .unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "config_drift_detected")
+            .unwrap()
+            .len(),
+        1
+    );
+    let run = store.get_run(&run_id).unwrap().unwrap();
+    assert_eq!(run.config_json["max_attempts"], serde_json::json!(3));
+    assert_eq!(run.status, "completed");
+}
+
+#[test]
+fn plan_edited_between_run_and_resume_is_flagged_as_spec_drift() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        r#"
+version = 2
+[checks]
+commands = ["true"]
+[run]
+spec_drift = "pause"
+"#,
+    );
+
+    let run_id = test_run_id("spec-drift");
+    execute_run(RunCommand {
+        plan_file: plan_path.clone(),
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: true,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: added later",
+    )
+    .unwrap();
+    let err = resume_run(&run_id, Some(db_path.clone())).unwrap_err();
+    assert!(format!("{err:#}").contains("plan file changed"));
+
+    let store = EventStore::open(&db_path).unwrap();
+    let drift = store
+        .list_events_of_type(&run_id, "spec_drift_detected")
+        .unwrap();
+    assert_eq!(drift.len(), 1);
+    let run = store.get_run(&run_id).unwrap().unwrap();
+    assert_eq!(drift[0].payload_json["frozen_sha256"], run.plan_sha256);
+    assert_ne!(drift[0].payload_json["live_sha256"], run.plan_sha256);
+    assert_eq!(drift[0].payload_json["action"], "pause");
+    let questions = store.unresolved_questions(&run_id).unwrap();
+    assert!(questions.iter().any(|(id, _)| id == "spec-q-drift"));
+
+    answer_question(
+        &run_id,
+        "spec-q-drift",
+        "continue with the frozen spec",
+        Some(db_path.clone()),
+    )
+    .unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "spec_drift_detected")
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "task_closed")
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn multi_line_answer_from_file_is_recorded_verbatim() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let answer_path = tmp.path().join("answer.md");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+    fs::write(
+        &answer_path,
+        "Keep the \"legacy\" flag.\n\n- parse `--x`\n- reject 'y'\n",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("answer-file");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+
+    let answer = fs::read_to_string(&answer_path).unwrap();
+    answer_question(&run_id, "spec-q-1", &answer, Some(db_path.clone())).unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let recorded = events
+        .iter()
+        .find(|e| e.event_type == "human_input_provided")
+        .and_then(|e| e.payload_json["text"].as_str())
+        .unwrap();
+    assert_eq!(recorded.as_bytes(), answer.as_bytes());
+}
+
+#[test]
+fn validate_accepts_plan_and_reports_sanity_errors() {
+    let tmp = tempdir().unwrap();
+    let valid = tmp.path().join("valid.md");
+    fs::write(
+        &valid,
+        "- [ ] task-a: first\n- [ ] task-b: second | deps=task-a",
+    )
+    .unwrap();
+    let translated = validate_plan(&valid).unwrap();
+    assert_eq!(translated.tasks.len(), 2);
+    assert_eq!(translated.tasks[1].dependencies, vec!["task_a".to_string()]);
+
+    let blocked = tmp.path().join("blocked.md");
+    fs::write(
+        &blocked,
+        "- [ ] task-a: first | deps=task-b\n- [ ] task-b: second | deps=task-a",
+    )
+    .unwrap();
+    let err = validate_plan(&blocked).unwrap_err();
+    assert!(
+        format!("{err}").contains("no initially ready task"),
+        "{err}"
+    );
+}
+
+#[test]
+fn dedupe_key_prevents_duplicate_event() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    let store = EventStore::open(&db_path).unwrap();
+
+    let run_id = test_run_id("dedupe");
+    store
+        .create_run(&RunRow {
+            id: run_id.clone(),
+            plan_path: "plan.md".to_string(),
+            plan_sha256: "abc".to_string(),
+            spl_plan_path: "plan.spl".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            status: "running".to_string(),
+            config_json: serde_json::json!({}),
+        })
+        .unwrap();
+
+    let ev = NewEvent {
+        event_type: "task_registered".to_string(),
+        task_id: Some("t1".to_string()),
+        actor_role: None,
+        actor_id: None,
+        attempt: None,
+        payload_json: serde_json::json!({"task_id": "t1"}),
+        dedupe_key: Some("task_registered:t1".to_string()),
+    };
+
+    let first = store.append_event(&run_id, &ev).unwrap();
+    let second = store.append_event(&run_id, &ev).unwrap();
+    assert!(first.is_some());
+    assert!(second.is_none());
+}
+
+#[test]
+fn retried_review_approved_for_the_same_attempt_is_ignored() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(tmp.path(), "version = 2\n[checks]\ncommands = [\"true\"]\n");
+
+    let run_id = test_run_id("attempt-dedupe");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let approved = store
+        .list_events(&run_id)
+        .unwrap()
+        .into_iter()
+        .find(|e| e.event_type == "review_approved")
+        .expect("missing review_approved");
+    assert_eq!(
+        approved.dedupe_key.as_deref(),
+        Some(attempt_dedupe_key("review_approved", "task_a", 1).as_str())
+    );
+    let retried = NewEvent {
+        event_type: approved.event_type.clone(),
+        task_id: approved.task_id.clone(),
+        actor_role: approved.actor_role.clone(),
+        actor_id: approved.actor_id.clone(),
+        attempt: approved.attempt,
+        payload_json: approved.payload_json.clone(),
+        dedupe_key: approved.dedupe_key.clone(),
+    };
+    assert!(store.append_event(&run_id, &retried).unwrap().is_none());
+}
+
+#[test]
+fn list_events_since_returns_only_newer_rows() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    let store = EventStore::open(&db_path).unwrap();
+
+    let run_id = test_run_id("since");
+    store
+        .create_run(&RunRow {
+            id: run_id.clone(),
+            plan_path: "plan.md".to_string(),
+            plan_sha256: "abc".to_string(),
+            spl_plan_path: "plan.spl".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            status: "running".to_string(),
+            config_json: serde_json::json!({}),
+        })
+        .unwrap();
+
+    let seqs = (1..=5)
+        .map(|n| {
+            store
+                .append_event(
+                    &run_id,
+                    &NewEvent::simple("note", serde_json::json!({ "n": n })),
+                )
+                .unwrap()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let newer = store.list_events_since(&run_id, seqs[2]).unwrap();
+    assert_eq!(
+        newer.iter().map(|e| e.seq).collect::<Vec<_>>(),
+        vec![seqs[3], seqs[4]]
+    );
+    assert_eq!(newer[0].payload_json, serde_json::json!({"n": 4}));
+}
+
+#[test]
+fn export_of_seq_window_emits_only_in_range_events_in_order() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    let store = EventStore::open(&db_path).unwrap();
+
+    let run_id = test_run_id("export-range");
+    store
+        .create_run(&RunRow {
+            id: run_id.clone(),
+            plan_path: "plan.md".to_string(),
+            plan_sha256: "abc".to_string(),
+            spl_plan_path: "plan.spl".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            status: "running".to_string(),
+            config_json: serde_json::json!({}),
+        })
+        .unwrap();
+
+    let seqs = (1..=6)
+        .map(|n| {
+            store
+                .append_event(
+                    &run_id,
+                    &NewEvent::simple("note", serde_json::json!({ "n": n })),
+                )
+                .unwrap()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let window = store
+        .list_events_in_range(&run_id, Some(seqs[1]), Some(seqs[3]))
+        .unwrap();
+    let mut out = Vec::new();
+    write_events_export(&window, "ndjson", &mut out).unwrap();
+    let exported = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exported
+            .iter()
+            .map(|ev| ev["seq"].as_i64().unwrap())
+            .collect::<Vec<_>>(),
+        vec![seqs[1], seqs[2], seqs[3]]
+    );
+    assert_eq!(exported[0]["payload_json"], serde_json::json!({"n": 2}));
+
+    let open_ended = store
+        .list_events_in_range(&run_id, Some(seqs[4]), None)
+        .unwrap();
+    assert_eq!(
+        open_ended.iter().map(|e| e.seq).collect::<Vec<_>>(),
+        vec![seqs[4], seqs[5]]
+    );
+
+    let err = store
+        .list_events_in_range(&run_id, Some(seqs[3]), Some(seqs[1]))
+        .unwrap_err();
+    assert!(format!("{err}").contains("invalid seq range"));
+}
+
+#[test]
+fn read_only_store_reads_events_but_rejects_writes() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    let run_id = test_run_id("read-only");
+    let row = RunRow {
+        id: run_id.clone(),
+        plan_path: "plan.md".to_string(),
+        plan_sha256: "abc".to_string(),
+        spl_plan_path: "plan.spl".to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        status: "running".to_string(),
+        config_json: serde_json::json!({}),
+    };
+    let writer = EventStore::open(&db_path).unwrap();
+    writer.create_run(&row).unwrap();
+    writer
+        .append_event(
+            &run_id,
+            &NewEvent::simple("note", serde_json::json!({ "n": 1 })),
+        )
+        .unwrap();
+
+    let reader = EventStore::open_read_only(&db_path).unwrap();
+    let events = reader.list_events(&run_id).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type, "note");
+
+    let err = reader
+        .create_run(&RunRow {
+            id: test_run_id("read-only-other"),
+            ..row
+        })
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("readonly"), "{err:#}");
+
+    assert!(EventStore::open_read_only(&tmp.path().join("missing.db")).is_err());
+}
+
+#[test]
+fn list_events_of_type_filters_in_seq_order() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    let store = EventStore::open(&db_path).unwrap();
+
+    let run_id = test_run_id("of-type");
+    store
+        .create_run(&RunRow {
+            id: run_id.clone(),
+            plan_path: "plan.md".to_string(),
+            plan_sha256: "abc".to_string(),
+            spl_plan_path: "plan.spl".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            status: "running".to_string(),
+            config_json: serde_json::json!({}),
+        })
+        .unwrap();
+
+    for (event_type, n) in [
+        ("note", 1),
+        ("marker", 2),
+        ("note", 3),
+        ("other", 4),
+        ("marker", 5),
+    ] {
+        store
+            .append_event(
+                &run_id,
+                &NewEvent::simple(event_type, serde_json::json!({ "n": n })),
+            )
+            .unwrap();
+    }
+
+    let markers = store.list_events_of_type(&run_id, "marker").unwrap();
+    assert_eq!(
+        markers
+            .iter()
+            .map(|e| e.payload_json["n"].as_i64().unwrap())
+            .collect::<Vec<_>>(),
+        vec![2, 5]
+    );
+    assert!(markers.iter().all(|e| e.event_type == "marker"));
+    assert!(markers[0].seq < markers[1].seq);
+}
+
+#[test]
+fn snapshot_roundtrip_replays_to_same_state() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: verify behavior | deps=task-a",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("snapshot");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    let mid = &events[events.len() / 2];
+    let mid_state = RunProjection::replay(&events[..events.len() / 2 + 1]);
+    store.save_snapshot(&run_id, mid.seq, &mid_state).unwrap();
+
+    let snapshot = store.load_latest_snapshot(&run_id).unwrap().unwrap();
+    assert_eq!(snapshot.0, mid.seq);
+    assert_eq!(
+        RunProjection::replay_from(snapshot, &events),
+        RunProjection::replay(&events)
+    );
+}
+
+#[test]
+fn snapshot_from_an_older_projection_version_is_ignored() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    let store = EventStore::open(&db_path).unwrap();
+    let state = RunProjection {
+        spec_approved: true,
+        ..RunProjection::default()
+    };
+    store.save_snapshot("run-1", 7, &state).unwrap();
+    assert_eq!(
+        store.load_latest_snapshot("run-1").unwrap(),
+        Some((7, state.clone()))
+    );
+
+    // Snapshots saved before versioning stored the bare projection.
+    rusqlite::Connection::open(&db_path)
+        .unwrap()
+        .execute(
+            "INSERT INTO snapshots (run_id, seq, state_json) VALUES ('run-1', 9, ?1)",
+            [serde_json::to_string(&state).unwrap()],
+        )
+        .unwrap();
+    assert_eq!(store.load_latest_snapshot("run-1").unwrap(), None);
+}
+
+#[test]
+fn review_question_uses_returned_question_id() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: ").unwrap();
+
+    let run_id = test_run_id("question-id");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    answer_question(&run_id, "spec-q-2", "filled objective", Some(db_path)).unwrap();
+}
+
+#[test]
+fn implementer_nonzero_exit_blocks_review_and_close() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: break build [impl-fail]").unwrap();
+
+    let run_id = test_run_id("impl-fail");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    assert!(events.iter().any(|e| e.event_type == "run_failed"));
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "task_failed_terminal")
+    );
+    assert!(events.iter().all(|e| e.event_type != "review_requested"));
+    assert!(events.iter().all(|e| e.event_type != "task_closed"));
+}
+
+#[test]
+fn reviewer_missing_output_fails_closed() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: reviewer output absent [missing-review-output]",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("review-missing");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    assert!(events.iter().any(|e| e.event_type == "review_requested"));
+    assert!(events.iter().any(|e| e.event_type == "review_found_issues"));
+    let invalid_reviewer = events
+        .iter()
+        .find(|e| e.event_type == "review_found_issues")
+        .expect("missing review_found_issues");
+    assert!(
+        invalid_reviewer
+            .payload_json
+            .get("reason")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .contains("invalid reviewer output")
+    );
+    assert!(events.iter().all(|e| e.event_type != "review_approved"));
+    assert!(events.iter().all(|e| e.event_type != "task_closed"));
+}
+
+#[test]
+fn reviewer_findings_persist_and_reach_next_implementer_attempt() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature with rework loop",
+    )
+    .unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature with rework loop","acceptance":"Complete objective: implement feature with rework loop","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  implementer)
+    if [ "${THENCE_ATTEMPT:-1}" = "1" ]; then
+      echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    else
+      if grep -q "must-handle-edge-case" "${THENCE_CAPSULE_FILE}"; then
+        echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+      else
+        echo '{"submitted":false}' > "${THENCE_RESULT_FILE}"
+      fi
+    fi
+    ;;
+  reviewer)
+    if [ "${THENCE_ATTEMPT:-1}" = "1" ]; then
+      cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"approved":false,"findings":["must-handle-edge-case","add-regression-test"]}
+JSON
+    else
+      echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}"
+    fi
+    ;;
+  checks-proposer) echo '{"commands":["true"],"rationale":"ok"}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&agent_path).unwrap().permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&agent_path, perms).unwrap();
+    }
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("findings-forward");
+    execute_run(RunCommand {
+        plan_file: plan_path.clone(),
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+
+    let findings_event = events
+        .iter()
+        .find(|e| e.event_type == "review_found_issues" && e.attempt == Some(1))
+        .expect("missing review_found_issues for attempt 1");
+    let findings = findings_event
+        .payload_json
+        .get("findings")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    assert!(
+        findings
+            .iter()
+            .any(|v| v.as_str() == Some("must-handle-edge-case"))
+    );
+
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "task_claimed" && e.attempt == Some(2))
+    );
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "review_approved" && e.attempt == Some(2))
+    );
+    assert!(events.iter().any(|e| e.event_type == "task_closed"));
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+
+    let capsule = plan_path
+        .parent()
+        .unwrap()
+        .join(".thence")
+        .join("runs")
+        .join(&run_id)
+        .join("capsules")
+        .join("task-a")
+        .join("attempt2")
+        .join("implementer.json");
+    let capsule_raw = fs::read_to_string(capsule).unwrap();
+    assert!(capsule_raw.contains("must-handle-edge-case"));
+}
+
+#[test]
+fn duplicate_sanitized_task_ids_pause_translation() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: one\n- [ ] task_a: two").unwrap();
+
+    let run_id = test_run_id("dup-id");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("translation failure"));
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "spec_question_opened")
+    );
+    assert!(events.iter().any(|e| {
+        e.event_type == "human_input_requested"
+            && e.payload_json.get("question_id").and_then(|v| v.as_str())
+                == Some("spec-q-translate")
+    }));
+}
+
+#[test]
+fn resume_with_open_question_uses_real_question_id() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: ").unwrap();
+
+    let run_id = test_run_id("resume-qid");
+    let _ = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    });
+
+    let err = resume_run(&run_id, Some(db_path.clone())).unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    let latest_human_input_requested = events
+        .iter()
+        .rev()
+        .find(|e| e.event_type == "human_input_requested")
+        .expect("expected human_input_requested");
+    assert_eq!(
+        latest_human_input_requested
+            .payload_json
+            .get("question_id")
+            .and_then(|v| v.as_str()),
+        Some("spec-q-2")
+    );
+}
+
+#[test]
+fn missing_checks_fails_fast() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(test_run_id("checks-gate")),
+        state_db: Some(db_path),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("No checks configured"));
+}
+
+#[test]
+fn approved_checks_are_saved_and_reused_by_the_next_run() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run = |prefix: &str, checks: Option<&str>, no_checks_file: bool| {
+        let run_id = test_run_id(prefix);
+        execute_run(RunCommand {
+            plan_file: plan_path.clone(),
+            agent: "codex".to_string(),
+            workers: 1,
+            reviewers: 1,
+            checks: checks.map(ToString::to_string),
+            checks_from_file: None,
+            simulate: true,
+            log: None,
+            log_format: LogFormat::Compact,
+            resume: false,
+            run_id: Some(run_id.clone()),
+            state_db: Some(db_path.clone()),
+            allow_partial_completion: false,
+            trust_plan_checks: false,
+            interactive: false,
+            attempt_timeout_secs: None,
+            reviewer_timeout_secs: None,
+            max_attempts: None,
+            check_timeout_secs: None,
+            agent_spec_review: false,
+            no_spec_review: false,
+            propose_checks: false,
+            no_checks_file,
+            reconfigure_checks: false,
+            worktree_keep_on_fail: false,
+            dry_run: false,
+            only_task: Vec::new(),
+            debug_dump_spl: None,
+            artifacts_dir: None,
+        })
+        .map(|_| run_id)
+    };
+
+    run("checks-file-first", Some("true; echo ok"), false).unwrap();
+    let saved: serde_json::Value = serde_json::from_str(
+        &fs::read_to_string(tmp.path().join(".thence").join("checks.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(saved["commands"], serde_json::json!(["true", "echo ok"]));
+
+    let second = run("checks-file-second", None, false).unwrap();
+    let approved = EventStore::open(&db_path)
+        .unwrap()
+        .list_events_of_type(&second, "checks_approved")
+        .unwrap();
+    assert_eq!(approved.len(), 1);
+    assert_eq!(approved[0].payload_json["source"], "checks_file");
+    assert_eq!(
+        approved[0].payload_json["commands"],
+        serde_json::json!(["true", "echo ok"])
+    );
+
+    let err = run("checks-file-ignored", None, true).unwrap_err();
+    assert!(format!("{err}").contains("No checks configured"));
+}
+
+#[test]
+fn translation_pause_resume_regenerates_spl_and_completes() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: one\n- [ ] task_a: two").unwrap();
+
+    let run_id = test_run_id("translate-resume");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path.clone(),
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("translation failure"));
+
+    // Fix plan after pause and resume same run.
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: one\n- [ ] task-b: two | deps=task-a",
+    )
+    .unwrap();
+    answer_question(
+        &run_id,
+        "spec-q-translate",
+        "fixed plan",
+        Some(db_path.clone()),
+    )
+    .unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let run = store.get_run(&run_id).unwrap().expect("run row");
+    let events = store.list_events(&run_id).unwrap();
+    assert!(std::path::Path::new(&run.spl_plan_path).exists());
+    assert!(events.iter().any(|e| e.event_type == "plan_translated"));
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+}
+
+#[test]
+fn resume_retranslates_when_translated_plan_missing() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: clarify behavior ???").unwrap();
+
+    let run_id = test_run_id("resume-missing-translated");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path.clone(),
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+    answer_question(&run_id, "spec-q-1", "clarified", Some(db_path.clone())).unwrap();
+
+    let translated_path = plan_path
+        .parent()
+        .unwrap()
+        .join(".thence")
+        .join("runs")
+        .join(&run_id)
+        .join("translated_plan.json");
+    if translated_path.exists() {
+        fs::remove_file(translated_path).unwrap();
+    }
+
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    assert!(events.iter().any(|e| e.event_type == "plan_translated"));
+    assert!(events.iter().any(|e| e.event_type == "task_registered"));
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+}
+
+#[test]
+fn resume_refreshes_agent_command_before_initial_translation() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        r#"
+version = 2
+[agent]
+provider = "codex"
+command = "missing-codex-command"
+[checks]
+commands = ["true"]
+"#,
+    );
+
+    let run_id = test_run_id("refresh-agent-command");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path.clone(),
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"Complete objective: implement feature","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  implementer) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+  reviewer) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&agent_path).unwrap().permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&agent_path, perms).unwrap();
+    }
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    answer_question(&run_id, "spec-q-translate", "retry", Some(db_path.clone())).unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+    let translate_question_count = events
+        .iter()
+        .filter(|e| {
+            e.event_type == "spec_question_opened"
+                && e.payload_json.get("question_id").and_then(|v| v.as_str())
+                    == Some("spec-q-translate")
+        })
+        .count();
+    assert_eq!(translate_question_count, 1);
+}
+
+#[test]
+fn translation_failure_question_quotes_translator_stderr() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+echo "translator exploded: model quota exhausted" >&2
+exit 3
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("translate-stderr");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("translation failure"));
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let opened = events
+        .iter()
+        .find(|e| {
+            e.event_type == "spec_question_opened"
+                && e.payload_json.get("question_id").and_then(|v| v.as_str())
+                    == Some("spec-q-translate")
+        })
+        .expect("translation question");
+    let question = opened.payload_json["question"].as_str().unwrap();
+    assert!(question.contains("translator stderr (tail):"), "{question}");
+    assert!(question.contains("translator exploded: model quota exhausted"));
+    assert_eq!(
+        opened.payload_json["translator_stderr_tail"],
+        "translator exploded: model quota exhausted"
+    );
+}
+
+#[test]
+fn duplicate_task_ids_record_translation_failed_and_question() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: one\n- [ ] task_a: two").unwrap();
+
+    let run_id = test_run_id("translation-failed");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let failed = events
+        .iter()
+        .find(|e| e.event_type == "translation_failed")
+        .expect("translation_failed event");
+    let error = failed.payload_json["error"].as_str().unwrap();
+    assert!(error.contains("plan-translator"), "{error}");
+    assert!(events.iter().any(|e| {
+        e.event_type == "spec_question_opened"
+            && e.payload_json.get("question_id").and_then(|v| v.as_str())
+                == Some("spec-q-translate")
+    }));
+
+    let state = RunProjection::replay(&events);
+    assert_eq!(state.last_translation_error.as_deref(), Some(error));
+    assert!(state.open_questions.contains_key("spec-q-translate"));
+}
+
+#[test]
+fn translate_answer_does_not_bypass_spec_review_gate() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: one\n- [ ] task_a: two").unwrap();
+
+    let run_id = test_run_id("translate-no-bypass");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path.clone(),
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("translation failure"));
+
+    // Fix translation issue, but keep ambiguity marker that should be caught by review gate.
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: unclear behavior ???\n- [ ] task-b: follow up | deps=task-a",
+    )
+    .unwrap();
+    answer_question(
+        &run_id,
+        "spec-q-translate",
+        "retry translation",
+        Some(db_path.clone()),
+    )
+    .unwrap();
+    let err = resume_run(&run_id, Some(db_path.clone())).unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    assert!(events.iter().any(|e| {
+        e.event_type == "spec_question_opened"
+            && e.payload_json.get("question_id").and_then(|v| v.as_str()) == Some("spec-q-1")
+    }));
+    assert!(!events.iter().any(|e| e.event_type == "spec_approved"));
+    assert!(!events.iter().any(|e| e.event_type == "checks_approved"));
+    assert!(!events.iter().any(|e| e.event_type == "task_registered"));
+}
+
+#[test]
+fn subprocess_invalid_reviewer_output_fails_closed() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: run reviewer invalid output").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"run reviewer invalid output","acceptance":"Complete objective: run reviewer invalid output","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  implementer) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+  reviewer) echo '{' > "${THENCE_RESULT_FILE}" ;;
+  checks-proposer) echo '{"commands":["true"],"rationale":"ok"}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&agent_path).unwrap().permissions();
+        perms.set_mode(0o755);
+        fs::set_permissions(&agent_path, perms).unwrap();
+    }
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("invalid-reviewer-json");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    assert!(events.iter().any(|e| e.event_type == "review_requested"));
+    assert!(events.iter().any(|e| e.event_type == "review_found_issues"));
+    assert!(events.iter().all(|e| e.event_type != "review_approved"));
+    assert!(events.iter().all(|e| e.event_type != "task_closed"));
+}
+
+#[test]
+fn agent_env_from_config_reaches_subprocess_agent() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<JSON
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"token=${CUSTOM_AGENT_TOKEN}","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  reviewer) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo "{\"submitted\":true,\"token\":\"${CUSTOM_AGENT_TOKEN}\"}" > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\nenv = {{ CUSTOM_AGENT_TOKEN = \"s3cret-value\" }}\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("agent-env");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let registered = events
+        .iter()
+        .find(|e| e.event_type == "task_registered")
+        .unwrap();
+    assert_eq!(registered.payload_json["objective"], "token=s3cret-value");
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+}
+
+#[test]
+fn agent_reported_model_is_recorded_on_work_and_review_events() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  reviewer) echo '{"approved":true,"findings":[],"agent_model":"review-model"}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo '{"submitted":true,"agent_model":"impl-model","agent_version":"1.2.3"}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("agent-model");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let submitted = store
+        .list_events_of_type(&run_id, "work_submitted")
+        .unwrap();
+    assert_eq!(submitted[0].payload_json["agent_model"], "impl-model");
+    assert_eq!(submitted[0].payload_json["agent_version"], "1.2.3");
+    let approved = store
+        .list_events_of_type(&run_id, "review_approved")
+        .unwrap();
+    assert_eq!(approved[0].payload_json["agent_model"], "review-model");
+    assert!(approved[0].payload_json["agent_version"].is_null());
+}
+
+#[test]
+fn implementer_runs_under_an_active_lease_that_is_released_afterwards() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    let seen_path = tmp.path().join("lease-seen.json");
+    let run_id = test_run_id("lease-active");
+    let lease_glob = tmp
+        .path()
+        .join(".thence")
+        .join("runs")
+        .join(&run_id)
+        .join("leases");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        format!(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${{THENCE_ROLE:-}}" in
+  plan-translator)
+    cat > "${{THENCE_RESULT_FILE}}" <<'JSON'
+{{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"]}}]}}
+JSON
+    ;;
+  reviewer) echo '{{"approved":true,"findings":[]}}' > "${{THENCE_RESULT_FILE}}" ;;
+  *)
+    cp "{leases}"/*/attempt1/implementer.json "{seen}"
+    echo '{{"submitted":true}}' > "${{THENCE_RESULT_FILE}}"
+    ;;
+esac
+"#,
+            leases = lease_glob.display(),
+            seen = seen_path.display()
+        ),
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let seen: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&seen_path).unwrap()).unwrap();
+    assert_eq!(seen["state"], "active");
+    assert_eq!(seen["role"], "implementer");
+    assert_eq!(seen["owner_pid"], std::process::id());
+
+    let store = EventStore::open(&db_path).unwrap();
+    let submitted = store
+        .list_events_of_type(&run_id, "work_submitted")
+        .unwrap();
+    let lease_path = submitted[0].payload_json["lease_path"].as_str().unwrap();
+    let released: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(lease_path).unwrap()).unwrap();
+    assert_eq!(released["state"], "released");
+}
+
+#[cfg(unix)]
+#[test]
+fn sigint_mid_attempt_releases_lease_and_pauses_run() {
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    let started_path = tmp.path().join("implementer-started");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        format!(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${{THENCE_ROLE:-}}" in
+  plan-translator)
+    cat > "${{THENCE_RESULT_FILE}}" <<'JSON'
+{{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"]}}]}}
+JSON
+    ;;
+  reviewer) echo '{{"approved":true,"findings":[]}}' > "${{THENCE_RESULT_FILE}}" ;;
+  *)
+    touch "{started}"
+    sleep 30
+    echo '{{"submitted":true}}' > "${{THENCE_RESULT_FILE}}"
+    ;;
+esac
+"#,
+            started = started_path.display()
+        ),
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("sigint");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_thence"))
+        .arg("run")
+        .arg(&plan_path)
+        .arg("--run-id")
+        .arg(&run_id)
+        .arg("--state-db")
+        .arg(&db_path)
+        .spawn()
+        .unwrap();
+
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while !started_path.exists() {
+        assert!(Instant::now() < deadline, "implementer never started");
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let killed = Command::new("kill")
+        .arg("-INT")
+        .arg(child.id().to_string())
+        .status()
+        .unwrap();
+    assert!(killed.success());
+
+    let deadline = Instant::now() + Duration::from_secs(20);
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            panic!("thence did not exit after SIGINT");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+    assert!(status.success(), "thence exited with {status}");
+
+    let store = EventStore::open(&db_path).unwrap();
+    let paused = store.list_events_of_type(&run_id, "run_paused").unwrap();
+    assert_eq!(paused.len(), 1);
+    assert_eq!(paused[0].payload_json["reason"], "interrupted");
+    let interrupted = store
+        .list_events_of_type(&run_id, "attempt_interrupted")
+        .unwrap();
+    assert_eq!(interrupted.len(), 1);
+    assert_eq!(interrupted[0].attempt, Some(1));
+    assert!(
+        store
+            .list_events_of_type(&run_id, "work_submitted")
+            .unwrap()
+            .is_empty()
+    );
+
+    let task_id = interrupted[0].task_id.clone().unwrap();
+    let lease_path = tmp
+        .path()
+        .join(".thence")
+        .join("runs")
+        .join(&run_id)
+        .join("leases")
+        .join(task_id)
+        .join("attempt1")
+        .join("implementer.json");
+    let lease: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(lease_path).unwrap()).unwrap();
+    assert_eq!(lease["state"], "released");
+}
+
+#[test]
+fn structured_reviewer_findings_reach_next_implementer_capsule() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  reviewer)
+    if [ "${THENCE_ATTEMPT}" = "1" ]; then
+      echo '{"approved":false,"findings":[{"message":"unchecked unwrap","severity":"high","file":"src/lib.rs","line":42},"missing docs"]}' > "${THENCE_RESULT_FILE}"
+    else
+      echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}"
+    fi
+    ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("structured-findings");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let issues = store
+        .list_events_of_type(&run_id, "review_found_issues")
+        .unwrap();
+    assert_eq!(issues[0].payload_json["reason"], "unchecked unwrap");
+    assert_eq!(
+        issues[0].payload_json["structured_findings"][1]["message"],
+        "missing docs"
+    );
+
+    let capsule = read_capsule(&run_id, "task-a", 2, "implementer", Some(db_path)).unwrap();
+    let unresolved = &capsule["payload"]["unresolved_findings"][0];
+    assert_eq!(unresolved["finding"], "unchecked unwrap");
+    assert_eq!(unresolved["attempts"], serde_json::json!([1]));
+    assert_eq!(unresolved["detail"]["severity"], "high");
+    assert_eq!(unresolved["detail"]["file"], "src/lib.rs");
+    assert_eq!(unresolved["detail"]["line"], 42);
+}
+
+#[test]
+fn advisory_only_rejection_still_closes_the_task() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  reviewer)
+    echo '{"approved":false,"findings":[{"message":"consider a doc comment","blocking":false}]}' > "${THENCE_RESULT_FILE}"
+    ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("advisory-findings");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    assert!(
+        store
+            .list_events_of_type(&run_id, "review_found_issues")
+            .unwrap()
+            .is_empty()
+    );
+    let approved = store
+        .list_events_of_type(&run_id, "review_approved")
+        .unwrap();
+    assert_eq!(approved.len(), 1);
+    assert_eq!(
+        approved[0].payload_json["advisories"],
+        serde_json::json!([{"message": "consider a doc comment", "blocking": false}])
+    );
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "checks_reported")
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "task_closed")
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn recurring_finding_is_forwarded_once_with_every_attempt() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  reviewer)
+    case "${THENCE_ATTEMPT}" in
+      1) echo '{"approved":false,"findings":["Missing tests"]}' > "${THENCE_RESULT_FILE}" ;;
+      2) echo '{"approved":false,"findings":["missing   tests","bad name"]}' > "${THENCE_RESULT_FILE}" ;;
+      *) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+    esac
+    ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("dedupe-findings");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let capsule = read_capsule(&run_id, "task-a", 3, "implementer", Some(db_path)).unwrap();
+    let unresolved = capsule["payload"]["unresolved_findings"]
+        .as_array()
+        .unwrap();
+    assert_eq!(unresolved.len(), 2);
+    assert_eq!(unresolved[0]["finding"], "Missing tests");
+    assert_eq!(unresolved[0]["attempts"], serde_json::json!([1, 2]));
+    assert_eq!(unresolved[1]["finding"], "bad name");
+    assert_eq!(unresolved[1]["attempts"], serde_json::json!([2]));
+}
+
+#[test]
+fn task_deadline_fails_task_before_attempts_run_out() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"],"deadline_secs":1}]}
+JSON
+    ;;
+  reviewer) echo '{"approved":false,"findings":["still broken"]}' > "${THENCE_RESULT_FILE}" ;;
+  *)
+    sleep 1
+    echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n[run]\nmax_attempts = 5\ntask_deadline_secs = 3600\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("task-deadline");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let claims = store.list_events_of_type(&run_id, "task_claimed").unwrap();
+    assert!(claims.len() < 5, "claimed {} attempts", claims.len());
+    let failed = store
+        .list_events_of_type(&run_id, "task_failed_terminal")
+        .unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].payload_json["reason"], "task deadline exceeded");
+    assert_eq!(failed[0].payload_json["deadline_secs"], 1);
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "run_failed")
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn repeated_resumes_record_plan_repair_once_per_source() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: store sessions ???").unwrap();
+
+    let run_id = test_run_id("plan-translated-dedupe");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: true,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let spl_path = std::path::PathBuf::from(store.get_run(&run_id).unwrap().unwrap().spl_plan_path);
+    // Each resume re-runs the spec gate, which repairs the SPL from the frozen plan and then
+    // pauses on the same ambiguity again.
+    for _ in 0..2 {
+        store
+            .append_event(
+                &run_id,
+                &NewEvent::simple(
+                    "spec_question_resolved",
+                    serde_json::json!({"question_id": "spec-q-1"}),
+                ),
+            )
+            .unwrap();
+        fs::write(&spl_path, "(given (task stale))\n").unwrap();
+        let err = resume_run(&run_id, Some(db_path.clone())).unwrap_err();
+        assert!(format!("{err}").contains("spec clarification"));
+    }
+    for _ in 0..2 {
+        store
+            .append_event(
+                &run_id,
+                &NewEvent::simple(
+                    "spec_question_resolved",
+                    serde_json::json!({"question_id": "spec-q-1"}),
+                ),
+            )
+            .unwrap();
+        fs::remove_file(&spl_path).unwrap();
+        resume_run(&run_id, Some(db_path.clone())).unwrap_err();
+    }
+
+    let sources = store
+        .list_events_of_type(&run_id, "plan_translated")
+        .unwrap()
+        .into_iter()
+        .map(|e| e.payload_json["source"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sources,
+        vec![
+            "agent",
+            "resume_reconciled_from_frozen",
+            "resume_regenerated_from_frozen"
+        ]
+    );
+}
+
+#[test]
+fn proposed_checks_are_approved_and_gate_the_run() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  checks-proposer) echo '{"commands":["true"],"rationale":"no test suite yet"}' > "${THENCE_RESULT_FILE}" ;;
+  reviewer) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("propose-checks");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: true,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("awaiting checks approval"));
+
+    let store = EventStore::open(&db_path).unwrap();
+    let proposed = store
+        .list_events_of_type(&run_id, "checks_proposed")
+        .unwrap();
+    assert_eq!(proposed.len(), 1);
+    assert_eq!(
+        proposed[0].payload_json["commands"],
+        serde_json::json!(["true"])
+    );
+    assert_eq!(proposed[0].payload_json["rationale"], "no test suite yet");
+    let questions = store.unresolved_questions(&run_id).unwrap();
+    assert_eq!(questions.len(), 1);
+    assert_eq!(questions[0].0, "checks-q-propose");
+
+    answer_question(
+        &run_id,
+        "checks-q-propose",
+        "approve",
+        Some(db_path.clone()),
+    )
+    .unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+
+    let approved = store
+        .list_events_of_type(&run_id, "checks_approved")
+        .unwrap();
+    assert_eq!(approved.len(), 1);
+    assert_eq!(approved[0].payload_json["source"], "proposer");
+    assert_eq!(
+        approved[0].payload_json["commands"],
+        serde_json::json!(["true"])
+    );
+    assert_eq!(
+        store
+            .list_events_of_type(&run_id, "run_completed")
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn agent_spec_review_question_pauses_run() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: store sessions").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"store sessions","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  spec-reviewer)
+    echo '{"approved":false,"question_id":"spec-q-storage","question":"Which session store should be used?"}' > "${THENCE_RESULT_FILE}"
+    ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("agent-spec-review");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: true,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("spec clarification"));
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let opened = events
+        .iter()
+        .find(|e| e.event_type == "spec_question_opened")
+        .unwrap();
+    assert_eq!(opened.payload_json["question_id"], "spec-q-storage");
+    assert_eq!(
+        opened.payload_json["question"],
+        "Which session store should be used?"
+    );
+    assert!(events.iter().any(|e| e.event_type == "run_paused"));
+    assert!(!events.iter().any(|e| e.event_type == "spec_approved"));
+}
+
+#[test]
+fn subprocess_implementer_times_out_after_attempt_timeout() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: hang past timeout").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"hang past timeout","acceptance":"Complete objective: hang past timeout","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  implementer) sleep 5; echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("attempt-timeout");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: Some(1),
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let submitted = events
+        .iter()
+        .find(|e| e.event_type == "work_submitted" && e.attempt == Some(1))
+        .expect("missing work_submitted for attempt 1");
+    assert_eq!(
+        submitted
+            .payload_json
+            .get("exit_code")
+            .and_then(|v| v.as_i64()),
+        Some(124)
+    );
+    assert!(events.iter().all(|e| e.event_type != "review_requested"));
+    assert!(events.iter().any(|e| e.event_type == "run_failed"));
+}
+
+#[test]
+fn slow_reviewer_times_out_into_invalid_output_gate() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: slow review").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"slow review","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  reviewer) sleep 5; echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("reviewer-timeout");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: Some(1),
+        max_attempts: Some(1),
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let issues = events
+        .iter()
+        .find(|e| e.event_type == "review_found_issues")
+        .expect("review_found_issues");
+    assert_eq!(issues.payload_json["source"], "reviewer_output_validation");
+    assert_eq!(issues.payload_json["timed_out"], true);
+    assert_eq!(
+        issues.payload_json["reason"],
+        "invalid reviewer output: reviewer timed out after 1s"
+    );
+    assert!(events.iter().all(|e| e.event_type != "review_approved"));
+    assert!(events.iter().any(|e| e.event_type == "run_failed"));
+}
+
+#[test]
+fn on_close_cleanup_removes_worktree_but_keeps_capsules_and_logs() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        "version = 2\n[checks]\ncommands = [\"true\"]\n[worktree]\ncleanup = \"on_close\"\n",
+    );
+
+    let run_id = test_run_id("cleanup");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert!(events.iter().any(|e| e.event_type == "task_closed"));
+    let run_dir = tmp.path().join(".thence").join("runs").join(&run_id);
+    assert!(!run_dir.join("worktrees/thence/task-a/v1/impl-1").exists());
+    let capsules = fs::read_dir(run_dir.join("capsules")).unwrap().count();
+    assert!(capsules > 0);
+    let logs_dir = run_dir.join("logs/task-a/attempt1");
+    for name in [
+        "implementer_attempt1_stdout.log",
+        "implementer_attempt1_stderr.log",
+        "reviewer_attempt1_stdout.log",
+    ] {
+        assert!(logs_dir.join(name).exists(), "missing kept log {name}");
+    }
+}
+
+#[test]
+fn artifacts_dir_keeps_run_artifacts_out_of_the_repo() {
+    let tmp = tempdir().unwrap();
+    let artifacts = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    git(tmp.path(), &["init", "-q"]);
+    fs::write(tmp.path().join("README.md"), "base\n").unwrap();
+    git(tmp.path(), &["add", "README.md"]);
+    git(tmp.path(), &["commit", "-q", "-m", "base"]);
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[checks]\ncommands = [\"true\"]\n[run]\nartifacts_dir = \"{}\"\n",
+            artifacts.path().display()
+        ),
+    );
+
+    let run_id = test_run_id("artifacts-dir");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let report = status_report(&run_id, Some(db_path.clone())).unwrap();
+    assert_eq!(report.status, "completed");
+    let run_dir = artifacts.path().join(&run_id);
+    assert_eq!(report.artifacts_dir, run_dir);
+    assert!(run_dir.join("spec.md").is_file());
+    assert!(run_dir.join("worktrees").is_dir());
+    assert!(fs::read_dir(run_dir.join("capsules")).unwrap().count() > 0);
+    assert!(read_capsule(&run_id, "task_a", 1, "reviewer", Some(db_path)).is_ok());
+    assert!(!tmp.path().join(".thence").join("runs").exists());
+}
+
+#[test]
+fn extra_policy_rule_keeps_a_blocked_task_from_being_claimed() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: implement feature\n- [ ] task-b: implement follow-up",
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        "version = 2\n[checks]\ncommands = [\"true\"]\n[policy]\nextra_rules_file = \".thence/policy.spl\"\n",
+    );
+    fs::write(
+        tmp.path().join(".thence").join("policy.spl"),
+        "; hold task_b back\n(always hold-b (task task_b) (claim-blocked task_b))\n",
+    )
+    .unwrap();
+
+    let run_id = test_run_id("extra-policy-rules");
+    let _ = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    });
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let claimed = events
+        .iter()
+        .filter(|e| e.event_type == "task_claimed")
+        .filter_map(|e| e.task_id.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(claimed, vec!["task_a".to_string()]);
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "task_closed" && e.task_id.as_deref() == Some("task_a"))
+    );
+    let failed = events
+        .iter()
+        .find(|e| e.event_type == "run_failed")
+        .expect("run_failed");
+    assert_eq!(failed.payload_json["reason"], "unschedulable state");
+}
+
+#[test]
+fn attempt_phases_record_their_wall_clock_duration() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"done","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  implementer)
+    sleep 0.4
+    echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    ;;
+  *)
+    sleep 0.3
+    echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}"
+    ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("phase-durations");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("sleep 0.2".to_string()),
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let duration_ms = |event_type: &str| {
+        events
+            .iter()
+            .find(|e| e.event_type == event_type)
+            .and_then(|e| e.payload_json["duration_ms"].as_u64())
+            .unwrap_or_else(|| panic!("{event_type} has no duration_ms"))
+    };
+    assert!(duration_ms("work_submitted") >= 400);
+    assert!(duration_ms("review_approved") >= 300);
+    assert!(duration_ms("checks_reported") >= 200);
+}
+
+#[test]
+fn plan_read_from_a_reader_is_saved_in_the_run_dir_and_runs() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    write_repo_config(tmp.path(), "version = 2\n[checks]\ncommands = [\"true\"]\n");
+
+    let run_id = test_run_id("plan-stdin");
+    execute_run_from_reader(
+        RunCommand {
+            plan_file: std::path::PathBuf::new(),
+            agent: "codex".to_string(),
+            workers: 1,
+            reviewers: 1,
+            checks: None,
+            checks_from_file: None,
+            simulate: true,
+            log: None,
+            log_format: LogFormat::Compact,
+            resume: false,
+            run_id: Some(run_id.clone()),
+            state_db: Some(db_path.clone()),
+            allow_partial_completion: false,
+            trust_plan_checks: false,
+            interactive: false,
+            attempt_timeout_secs: None,
+            reviewer_timeout_secs: None,
+            max_attempts: None,
+            check_timeout_secs: None,
+            agent_spec_review: false,
+            no_spec_review: false,
+            propose_checks: false,
+            no_checks_file: false,
+            reconfigure_checks: false,
+            worktree_keep_on_fail: false,
+            dry_run: false,
+            only_task: Vec::new(),
+            debug_dump_spl: None,
+            artifacts_dir: None,
+        },
+        tmp.path(),
+        "- [ ] task-a: implement feature".as_bytes(),
+    )
+    .unwrap();
+
+    let report = status_report(&run_id, Some(db_path.clone())).unwrap();
+    assert_eq!(report.status, "completed");
+    let saved = report.artifacts_dir.join("stdin-spec.md");
+    assert_eq!(std::path::PathBuf::from(&report.plan_path), saved);
+    assert_eq!(
+        fs::read_to_string(&saved).unwrap(),
+        "- [ ] task-a: implement feature"
+    );
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert!(events.iter().any(|e| e.event_type == "plan_translated"));
+}
+
+#[test]
+fn state_db_from_a_newer_schema_is_refused() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    drop(EventStore::open(&db_path).unwrap());
+    let newer = thence::events::schema::SCHEMA_VERSION + 1;
+    rusqlite::Connection::open(&db_path)
+        .unwrap()
+        .pragma_update(None, "user_version", newer)
+        .unwrap();
+
+    for err in [
+        EventStore::open(&db_path).err().expect("open must fail"),
+        EventStore::open_read_only(&db_path)
+            .err()
+            .expect("read-only open must fail"),
+    ] {
+        let msg = format!("{err:#}");
+        assert!(msg.contains(&format!("schema version {newer}")), "{msg}");
+        assert!(msg.contains("upgrade thence"), "{msg}");
+    }
+}
+
+#[test]
+fn task_added_to_the_frozen_plan_is_registered_on_resume() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "Implement a tiny parser with tests.").unwrap();
+
+    let run_id = test_run_id("plan-reconcile");
+    let run_dir = tmp.path().join(".thence").join("runs").join(&run_id);
+    fs::create_dir_all(&run_dir).unwrap();
+    let spl_path = run_dir.join("plan.spl");
+    fs::write(&spl_path, "(given (task task-a))\n(given (ready task-a))\n").unwrap();
+    fs::write(
+        run_dir.join("spec.md"),
+        "Implement a tiny parser with tests.",
+    )
+    .unwrap();
+    fs::write(
+        run_dir.join("translated_plan.json"),
+        r#"{
+  "tasks": [
+    {"id":"task-a","objective":"build parser","acceptance":"done","dependencies":[],"checks":["true"]},
+    {"id":"task-b","objective":"document parser","acceptance":"done","dependencies":["task-a"],"checks":["true"]}
+  ],
+  "spl": "(given (task task-a))\n(given (ready task-a))\n(given (task task-b))\n(given (depends-on task-b task-a))\n"
+}"#,
+    )
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    store
+        .create_run(&RunRow {
+            id: run_id.clone(),
+            plan_path: plan_path.display().to_string(),
+            plan_sha256: "abc".to_string(),
+            spl_plan_path: spl_path.display().to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            status: "running".to_string(),
+            config_json: serde_json::json!({
+                "agent": "codex",
+                "workers": 1,
+                "reviewers": 1,
+                "checks": ["true"],
+                "checks_from_cli": true,
+                "simulate": true,
+                "allow_partial_completion": false,
+                "trust_plan_checks": false,
+                "interactive": false,
+                "max_attempts": 3,
+                "check_timeout_secs": 60,
+                "attempt_timeout_secs": 120
+            }),
+        })
+        .unwrap();
+    let task_a_event = |event_type: &str, payload: serde_json::Value| NewEvent {
+        event_type: event_type.to_string(),
+        task_id: Some("task-a".to_string()),
+        actor_role: None,
+        actor_id: None,
+        attempt: (event_type != "task_registered").then_some(1),
+        payload_json: payload,
+        dedupe_key: (event_type == "task_registered").then(|| "task_registered:task-a".to_string()),
+    };
+    for event in [
+        NewEvent::simple("run_started", serde_json::json!({})),
+        NewEvent::simple("spec_approved", serde_json::json!({"approved": true})),
+        NewEvent::simple("checks_approved", serde_json::json!({"commands": ["true"]})),
+        task_a_event(
+            "task_registered",
+            serde_json::json!({
+                "task_id": "task-a",
+                "objective": "build parser",
+                "acceptance": "done",
+                "dependencies": [],
+                "checks": []
+            }),
+        ),
+        task_a_event("task_claimed", serde_json::json!({"attempt": 1})),
+        task_a_event("task_closed", serde_json::json!({"closed": true})),
+    ] {
+        store.append_event(&run_id, &event).unwrap();
+    }
+    let before = store.list_events(&run_id).unwrap().len();
+
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let new_events = &events[before..];
+    assert!(
+        new_events
+            .iter()
+            .all(|e| e.task_id.as_deref() != Some("task-a")),
+        "task-a was touched: {new_events:?}"
+    );
+    assert!(
+        new_events
+            .iter()
+            .any(|e| e.event_type == "task_registered" && e.task_id.as_deref() == Some("task-b"))
+    );
+    assert!(
+        new_events
+            .iter()
+            .any(|e| e.event_type == "task_closed" && e.task_id.as_deref() == Some("task-b"))
+    );
+    assert_eq!(
+        events.last().map(|e| e.event_type.as_str()),
+        Some("run_completed")
+    );
+    assert!(
+        fs::read_to_string(&spl_path)
+            .unwrap()
+            .contains("(task task-b)")
+    );
+}
+
+#[test]
+fn checks_from_file_take_precedence_over_config_checks() {
+    let tmp = tempdir().unwrap();
+    let shared = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let checks_path = shared.path().join("checks.toml");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    fs::write(&checks_path, "commands = [\"true\", \"test -d .\"]\n").unwrap();
+    write_repo_config(
+        tmp.path(),
+        "version = 2\n[checks]\ncommands = [\"false\"]\n",
+    );
+
+    let run_id = test_run_id("checks-from-file");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: Some(checks_path.clone()),
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let approved = events
+        .iter()
+        .find(|e| e.event_type == "checks_approved")
+        .expect("checks_approved");
+    assert_eq!(
+        approved.payload_json["commands"],
+        serde_json::json!(["true", "test -d ."])
+    );
+    assert_eq!(approved.payload_json["source"], "checks_from_file");
+    assert_eq!(
+        approved.payload_json["source_path"],
+        serde_json::json!(checks_path)
+    );
+    assert_eq!(
+        status_report(&run_id, Some(db_path)).unwrap().status,
+        "completed"
+    );
+}
+
+#[test]
+fn attempt_budget_pauses_the_run_until_it_is_raised() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: broken feature [impl-fail]\n- [ ] task-b: other broken feature [impl-fail]",
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        "version = 2\n[checks]\ncommands = [\"true\"]\n[run]\nmax_total_attempts = 2\n",
+    );
+
+    let run_id = test_run_id("attempt-budget");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: true,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let claims = |store: &EventStore| {
+        store
+            .list_events_of_type(&run_id, "task_claimed")
+            .unwrap()
+            .len()
+    };
+    assert_eq!(claims(&store), 2);
+    let state = RunProjection::replay(&store.list_events(&run_id).unwrap());
+    assert!(state.terminal.is_none());
+    assert!(state.open_questions.contains_key("budget-q-attempts-2"));
+
+    let err =
+        answer_question(&run_id, "budget-q-attempts-2", "2", Some(db_path.clone())).unwrap_err();
+    assert!(format!("{err:#}").contains("greater than the 2 attempts already claimed"));
+
+    answer_question(&run_id, "budget-q-attempts-2", "3", Some(db_path.clone())).unwrap();
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+    assert_eq!(claims(&store), 3);
+    let state = RunProjection::replay(&store.list_events(&run_id).unwrap());
+    assert!(state.terminal.is_none());
+    assert!(state.open_questions.contains_key("budget-q-attempts-3"));
+}
+
+#[test]
+fn failed_check_commands_are_projected_and_inspected() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        "version = 2\n[checks]\ncommands = [\"true\", \"exit 3\"]\n",
+    );
+
+    let run_id = test_run_id("failed-checks");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: Some(1),
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let state = RunProjection::replay(&events);
+    let task = &state.tasks["task_a"];
+    assert!(task.terminal_failed);
+    assert_eq!(
+        task.failed_checks.get(&1),
+        Some(&vec!["exit 3".to_string()])
+    );
+
+    let inspected = inspect_run_json(&run_id, Some(db_path)).unwrap();
+    assert_eq!(
+        inspected["failed_checks"],
+        serde_json::json!([{"task_id": "task_a", "attempt": 1, "commands": ["exit 3"]}])
+    );
+}
+
+#[test]
+fn only_task_registers_the_task_and_its_dependency_closure() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: base\n- [ ] task-b: builds on a | deps=task-a\n- [ ] task-c: unrelated",
+    )
+    .unwrap();
+    let run = |run_id: &str, only_task: &str| {
+        execute_run(RunCommand {
+            plan_file: plan_path.clone(),
+            agent: "codex".to_string(),
+            workers: 1,
+            reviewers: 1,
+            checks: Some("true".to_string()),
+            checks_from_file: None,
+            simulate: true,
+            log: None,
+            log_format: LogFormat::Compact,
+            resume: false,
+            run_id: Some(run_id.to_string()),
+            state_db: Some(db_path.clone()),
+            allow_partial_completion: false,
+            trust_plan_checks: false,
+            interactive: false,
+            attempt_timeout_secs: None,
+            reviewer_timeout_secs: None,
+            max_attempts: None,
+            check_timeout_secs: None,
+            agent_spec_review: false,
+            no_spec_review: false,
+            propose_checks: false,
+            no_checks_file: false,
+            reconfigure_checks: false,
+            worktree_keep_on_fail: false,
+            dry_run: false,
+            only_task: vec![only_task.to_string()],
+            debug_dump_spl: None,
+            artifacts_dir: None,
+        })
+    };
+
+    let run_id = test_run_id("only-task");
+    run(&run_id, "task_b").unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let registered = store
+        .list_events_of_type(&run_id, "task_registered")
+        .unwrap()
+        .into_iter()
+        .filter_map(|e| e.task_id)
+        .collect::<Vec<_>>();
+    assert_eq!(registered, vec!["task_a", "task_b"]);
+    let state = RunProjection::replay(&store.list_events(&run_id).unwrap());
+    assert_eq!(state.terminal.as_deref(), Some("run_completed"));
+
+    let err = run(&test_run_id("only-task-unknown"), "task_z").unwrap_err();
+    assert!(
+        format!("{err:#}").contains("--only-task task_z is not a task"),
+        "{err:#}"
+    );
+}
+
+#[test]
+fn check_retries_rerun_a_flaky_command_before_failing_the_attempt() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let counter = tmp.path().join("check-ran");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[checks]\nretries = 1\ncommands = [\"test -f {0} || {{ touch {0}; exit 1; }}\"]\n",
+            counter.display()
+        ),
+    );
+
+    let run_id = test_run_id("check-retries");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: None,
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: Some(1),
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    let state = RunProjection::replay(&events);
+    assert!(state.tasks["task_a"].closed);
+
+    let reports = store
+        .list_events_of_type(&run_id, "checks_reported")
+        .unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].payload_json["passed"], serde_json::json!(true));
+    let result = &reports[0].payload_json["results"][0];
+    assert_eq!(result["ok"], serde_json::json!(true));
+    let tries = result["tries"].as_array().unwrap();
+    assert_eq!(tries.len(), 2);
+    assert_eq!(tries[0]["ok"], serde_json::json!(false));
+    assert_eq!(tries[1]["ok"], serde_json::json!(true));
+}
+
+#[test]
+fn plan_in_a_repo_subdirectory_keeps_artifacts_at_the_repo_root() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    let spec_dir = tmp.path().join("docs").join("specs");
+    let plan_path = spec_dir.join("plan.md");
+    git(tmp.path(), &["init", "-q"]);
+    fs::write(tmp.path().join("README.md"), "repo\n").unwrap();
+    git(tmp.path(), &["add", "README.md"]);
+    git(tmp.path(), &["commit", "-q", "-m", "base"]);
+    fs::create_dir_all(&spec_dir).unwrap();
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run_id = test_run_id("nested-plan");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert!(events.iter().any(|e| e.event_type == "run_completed"));
+    assert!(
+        tmp.path()
+            .join(".thence")
+            .join("runs")
+            .join(&run_id)
+            .is_dir()
+    );
+    assert!(!spec_dir.join(".thence").exists());
+    assert!(!tmp.path().join("docs").join(".thence").exists());
+
+    // The root is recorded, so resume does not have to find it again from the plan path.
+    let run = EventStore::open(&db_path)
+        .unwrap()
+        .get_run(&run_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        run.config_json["repo_root"],
+        serde_json::json!(tmp.path().canonicalize().unwrap())
+    );
+}
+
+/// CPU time the calling thread has used, from `/proc/thread-self/stat` (clock ticks of 10ms).
+#[cfg(target_os = "linux")]
+fn thread_cpu_ms() -> u64 {
+    let stat = fs::read_to_string("/proc/thread-self/stat").unwrap();
+    let fields = stat
+        .rsplit_once(") ")
+        .unwrap()
+        .1
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    let ticks = fields[11].parse::<u64>().unwrap() + fields[12].parse::<u64>().unwrap();
+    ticks * 10
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn supervisor_polls_instead_of_spinning_while_another_attempt_is_in_flight() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    fs::write(&plan_path, "- [ ] task-a: first\n- [ ] task-b: second").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n(given (task task-b))\n(given (depends-on task-b task-a))\n","tasks":[{"id":"task-a","objective":"first","acceptance":"done","dependencies":[],"checks":["true"]},{"id":"task-b","objective":"second","acceptance":"done","dependencies":["task-a"],"checks":["true"]}]}
+JSON
+    ;;
+  implementer)
+    sleep 0.5
+    echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    ;;
+  *)
+    echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}"
+    ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[run]\npoll_interval_ms = 100\n",
+            agent_path.display()
+        ),
+    );
+
+    // A second worker claims task-b while task-a runs and only records its close later.
+    let run_id = test_run_id("poll-in-flight");
+    let lease_path = tmp
+        .path()
+        .join(".thence/runs")
+        .join(&run_id)
+        .join("leases/task-b/attempt1/implementer.json");
+    let other_worker = {
+        let (db_path, run_id) = (db_path.clone(), run_id.clone());
+        std::thread::spawn(move || {
+            let store = loop {
+                if let Ok(store) = EventStore::open(&db_path) {
+                    break store;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            };
+            let has = |event_type: &str, task_id: &str| {
+                store
+                    .list_events_of_type(&run_id, event_type)
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|e| e.task_id.as_deref() == Some(task_id))
+            };
+            while !has("task_claimed", "task-a") {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            let now = chrono::Utc::now().to_rfc3339();
+            fs::create_dir_all(lease_path.parent().unwrap()).unwrap();
+            fs::write(
+                &lease_path,
+                serde_json::json!({
+                    "version": 1,
+                    "run_id": run_id,
+                    "task_id": "task-b",
+                    "attempt": 1,
+                    "role": "implementer",
+                    "owner_pid": std::process::id(),
+                    "started_at": now,
+                    "last_seen_at": now,
+                    "state": "active"
+                })
+                .to_string(),
+            )
+            .unwrap();
+            let event = |event_type: &str, payload: serde_json::Value| NewEvent {
+                event_type: event_type.to_string(),
+                task_id: Some("task-b".to_string()),
+                actor_role: Some("worker".to_string()),
+                actor_id: Some("other-worker".to_string()),
+                attempt: Some(1),
+                payload_json: payload,
+                dedupe_key: None,
+            };
+            store
+                .append_event(
+                    &run_id,
+                    &event("task_claimed", serde_json::json!({"attempt": 1})),
+                )
+                .unwrap();
+            while !has("task_closed", "task-a") {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1500));
+            store
+                .append_event(
+                    &run_id,
+                    &event("task_closed", serde_json::json!({"closed": true})),
+                )
+                .unwrap();
+        })
+    };
+
+    let cpu_before = thread_cpu_ms();
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+    let cpu_ms = thread_cpu_ms() - cpu_before;
+    other_worker.join().unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let claims = events
+        .iter()
+        .filter(|e| e.event_type == "task_claimed")
+        .filter_map(|e| e.task_id.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(claims, vec!["task-a", "task-b"]);
+    assert_eq!(events.last().unwrap().event_type, "run_completed");
+    // Waiting out the other worker's 1.5s must not keep this thread busy.
+    assert!(
+        cpu_ms < 750,
+        "supervisor used {cpu_ms}ms of CPU while waiting"
+    );
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) -> String {
+    let out = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(out.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&out.stdout).to_string()
+}
+
+#[test]
+fn overlapping_edits_merge_first_task_and_conflict_second() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    git(tmp.path(), &["init", "-q"]);
+    fs::write(tmp.path().join("shared.txt"), "base\n").unwrap();
+    git(tmp.path(), &["add", "shared.txt"]);
+    git(tmp.path(), &["commit", "-q", "-m", "base"]);
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: edit shared file\n- [ ] task-b: edit shared file too",
+    )
+    .unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n(given (task task-b))\n(given (ready task-b))\n","tasks":[{"id":"task-a","objective":"edit shared file","acceptance":"shared.txt edited","dependencies":[],"checks":["true"]},{"id":"task-b","objective":"edit shared file too","acceptance":"shared.txt edited","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  implementer)
+    echo "${THENCE_TASK_ID}" > shared.txt
+    echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    ;;
+  *) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("merge-conflict");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 2,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "merge_succeeded" && e.task_id.as_deref() == Some("task-a"))
+    );
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "merge_conflict" && e.task_id.as_deref() == Some("task-b"))
+    );
+    // The retry starts from the integration branch, so it no longer conflicts with task-a.
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == "merge_succeeded" && e.task_id.as_deref() == Some("task-b"))
+    );
+    let integrated = git(
+        tmp.path(),
+        &["show", &format!("thence/{run_id}:shared.txt")],
+    );
+    assert_eq!(integrated, "task-b\n");
+}
+
+#[test]
+fn user_files_named_like_attempt_artifacts_are_merged() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    git(tmp.path(), &["init", "-q"]);
+    fs::write(tmp.path().join("README.md"), "base\n").unwrap();
+    git(tmp.path(), &["add", "README.md"]);
+    git(tmp.path(), &["commit", "-q", "-m", "base"]);
+    fs::write(&plan_path, "- [ ] task-a: add retry helpers").unwrap();
+    fs::write(
+        &agent_path,
+        r#"#!/usr/bin/env bash
+set -euo pipefail
+case "${THENCE_ROLE:-}" in
+  plan-translator)
+    cat > "${THENCE_RESULT_FILE}" <<'JSON'
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"add retry helpers","acceptance":"helpers added","dependencies":[],"checks":["true"]}]}
+JSON
+    ;;
+  implementer)
+    echo "fn helper() {}" > foo_attempt_bar.rs
+    mkdir -p tests
+    echo "fn t() {}" > tests/max_attempts_test.rs
+    echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    ;;
+  *) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
+esac
+"#,
+    )
+    .unwrap();
+    write_repo_config(
+        tmp.path(),
+        &format!(
+            "version = 2\n[agent]\nprovider = \"codex\"\ncommand = \"bash {}\"\n[checks]\ncommands = [\"true\"]\n",
+            agent_path.display()
+        ),
+    );
+
+    let run_id = test_run_id("attempt-named-files");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: false,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+    let branch = format!("thence/{run_id}");
+    let files = git(tmp.path(), &["ls-tree", "-r", "--name-only", &branch]);
+    let files = files.lines().collect::<Vec<_>>();
+    assert!(files.contains(&"foo_attempt_bar.rs"), "{files:?}");
+    assert!(files.contains(&"tests/max_attempts_test.rs"), "{files:?}");
+    assert!(
+        files.iter().all(|f| !f.starts_with("implementer_attempt")),
+        "{files:?}"
+    );
+}
 
+#[test]
+fn dependent_task_starts_from_its_merged_dependency() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let agent_path = tmp.path().join("agent.sh");
+    git(tmp.path(), &["init", "-q"]);
+    fs::write(tmp.path().join("README.md"), "base\n").unwrap();
+    git(tmp.path(), &["add", "README.md"]);
+    git(tmp.path(), &["commit", "-q", "-m", "base"]);
+    fs::write(
+        &plan_path,
+        "- [ ] task-a: write a.txt\n- [ ] task-b: copy a.txt",
+    )
+    .unwrap();
     fs::write(
         &agent_path,
         r#"#!/usr/bin/env bash
@@ -858,23 +6906,21 @@ set -euo pipefail
 case "${THENCE_ROLE:-}" in
   plan-translator)
     cat > "${THENCE_RESULT_FILE}" <<'JSON'
-{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"implement feature","acceptance":"Complete objective: implement feature","dependencies":[],"checks":["true"]}]}
+{"spl":"(given (task task-a))\n(given (ready task-a))\n(given (task task-b))\n(given (depends-on task-b task-a))\n","tasks":[{"id":"task-a","objective":"write a.txt","acceptance":"done","dependencies":[],"checks":["true"]},{"id":"task-b","objective":"copy a.txt","acceptance":"done","dependencies":["task-a"],"checks":["true"]}]}
 JSON
     ;;
-  implementer) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
-  reviewer) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
-  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+  implementer)
+    case "${THENCE_TASK_ID}" in
+      task-a) echo "from task-a" > a.txt ;;
+      task-b) cp a.txt b.txt ;;
+    esac
+    echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    ;;
+  *) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
 esac
 "#,
     )
     .unwrap();
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&agent_path).unwrap().permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(&agent_path, perms).unwrap();
-    }
     write_repo_config(
         tmp.path(),
         &format!(
@@ -883,41 +6929,17 @@ esac
         ),
     );
 
-    answer_question(&run_id, "spec-q-translate", "retry", Some(db_path.clone())).unwrap();
-    resume_run(&run_id, Some(db_path.clone())).unwrap();
-
-    let events = EventStore::open(&db_path)
-        .unwrap()
-        .list_events(&run_id)
-        .unwrap();
-    assert!(events.iter().any(|e| e.event_type == "run_completed"));
-    let translate_question_count = events
-        .iter()
-        .filter(|e| {
-            e.event_type == "spec_question_opened"
-                && e.payload_json.get("question_id").and_then(|v| v.as_str())
-                    == Some("spec-q-translate")
-        })
-        .count();
-    assert_eq!(translate_question_count, 1);
-}
-
-#[test]
-fn translate_answer_does_not_bypass_spec_review_gate() {
-    let tmp = tempdir().unwrap();
-    let plan_path = tmp.path().join("plan.md");
-    let db_path = tmp.path().join("state.db");
-    fs::write(&plan_path, "- [ ] task-a: one\n- [ ] task_a: two").unwrap();
-
-    let run_id = test_run_id("translate-no-bypass");
-    let err = execute_run(RunCommand {
-        plan_file: plan_path.clone(),
+    let run_id = test_run_id("dependency-base");
+    execute_run(RunCommand {
+        plan_file: plan_path,
         agent: "codex".to_string(),
-        workers: 2,
+        workers: 1,
         reviewers: 1,
         checks: Some("true".to_string()),
-        simulate: true,
+        checks_from_file: None,
+        simulate: false,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -925,45 +6947,41 @@ fn translate_answer_does_not_bypass_spec_review_gate() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
-    .unwrap_err();
-    assert!(format!("{err}").contains("translation failure"));
-
-    // Fix translation issue, but keep ambiguity marker that should be caught by review gate.
-    fs::write(
-        &plan_path,
-        "- [ ] task-a: unclear behavior ???\n- [ ] task-b: follow up | deps=task-a",
-    )
-    .unwrap();
-    answer_question(
-        &run_id,
-        "spec-q-translate",
-        "retry translation",
-        Some(db_path.clone()),
-    )
     .unwrap();
-    let err = resume_run(&run_id, Some(db_path.clone())).unwrap_err();
-    assert!(format!("{err}").contains("paused"));
-
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-    assert!(events.iter().any(|e| {
-        e.event_type == "spec_question_opened"
-            && e.payload_json.get("question_id").and_then(|v| v.as_str()) == Some("spec-q-1")
-    }));
-    assert!(!events.iter().any(|e| e.event_type == "spec_approved"));
-    assert!(!events.iter().any(|e| e.event_type == "checks_approved"));
-    assert!(!events.iter().any(|e| e.event_type == "task_registered"));
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert_eq!(events.last().unwrap().event_type, "run_completed");
+    let copied = git(tmp.path(), &["show", &format!("thence/{run_id}:b.txt")]);
+    assert_eq!(copied, "from task-a\n");
 }
 
 #[test]
-fn subprocess_invalid_reviewer_output_fails_closed() {
+fn implementer_changes_are_captured_as_an_attempt_diff() {
     let tmp = tempdir().unwrap();
     let plan_path = tmp.path().join("plan.md");
     let db_path = tmp.path().join("state.db");
     let agent_path = tmp.path().join("agent.sh");
-    fs::write(&plan_path, "- [ ] task-a: run reviewer invalid output").unwrap();
+    git(tmp.path(), &["init", "-q"]);
+    fs::write(tmp.path().join("shared.txt"), "base\n").unwrap();
+    git(tmp.path(), &["add", "shared.txt"]);
+    git(tmp.path(), &["commit", "-q", "-m", "base"]);
+    fs::write(&plan_path, "- [ ] task-a: edit shared file").unwrap();
     fs::write(
         &agent_path,
         r#"#!/usr/bin/env bash
@@ -971,24 +6989,20 @@ set -euo pipefail
 case "${THENCE_ROLE:-}" in
   plan-translator)
     cat > "${THENCE_RESULT_FILE}" <<'JSON'
-{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"run reviewer invalid output","acceptance":"Complete objective: run reviewer invalid output","dependencies":[],"checks":["true"]}]}
+{"spl":"(given (task task-a))\n(given (ready task-a))\n","tasks":[{"id":"task-a","objective":"edit shared file","acceptance":"shared.txt edited","dependencies":[],"checks":["true"]}]}
 JSON
     ;;
-  implementer) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
-  reviewer) echo '{' > "${THENCE_RESULT_FILE}" ;;
-  checks-proposer) echo '{"commands":["true"],"rationale":"ok"}' > "${THENCE_RESULT_FILE}" ;;
-  *) echo '{"submitted":true}' > "${THENCE_RESULT_FILE}" ;;
+  implementer)
+    echo "changed by agent" > shared.txt
+    echo "brand new" > added.txt
+    echo "counts retries" > retry_attempt_count.rs
+    echo '{"submitted":true}' > "${THENCE_RESULT_FILE}"
+    ;;
+  *) echo '{"approved":true,"findings":[]}' > "${THENCE_RESULT_FILE}" ;;
 esac
 "#,
     )
     .unwrap();
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let mut perms = fs::metadata(&agent_path).unwrap().permissions();
-        perms.set_mode(0o755);
-        fs::set_permissions(&agent_path, perms).unwrap();
-    }
     write_repo_config(
         tmp.path(),
         &format!(
@@ -997,15 +7011,17 @@ esac
         ),
     );
 
-    let run_id = test_run_id("invalid-reviewer-json");
+    let run_id = test_run_id("attempt-diff");
     execute_run(RunCommand {
         plan_file: plan_path,
         agent: "codex".to_string(),
-        workers: 2,
+        workers: 1,
         reviewers: 1,
-        checks: Some("true".to_string()),
+        checks: None,
+        checks_from_file: None,
         simulate: false,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -1013,16 +7029,45 @@ esac
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
-    let store = EventStore::open(&db_path).unwrap();
-    let events = store.list_events(&run_id).unwrap();
-    assert!(events.iter().any(|e| e.event_type == "review_requested"));
-    assert!(events.iter().any(|e| e.event_type == "review_found_issues"));
-    assert!(events.iter().all(|e| e.event_type != "review_approved"));
-    assert!(events.iter().all(|e| e.event_type != "task_closed"));
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let submitted = events
+        .iter()
+        .find(|e| e.event_type == "work_submitted")
+        .expect("missing work_submitted");
+    let diff_path = submitted.payload_json["diff_path"].as_str().unwrap();
+    let diff = fs::read_to_string(diff_path).unwrap();
+    assert!(diff.contains("-base\n+changed by agent"), "{diff}");
+    assert!(diff.contains("+brand new"), "{diff}");
+    assert!(diff.contains("+counts retries"), "{diff}");
+    assert!(
+        !diff.contains("THENCE") && !diff.contains("implementer_attempt"),
+        "{diff}"
+    );
+
+    let capsule = read_capsule(&run_id, "task-a", 1, "reviewer", Some(db_path)).unwrap();
+    assert_eq!(
+        capsule["payload"]["submission_refs"]["work_submitted"]["diff_path"],
+        serde_json::json!(diff_path)
+    );
 }
 
 #[test]
@@ -1222,24 +7267,153 @@ fn resume_interrupts_stale_orphan_attempt_lease() {
             }),
         })
         .unwrap();
-    store
-        .append_event(
-            &run_id,
-            &NewEvent::simple("run_started", serde_json::json!({})),
-        )
-        .unwrap();
-    store
-        .append_event(
-            &run_id,
-            &NewEvent::simple("spec_approved", serde_json::json!({"approved": true})),
-        )
-        .unwrap();
-    store
-        .append_event(
-            &run_id,
-            &NewEvent::simple("checks_approved", serde_json::json!({"commands": ["true"]})),
-        )
-        .unwrap();
+    store
+        .append_event(
+            &run_id,
+            &NewEvent::simple("run_started", serde_json::json!({})),
+        )
+        .unwrap();
+    store
+        .append_event(
+            &run_id,
+            &NewEvent::simple("spec_approved", serde_json::json!({"approved": true})),
+        )
+        .unwrap();
+    store
+        .append_event(
+            &run_id,
+            &NewEvent::simple("checks_approved", serde_json::json!({"commands": ["true"]})),
+        )
+        .unwrap();
+    store
+        .append_event(
+            &run_id,
+            &NewEvent {
+                event_type: "task_registered".to_string(),
+                task_id: Some("task-a".to_string()),
+                actor_role: None,
+                actor_id: None,
+                attempt: None,
+                payload_json: serde_json::json!({
+                    "task_id": "task-a",
+                    "objective": "build parser",
+                    "acceptance": "done",
+                    "dependencies": [],
+                    "checks": ["true"]
+                }),
+                dedupe_key: Some("task_registered:task-a".to_string()),
+            },
+        )
+        .unwrap();
+    store
+        .append_event(
+            &run_id,
+            &NewEvent {
+                event_type: "task_claimed".to_string(),
+                task_id: Some("task-a".to_string()),
+                actor_role: Some("implementer".to_string()),
+                actor_id: Some("impl-1".to_string()),
+                attempt: Some(1),
+                payload_json: serde_json::json!({"attempt": 1}),
+                dedupe_key: None,
+            },
+        )
+        .unwrap();
+
+    let lease_path = run_dir
+        .join("leases")
+        .join("task-a")
+        .join("attempt1")
+        .join("implementer.json");
+    fs::create_dir_all(lease_path.parent().unwrap()).unwrap();
+    let stale = (chrono::Utc::now() - chrono::Duration::seconds(300)).to_rfc3339();
+    fs::write(
+        &lease_path,
+        serde_json::json!({
+            "version": 1,
+            "run_id": run_id.clone(),
+            "task_id": "task-a",
+            "attempt": 1,
+            "role": "implementer",
+            "owner_pid": 999999,
+            "started_at": stale,
+            "last_seen_at": stale,
+            "state": "active"
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    resume_run(&run_id, Some(db_path.clone())).unwrap();
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    assert!(events.iter().any(|e| e.event_type == "attempt_interrupted"));
+}
+
+#[test]
+fn resume_reclaims_an_attempt_that_crashed_before_its_lease_was_written() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "Implement a tiny parser with tests.").unwrap();
+
+    let run_id = test_run_id("claim-no-lease");
+    let run_dir = tmp.path().join(".thence").join("runs").join(&run_id);
+    fs::create_dir_all(&run_dir).unwrap();
+    let spl_path = run_dir.join("plan.spl");
+    fs::write(&spl_path, "(given (task task-a))\n(given (ready task-a))\n").unwrap();
+    fs::write(
+        run_dir.join("spec.md"),
+        "Implement a tiny parser with tests.",
+    )
+    .unwrap();
+    fs::write(
+        run_dir.join("translated_plan.json"),
+        r#"{
+  "tasks": [
+    {"id":"task-a","objective":"build parser","acceptance":"done","dependencies":[],"checks":["true"]}
+  ],
+  "spl": "(given (task task-a))\n(given (ready task-a))\n"
+}"#,
+    )
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    store
+        .create_run(&RunRow {
+            id: run_id.clone(),
+            plan_path: plan_path.display().to_string(),
+            plan_sha256: "abc".to_string(),
+            spl_plan_path: spl_path.display().to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            status: "running".to_string(),
+            config_json: serde_json::json!({
+                "agent": "codex",
+                "workers": 1,
+                "reviewers": 1,
+                "checks": ["true"],
+                "checks_from_cli": true,
+                "simulate": true,
+                "allow_partial_completion": false,
+                "trust_plan_checks": false,
+                "interactive": false,
+                "max_attempts": 3,
+                "check_timeout_secs": 60,
+                "attempt_timeout_secs": 120
+            }),
+        })
+        .unwrap();
+    for (event_type, payload) in [
+        ("run_started", serde_json::json!({})),
+        ("spec_approved", serde_json::json!({"approved": true})),
+        ("checks_approved", serde_json::json!({"commands": ["true"]})),
+    ] {
+        store
+            .append_event(&run_id, &NewEvent::simple(event_type, payload))
+            .unwrap();
+    }
     store
         .append_event(
             &run_id,
@@ -1274,37 +7448,29 @@ fn resume_interrupts_stale_orphan_attempt_lease() {
             },
         )
         .unwrap();
-
-    let lease_path = run_dir
-        .join("leases")
-        .join("task-a")
-        .join("attempt1")
-        .join("implementer.json");
-    fs::create_dir_all(lease_path.parent().unwrap()).unwrap();
-    let stale = (chrono::Utc::now() - chrono::Duration::seconds(300)).to_rfc3339();
-    fs::write(
-        &lease_path,
-        serde_json::json!({
-            "version": 1,
-            "run_id": run_id.clone(),
-            "task_id": "task-a",
-            "attempt": 1,
-            "role": "implementer",
-            "owner_pid": 999999,
-            "started_at": stale,
-            "last_seen_at": stale,
-            "state": "active"
-        })
-        .to_string(),
-    )
-    .unwrap();
+    // The crash hit mid-write: only the temp file of the lease exists.
+    let lease_dir = run_dir.join("leases").join("task-a").join("attempt1");
+    fs::create_dir_all(&lease_dir).unwrap();
+    fs::write(lease_dir.join("implementer.tmp-999999"), "{\"version\": 1,").unwrap();
 
     resume_run(&run_id, Some(db_path.clone())).unwrap();
-    let events = EventStore::open(&db_path)
-        .unwrap()
-        .list_events(&run_id)
-        .unwrap();
-    assert!(events.iter().any(|e| e.event_type == "attempt_interrupted"));
+
+    let events = store.list_events(&run_id).unwrap();
+    let interrupted = events
+        .iter()
+        .find(|e| e.event_type == "attempt_interrupted")
+        .expect("missing attempt_interrupted");
+    assert_eq!(interrupted.attempt, Some(1));
+    assert_eq!(interrupted.payload_json["lease"]["state"], "missing");
+    let position = |event_type: &str, attempt: i64| {
+        events
+            .iter()
+            .position(|e| e.event_type == event_type && e.attempt == Some(attempt))
+            .unwrap_or_else(|| panic!("missing {event_type} for attempt {attempt}"))
+    };
+    assert!(position("attempt_interrupted", 1) < position("task_claimed", 2));
+    assert!(position("task_claimed", 2) < position("task_closed", 2));
+    assert_eq!(events.last().unwrap().event_type, "run_completed");
 }
 
 #[test]
@@ -1330,8 +7496,10 @@ fn worktree_provision_symlink_makes_env_available_to_checks() {
         workers: 1,
         reviewers: 1,
         checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -1339,7 +7507,19 @@ fn worktree_provision_symlink_makes_env_available_to_checks() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
@@ -1396,8 +7576,10 @@ fn worktree_provision_missing_required_source_fails_attempt() {
         workers: 1,
         reviewers: 1,
         checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -1405,7 +7587,19 @@ fn worktree_provision_missing_required_source_fails_attempt() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
@@ -1457,8 +7651,10 @@ fn worktree_provision_missing_optional_source_is_skipped() {
         workers: 1,
         reviewers: 1,
         checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -1466,7 +7662,19 @@ fn worktree_provision_missing_optional_source_is_skipped() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
@@ -1519,8 +7727,10 @@ fn worktree_provision_copy_mode_creates_independent_file() {
         workers: 1,
         reviewers: 1,
         checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -1528,7 +7738,19 @@ fn worktree_provision_copy_mode_creates_independent_file() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
@@ -1589,8 +7811,10 @@ fn worktree_provision_applies_across_attempts() {
         workers: 1,
         reviewers: 1,
         checks: None,
+        checks_from_file: None,
         simulate: true,
         log: None,
+        log_format: LogFormat::Compact,
         resume: false,
         run_id: Some(run_id.clone()),
         state_db: Some(db_path.clone()),
@@ -1598,7 +7822,19 @@ fn worktree_provision_applies_across_attempts() {
         trust_plan_checks: false,
         interactive: false,
         attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
         debug_dump_spl: None,
+        artifacts_dir: None,
     })
     .unwrap();
 
@@ -1659,3 +7895,446 @@ fn worktree_provision_applies_across_attempts() {
     );
     assert!(events.iter().any(|e| e.event_type == "run_completed"));
 }
+
+#[test]
+fn list_runs_orders_newest_first_and_filters_by_status() {
+    let tmp = tempdir().unwrap();
+    let db_path = tmp.path().join("state.db");
+    let store = EventStore::open(&db_path).unwrap();
+
+    for (id, created_at, status) in [
+        ("run-old", "2026-01-01T00:00:00+00:00", "completed"),
+        ("run-new", "2026-01-03T00:00:00+00:00", "running"),
+        ("run-mid", "2026-01-02T00:00:00+00:00", "failed"),
+    ] {
+        store
+            .create_run(&RunRow {
+                id: id.to_string(),
+                plan_path: "plan.md".to_string(),
+                plan_sha256: "abc".to_string(),
+                spl_plan_path: "plan.spl".to_string(),
+                created_at: created_at.to_string(),
+                status: status.to_string(),
+                config_json: serde_json::json!({}),
+            })
+            .unwrap();
+    }
+
+    let all = store
+        .list_all_runs()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.id)
+        .collect::<Vec<_>>();
+    assert_eq!(all, vec!["run-new", "run-mid", "run-old"]);
+
+    let running = store.list_runs(Some("running")).unwrap();
+    assert_eq!(running.len(), 1);
+    assert_eq!(running[0].id, "run-new");
+
+    list_runs(Some("failed"), Some(db_path)).unwrap();
+}
+
+#[test]
+fn resumable_runs_report_phase_questions_and_current_attempt() {
+    let tmp = tempdir().unwrap();
+    let store = EventStore::open(&tmp.path().join("state.db")).unwrap();
+    for (id, created_at) in [
+        ("run-question", "2026-01-01T00:00:00+00:00"),
+        ("run-working", "2026-01-02T00:00:00+00:00"),
+    ] {
+        store
+            .create_run(&RunRow {
+                id: id.to_string(),
+                plan_path: "plan.md".to_string(),
+                plan_sha256: "abc".to_string(),
+                spl_plan_path: "plan.spl".to_string(),
+                created_at: created_at.to_string(),
+                status: "running".to_string(),
+                config_json: serde_json::json!({}),
+            })
+            .unwrap();
+    }
+    store
+        .append_event(
+            "run-question",
+            &NewEvent::simple(
+                "spec_question_opened",
+                serde_json::json!({"question_id": "spec-q-1", "question": "which db?"}),
+            ),
+        )
+        .unwrap();
+    for event in [
+        NewEvent::simple(
+            "task_registered",
+            serde_json::json!({"task_id": "task_a", "objective": "build"}),
+        ),
+        NewEvent::simple("spec_approved", serde_json::json!({})),
+        NewEvent::simple("checks_approved", serde_json::json!({"commands": ["true"]})),
+        NewEvent {
+            event_type: "task_claimed".to_string(),
+            task_id: Some("task_a".to_string()),
+            actor_role: Some("implementer".to_string()),
+            actor_id: Some("impl-1".to_string()),
+            attempt: Some(1),
+            payload_json: serde_json::json!({"attempt": 1}),
+            dedupe_key: None,
+        },
+    ] {
+        store.append_event("run-working", &event).unwrap();
+    }
+
+    let mut out = Vec::new();
+    write_resumable_runs(&store, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let line = |id: &str| out.lines().find(|l| l.starts_with(id)).unwrap().to_string();
+    let question = line("run-question");
+    assert!(question.contains("paused_for_question"));
+    assert_eq!(question.split_whitespace().nth(2), Some("1"));
+    let working = line("run-working");
+    assert!(working.contains("implementation_loop"));
+    assert!(working.ends_with("task=task_a attempt=1"));
+}
+
+#[test]
+fn cancel_paused_run_marks_terminal_and_rejects_second_cancel() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: This spec is ambiguous ???").unwrap();
+
+    let run_id = test_run_id("cancel");
+    let err = execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap_err();
+    assert!(format!("{err}").contains("paused"));
+
+    cancel_run(&run_id, Some(db_path.clone())).unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let state = RunProjection::replay(&store.list_events(&run_id).unwrap());
+    assert_eq!(state.terminal.as_deref(), Some("run_cancelled"));
+    assert_eq!(store.get_run(&run_id).unwrap().unwrap().status, "cancelled");
+    assert!(!store.list_resumable_run_ids().unwrap().contains(&run_id));
+
+    let err = cancel_run(&run_id, Some(db_path)).unwrap_err();
+    assert!(format!("{err}").contains("already terminal"));
+}
+
+#[test]
+fn delete_run_removes_terminal_run_and_requires_force_otherwise() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let mut run_ids = Vec::new();
+    for (prefix, dry_run) in [("delete-done", false), ("delete-open", true)] {
+        let run_id = test_run_id(prefix);
+        execute_run(RunCommand {
+            plan_file: plan_path.clone(),
+            agent: "codex".to_string(),
+            workers: 1,
+            reviewers: 1,
+            checks: Some("true".to_string()),
+            checks_from_file: None,
+            simulate: true,
+            log: None,
+            log_format: LogFormat::Compact,
+            resume: false,
+            run_id: Some(run_id.clone()),
+            state_db: Some(db_path.clone()),
+            allow_partial_completion: false,
+            trust_plan_checks: false,
+            interactive: false,
+            attempt_timeout_secs: None,
+            reviewer_timeout_secs: None,
+            max_attempts: None,
+            check_timeout_secs: None,
+            agent_spec_review: false,
+            no_spec_review: false,
+            propose_checks: false,
+            no_checks_file: false,
+            reconfigure_checks: false,
+            worktree_keep_on_fail: false,
+            dry_run,
+            only_task: Vec::new(),
+            debug_dump_spl: None,
+            artifacts_dir: None,
+        })
+        .unwrap();
+        run_ids.push(run_id);
+    }
+    let (done, open) = (&run_ids[0], &run_ids[1]);
+
+    let store = EventStore::open(&db_path).unwrap();
+    assert_eq!(store.get_run(done).unwrap().unwrap().status, "completed");
+    delete_run(done, false, Some(db_path.clone())).unwrap();
+    assert!(store.get_run(done).unwrap().is_none());
+    assert!(store.list_events(done).unwrap().is_empty());
+
+    let err = delete_run(open, false, Some(db_path.clone())).unwrap_err();
+    assert!(format!("{err}").contains("not terminal"));
+    assert!(!store.list_events(open).unwrap().is_empty());
+    delete_run(open, true, Some(db_path.clone())).unwrap();
+    assert!(store.get_run(open).unwrap().is_none());
+
+    let err = delete_run(done, false, Some(db_path)).unwrap_err();
+    assert!(format!("{err}").contains("run not found"));
+}
+
+#[test]
+fn export_round_trips_full_event_rows() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run_id = test_run_id("export");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+
+    let mut json_out = Vec::new();
+    write_events_export(&events, "json", &mut json_out).unwrap();
+    let from_json: Vec<EventRow> = serde_json::from_slice(&json_out).unwrap();
+    assert_eq!(from_json, events);
+
+    let mut ndjson_out = Vec::new();
+    write_events_export(&events, "ndjson", &mut ndjson_out).unwrap();
+    let from_ndjson = String::from_utf8(ndjson_out)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<EventRow>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(from_ndjson, events);
+}
+
+#[test]
+fn tail_prints_every_event_of_a_completed_run() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run_id = test_run_id("tail");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let store = EventStore::open(&db_path).unwrap();
+    let events = store.list_events(&run_id).unwrap();
+    let mut out = Vec::new();
+    tail_events(
+        &store,
+        &run_id,
+        false,
+        std::time::Duration::from_millis(10),
+        &mut out,
+    )
+    .unwrap();
+    let out = String::from_utf8(out).unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), events.len());
+    for (line, ev) in lines.iter().zip(&events) {
+        assert!(line.contains(&ev.event_type), "{line}");
+    }
+    assert!(out.contains("task=task_a"));
+    assert!(lines.last().unwrap().contains("run_completed"));
+}
+
+#[test]
+fn read_capsule_returns_implementer_capsule_for_attempt() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run_id = test_run_id("show-capsule");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: None,
+        log_format: LogFormat::Compact,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let capsule = read_capsule(&run_id, "task_a", 1, "implementer", Some(db_path.clone())).unwrap();
+    assert_eq!(capsule["task_id"], "task_a");
+    assert_eq!(capsule["attempt"], 1);
+
+    let err = read_capsule(&run_id, "task_a", 2, "implementer", Some(db_path)).unwrap_err();
+    assert!(format!("{err}").contains("no implementer capsule for task task_a attempt 2"));
+}
+
+#[test]
+fn full_log_format_mirrors_complete_event_rows() {
+    let tmp = tempdir().unwrap();
+    let plan_path = tmp.path().join("plan.md");
+    let db_path = tmp.path().join("state.db");
+    let log_path = tmp.path().join("events.ndjson");
+    fs::write(&plan_path, "- [ ] task-a: implement feature").unwrap();
+
+    let run_id = test_run_id("full-log");
+    execute_run(RunCommand {
+        plan_file: plan_path,
+        agent: "codex".to_string(),
+        workers: 1,
+        reviewers: 1,
+        checks: Some("true".to_string()),
+        checks_from_file: None,
+        simulate: true,
+        log: Some(log_path.clone()),
+        log_format: LogFormat::Full,
+        resume: false,
+        run_id: Some(run_id.clone()),
+        state_db: Some(db_path.clone()),
+        allow_partial_completion: false,
+        trust_plan_checks: false,
+        interactive: false,
+        attempt_timeout_secs: None,
+        reviewer_timeout_secs: None,
+        max_attempts: None,
+        check_timeout_secs: None,
+        agent_spec_review: false,
+        no_spec_review: false,
+        propose_checks: false,
+        no_checks_file: false,
+        reconfigure_checks: false,
+        worktree_keep_on_fail: false,
+        dry_run: false,
+        only_task: Vec::new(),
+        debug_dump_spl: None,
+        artifacts_dir: None,
+    })
+    .unwrap();
+
+    let events = EventStore::open(&db_path)
+        .unwrap()
+        .list_events(&run_id)
+        .unwrap();
+    let mirrored = fs::read_to_string(&log_path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<EventRow>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(mirrored, events);
+}
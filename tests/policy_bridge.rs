@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, HashSet};
 use thence::events::projector::{RunProjection, TaskProjection};
-use thence::policy::spindle_bridge::derive_policy_state;
+use thence::policy::spindle_bridge::{derive_policy_state, validate_extra_rules};
 
 #[test]
 fn policy_marks_basic_task_claimable() {
@@ -13,6 +13,7 @@ fn policy_marks_basic_task_claimable() {
         terminal: None,
         tasks: BTreeMap::new(),
         open_questions: Default::default(),
+        last_translation_error: None,
     };
     run.tasks.insert(
         "t1".to_string(),
@@ -20,22 +21,28 @@ fn policy_marks_basic_task_claimable() {
             id: "t1".to_string(),
             objective: "o".to_string(),
             acceptance: "a".to_string(),
+            acceptance_criteria: vec![],
             dependencies: vec![],
             required_checks: vec![],
+            priority: 0,
             attempts: 0,
             claimed: false,
             latest_attempt: 0,
             review_approved_attempts: HashSet::new(),
             checks_passed_attempts: HashSet::new(),
+            failed_checks: BTreeMap::new(),
             unresolved_findings_attempts: HashSet::new(),
             merged_attempts: HashSet::new(),
             closed: false,
             terminal_failed: false,
+            skipped: false,
+            deadline_secs: None,
+            first_claimed_at: None,
         },
     );
 
     let plan = "(given (task t1))\n(given (ready t1))\n";
-    let snap = derive_policy_state(&run, plan).unwrap();
+    let snap = derive_policy_state(&run, plan, None).unwrap();
     assert!(snap.claimable.contains("t1"));
 }
 
@@ -50,6 +57,7 @@ fn policy_marks_dependency_task_claimable_after_dependency_closes() {
         terminal: None,
         tasks: BTreeMap::new(),
         open_questions: Default::default(),
+        last_translation_error: None,
     };
     run.tasks.insert(
         "task_a".to_string(),
@@ -57,17 +65,23 @@ fn policy_marks_dependency_task_claimable_after_dependency_closes() {
             id: "task_a".to_string(),
             objective: "a".to_string(),
             acceptance: "a".to_string(),
+            acceptance_criteria: vec![],
             dependencies: vec![],
             required_checks: vec![],
+            priority: 0,
             attempts: 1,
             claimed: false,
             latest_attempt: 1,
             review_approved_attempts: HashSet::from([1]),
             checks_passed_attempts: HashSet::from([1]),
+            failed_checks: BTreeMap::new(),
             unresolved_findings_attempts: HashSet::new(),
             merged_attempts: HashSet::from([1]),
             closed: true,
             terminal_failed: false,
+            skipped: false,
+            deadline_secs: None,
+            first_claimed_at: None,
         },
     );
     run.tasks.insert(
@@ -76,17 +90,23 @@ fn policy_marks_dependency_task_claimable_after_dependency_closes() {
             id: "task_b".to_string(),
             objective: "b".to_string(),
             acceptance: "b".to_string(),
+            acceptance_criteria: vec![],
             dependencies: vec!["task_a".to_string()],
             required_checks: vec![],
+            priority: 0,
             attempts: 0,
             claimed: false,
             latest_attempt: 0,
             review_approved_attempts: HashSet::new(),
             checks_passed_attempts: HashSet::new(),
+            failed_checks: BTreeMap::new(),
             unresolved_findings_attempts: HashSet::new(),
             merged_attempts: HashSet::new(),
             closed: false,
             terminal_failed: false,
+            skipped: false,
+            deadline_secs: None,
+            first_claimed_at: None,
         },
     );
 
@@ -96,6 +116,36 @@ fn policy_marks_dependency_task_claimable_after_dependency_closes() {
 (given (task task_b))
 (always r-ready-task_b (closed task_a) (ready task_b))
 "#;
-    let snap = derive_policy_state(&run, plan).unwrap();
+    let snap = derive_policy_state(&run, plan, None).unwrap();
     assert!(snap.claimable.contains("task_b"));
 }
+
+#[test]
+fn extra_policy_rules_reject_imports() {
+    validate_extra_rules(
+        "(always hold (task t1) (claim-blocked t1))\n; (import \"ok in a comment\")\n",
+    )
+    .unwrap();
+    let err = validate_extra_rules("(import \"other.spl\")\n").unwrap_err();
+    assert!(format!("{err}").contains("(import ...)"), "{err}");
+}
+
+#[test]
+fn policy_parse_error_names_the_malformed_section() {
+    let run = RunProjection {
+        run_id: "r3".to_string(),
+        spec_approved: true,
+        checks_approved: true,
+        ..RunProjection::default()
+    };
+
+    let plan = "(given (task t1))\n(given (ready t1)\n";
+    let err = derive_policy_state(&run, plan, None).unwrap_err();
+    let msg = format!("{err:#}");
+    assert!(
+        msg.contains("policy SPL parse failed in the translated plan facts/rules section"),
+        "{msg}"
+    );
+    assert!(msg.contains("| (given (ready t1)"), "{msg}");
+    assert!(!msg.contains("policy-claimable"), "{msg}");
+}
@@ -64,3 +64,91 @@ fn checks_question_events_do_not_open_projected_questions() {
     let state = RunProjection::replay(&events);
     assert!(state.open_questions.is_empty());
 }
+
+#[test]
+fn replay_from_snapshot_matches_full_replay() {
+    let event =
+        |seq: i64, event_type: &str, attempt: Option<i64>, payload: serde_json::Value| EventRow {
+            seq,
+            run_id: "r1".to_string(),
+            ts: format!("2026-02-20T00:00:{seq:02}Z"),
+            event_type: event_type.to_string(),
+            task_id: payload
+                .get("task_id")
+                .and_then(|v| v.as_str())
+                .map(ToString::to_string),
+            actor_role: None,
+            actor_id: None,
+            attempt,
+            payload_json: payload,
+            dedupe_key: None,
+        };
+    let events = vec![
+        event(1, "spec_approved", None, serde_json::json!({})),
+        event(
+            2,
+            "checks_approved",
+            None,
+            serde_json::json!({"commands":["true"]}),
+        ),
+        event(
+            3,
+            "task_registered",
+            None,
+            serde_json::json!({"task_id":"t1","objective":"one","dependencies":[],"checks":[]}),
+        ),
+        event(
+            4,
+            "task_registered",
+            None,
+            serde_json::json!({"task_id":"t2","objective":"two","dependencies":["t1"],"checks":[]}),
+        ),
+        event(
+            5,
+            "task_claimed",
+            Some(1),
+            serde_json::json!({"task_id":"t1"}),
+        ),
+        event(
+            6,
+            "review_approved",
+            Some(1),
+            serde_json::json!({"task_id":"t1"}),
+        ),
+        event(
+            7,
+            "checks_reported",
+            Some(1),
+            serde_json::json!({"task_id":"t1","passed":true}),
+        ),
+        event(
+            8,
+            "merge_succeeded",
+            Some(1),
+            serde_json::json!({"task_id":"t1"}),
+        ),
+        event(
+            9,
+            "task_closed",
+            Some(1),
+            serde_json::json!({"task_id":"t1"}),
+        ),
+        event(
+            10,
+            "task_claimed",
+            Some(1),
+            serde_json::json!({"task_id":"t2"}),
+        ),
+    ];
+
+    let full = RunProjection::replay(&events);
+    for split in 0..events.len() {
+        let snapshot = RunProjection::replay(&events[..split]);
+        let snapshot_seq = events[..split].last().map(|e| e.seq).unwrap_or(0);
+        let resumed = RunProjection::replay_from((snapshot_seq, snapshot), &events);
+        assert_eq!(
+            resumed, full,
+            "mismatch when snapshotting after {split} events"
+        );
+    }
+}